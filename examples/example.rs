@@ -2,7 +2,9 @@ mod util;
 
 use crate::util::StatefulTree;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -91,8 +93,8 @@ fn run_app<'a, B: Backend>(terminal: &mut Terminal<B>, mut app: App<'a>) -> io::
             f.render_stateful_widget(items, area, &mut app.tree.state);
         })?;
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
+        match event::read()? {
+            Event::Key(key) => match key.code {
                 KeyCode::Char('q') => return Ok(()),
                 KeyCode::Char('a') => {
                     app.tree.with_selected_leaf(|node| {
@@ -109,7 +111,16 @@ fn run_app<'a, B: Backend>(terminal: &mut Terminal<B>, mut app: App<'a>) -> io::
                 KeyCode::Home => app.tree.first(),
                 KeyCode::End => app.tree.last(),
                 _ => {}
-            }
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    app.tree.state.click_at((mouse.column, mouse.row));
+                }
+                MouseEventKind::ScrollDown => app.tree.state.scroll_down(&app.tree.items, 1),
+                MouseEventKind::ScrollUp => app.tree.state.scroll_up(&app.tree.items, 1),
+                _ => {}
+            },
+            _ => {}
         }
     }
 }
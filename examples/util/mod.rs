@@ -1,8 +1,8 @@
-use tui_tree_widget::{TreeItem, TreeState, TreeItemRender};
+use tui_tree_widget::{TreeItem, TreeItemRender, TreeItems, TreeState};
 
 pub struct StatefulTree<A> {
     pub state: TreeState,
-    pub items: Vec<TreeItem<A>>,
+    pub items: TreeItems<A>,
 }
 
 impl<A: TreeItemRender> StatefulTree<A> {
@@ -10,14 +10,14 @@ impl<A: TreeItemRender> StatefulTree<A> {
     pub fn new() -> Self {
         Self {
             state: TreeState::default(),
-            items: Vec::new(),
+            items: TreeItems::new(),
         }
     }
 
     pub fn with_items(items: Vec<TreeItem<A>>) -> Self {
         Self {
             state: TreeState::default(),
-            items,
+            items: items.into(),
         }
     }
 
@@ -41,7 +41,7 @@ impl<A: TreeItemRender> StatefulTree<A> {
         self.state.key_left();
     }
 
-    pub fn right<'b>(&'b mut self) {
+    pub fn right(&mut self) {
         self.state.key_right();
     }
 
@@ -49,29 +49,7 @@ impl<A: TreeItemRender> StatefulTree<A> {
         self.state.toggle_selected();
     }
 
-    fn items_mut<'b>(&'b mut self) -> &'b mut Vec<TreeItem<A>> {
-        &mut self.items
-    }
-
-    pub fn with_selected_leaf<'b>(&'b mut self, f: impl FnOnce(Option<&'b mut TreeItem<A>>))
-    where
-    {
-        fn traverse<'short, A: TreeItemRender>(
-            path: Vec<usize>,
-            nodes: &'short mut [TreeItem<A>],
-        ) -> Option<&'short mut TreeItem<A>>
-        {
-            let first = path.first()?;
-            let node = nodes.get_mut(*first)?;
-            if path.len() == 1 {
-                Some(node)
-            } else {
-                traverse(path[1..].to_owned(), node.children_mut())
-            }
-        }
-
-        let res = traverse(self.state.selected(), self.items_mut());
-
-        f(res)
+    pub fn with_selected_leaf<'b>(&'b mut self, f: impl FnOnce(Option<&'b mut TreeItem<A>>)) {
+        f(self.state.selected_item_mut(&mut self.items));
     }
 }
@@ -0,0 +1,118 @@
+use crate::error::{resolve, resolve_mut, TreeError};
+use crate::identifier::TreeIdentifier;
+use crate::TreeItem;
+
+/// Lookups and changes of items by their identifier.
+///
+/// These only change the items.
+/// Identifiers stored within a [`TreeState`](crate::TreeState) still reference the old
+/// positions afterwards, see [`tree_ops`](crate::tree_ops) for changes which keep it in sync.
+impl<A> TreeItem<A> {
+    /// Get the [`TreeItem`] referenced by the identifier within the items.
+    /// Returns `None` when the identifier is empty or does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeItem;
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// assert!(TreeItem::get(&items, &[0, 0]).is_some());
+    /// assert!(TreeItem::get(&items, &[0, 1]).is_none());
+    /// ```
+    pub fn get<'a>(items: &'a [Self], identifier: TreeIdentifier) -> Option<&'a Self> {
+        resolve(items, identifier).ok()
+    }
+
+    /// Same as [`get`](Self::get) but the [`TreeItem`] is borrowed mutably
+    pub fn get_mut<'a>(items: &'a mut [Self], identifier: TreeIdentifier) -> Option<&'a mut Self> {
+        resolve_mut(items, identifier).ok()
+    }
+
+    /// Remove the [`TreeItem`] referenced by the identifier from its parent and return it.
+    /// The following siblings move up by one.
+    /// Returns `None` when the identifier is empty or does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeItem;
+    /// let mut items = vec![TreeItem::new(
+    ///     "a",
+    ///     vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("c")],
+    /// )];
+    /// let removed = TreeItem::remove(&mut items, &[0, 0]).unwrap();
+    /// assert!(removed.children().is_empty());
+    /// assert_eq!(items[0].children().len(), 1);
+    /// ```
+    pub fn remove(items: &mut Vec<Self>, identifier: TreeIdentifier) -> Option<Self> {
+        let (index, parent) = identifier.split_last()?;
+        let siblings = siblings_mut(items, parent).ok()?;
+        (*index < siblings.len()).then(|| siblings.remove(*index))
+    }
+
+    /// Insert the item so it is referenced by the identifier afterwards.
+    /// The following siblings move down by one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier is empty, its parent does not exist or its last
+    /// index is larger than the amount of children of the parent.
+    /// The items are not changed then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeItem;
+    /// let mut items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("c")])];
+    /// TreeItem::insert_at(&mut items, &[0, 0], TreeItem::new_leaf("b")).unwrap();
+    /// assert_eq!(items[0].children().len(), 2);
+    /// assert!(TreeItem::insert_at(&mut items, &[0, 5], TreeItem::new_leaf("d")).is_err());
+    /// ```
+    pub fn insert_at(
+        items: &mut Vec<Self>,
+        identifier: TreeIdentifier,
+        item: Self,
+    ) -> Result<(), TreeError> {
+        let (index, parent) = identifier
+            .split_last()
+            .ok_or(TreeError::InvalidIdentifier)?;
+        let siblings = siblings_mut(items, parent)?;
+        if *index > siblings.len() {
+            return Err(TreeError::OutOfBounds {
+                identifier: identifier.to_vec(),
+                depth: parent.len(),
+            });
+        }
+        siblings.insert(*index, item);
+        Ok(())
+    }
+}
+
+/// Children of the parent, or the root items when the parent is empty
+fn siblings_mut<'a, A>(
+    items: &'a mut Vec<TreeItem<A>>,
+    parent: TreeIdentifier,
+) -> Result<&'a mut Vec<TreeItem<A>>, TreeError> {
+    if parent.is_empty() {
+        Ok(items)
+    } else {
+        Ok(&mut *resolve_mut(items, parent)?.children)
+    }
+}
+
+#[test]
+fn remove_and_insert_round_trip() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let removed = TreeItem::remove(&mut items, &[1, 1]).unwrap();
+    assert_eq!(removed.elem, "d");
+    assert_eq!(items[1].children[1].elem, "g");
+    assert!(TreeItem::remove(&mut items, &[1, 5]).is_none());
+    assert!(TreeItem::remove(&mut items, &[]).is_none());
+
+    TreeItem::insert_at(&mut items, &[3], removed).unwrap();
+    assert_eq!(TreeItem::get(&items, &[3, 1]).unwrap().elem, "f");
+    assert_eq!(
+        TreeItem::insert_at(&mut items, &[], TreeItem::new_leaf("x")).unwrap_err(),
+        TreeError::InvalidIdentifier
+    );
+}
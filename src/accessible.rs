@@ -0,0 +1,57 @@
+use crate::flatten::flatten_state;
+use crate::{text_to_string, Tree, TreeItemRender, TreeState};
+
+/// Visible row described without any glyphs, returned by [`Tree::to_accessible_lines`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessibleRow {
+    /// Depth within the tree, 0 for root items
+    pub depth: usize,
+    /// Whether the children are shown, `None` for leaves
+    pub expanded: Option<bool>,
+    pub selected: bool,
+    /// Rendered text of the item, lines joined by `\n`
+    pub text: String,
+}
+
+impl<'a, A: TreeItemRender> Tree<'a, A> {
+    /// Describe the visible rows by their structure instead of indentation and symbols.
+    ///
+    /// For screen reader bridges or logs which announce `level 2, collapsed, selected` rather
+    /// than reading out arrows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{AccessibleRow, Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.select(vec![0, 0]);
+    ///
+    /// let rows = Tree::new(items).to_accessible_lines(&state);
+    /// assert_eq!(
+    ///     rows[1],
+    ///     AccessibleRow {
+    ///         depth: 1,
+    ///         expanded: None,
+    ///         selected: true,
+    ///         text: "b".to_string(),
+    ///     }
+    /// );
+    /// assert_eq!(rows[0].expanded, Some(true));
+    /// ```
+    pub fn to_accessible_lines(&self, state: &TreeState) -> Vec<AccessibleRow> {
+        flatten_state(state, &self.items)
+            .iter()
+            .map(|row| AccessibleRow {
+                depth: row.depth(),
+                expanded: row
+                    .item
+                    .has_children()
+                    .then(|| state.is_expanded(&row.identifier, row.item)),
+                selected: row.identifier == state.selected,
+                text: text_to_string(&row.item.elem.as_text()),
+            })
+            .collect()
+    }
+}
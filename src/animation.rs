@@ -0,0 +1,52 @@
+use crate::TreeState;
+
+impl TreeState {
+    /// Frame of the [arrow animation](crate::Tree::arrow_animation) the node currently shows.
+    /// `None` when the node was not opened recently or the animation is over.
+    pub(crate) fn arrow_frame<'f>(
+        &self,
+        identifier: &[usize],
+        frames: &[&'f str],
+    ) -> Option<&'f str> {
+        self.opening
+            .get(identifier)
+            .and_then(|shown| frames.get(*shown))
+            .copied()
+    }
+
+    /// Advance the animation of every recently opened node by one frame.
+    /// Nodes which have shown all frames are forgotten.
+    pub(crate) fn advance_arrow_animation(&mut self, frames: usize) {
+        self.opening.retain(|_, shown| {
+            *shown += 1;
+            *shown < frames
+        });
+    }
+}
+
+#[test]
+fn arrow_animation_shows_every_frame_once() {
+    let frames = ["1", "2", "3"];
+    let mut state = TreeState::default();
+    state.open(vec![0]);
+    let mut shown = Vec::new();
+    while let Some(frame) = state.arrow_frame(&[0], &frames) {
+        shown.push(frame);
+        state.advance_arrow_animation(frames.len());
+    }
+    assert_eq!(shown, frames);
+    assert!(state.opening.is_empty());
+}
+
+#[test]
+fn reopening_restarts_the_animation() {
+    let frames = ["1", "2"];
+    let mut state = TreeState::default();
+    state.open(vec![0]);
+    state.advance_arrow_animation(frames.len());
+    assert!(!state.open(vec![0]));
+    assert_eq!(state.arrow_frame(&[0], &frames), Some("2"));
+    state.close(&[0]);
+    state.open(vec![0]);
+    assert_eq!(state.arrow_frame(&[0], &frames), Some("1"));
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use crate::error::resolve;
+use crate::identifier::TreeIdentifierVec;
+use crate::{TreeItem, TreeState};
+
+/// Inconsistency between a [`TreeState`] and the items it is used with, see
+/// [`TreeState::audit`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StateIssue {
+    /// An opened node does not exist in the items
+    StaleOpened(TreeIdentifierVec),
+    /// An opened node has no children
+    OpenedLeaf(TreeIdentifierVec),
+    /// The selected node does not exist in the items
+    SelectionOutOfRange(TreeIdentifierVec),
+}
+
+impl fmt::Display for StateIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StaleOpened(identifier) => {
+                write!(f, "the opened node {:?} does not exist", identifier)
+            }
+            Self::OpenedLeaf(identifier) => {
+                write!(f, "the opened node {:?} has no children", identifier)
+            }
+            Self::SelectionOutOfRange(identifier) => {
+                write!(f, "the selected node {:?} does not exist", identifier)
+            }
+        }
+    }
+}
+
+impl TreeState {
+    /// Check the state against the items and report every inconsistency.
+    ///
+    /// The state is not changed.
+    /// Useful in debug builds or a debug overlay when a tree behaves unexpectedly, usually
+    /// because the items changed without updating the state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{StateIssue, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.open(vec![0, 0]);
+    /// state.open(vec![3]);
+    /// state.select(vec![0, 2]);
+    ///
+    /// assert_eq!(
+    ///     state.audit(&items),
+    ///     [
+    ///         StateIssue::SelectionOutOfRange(vec![0, 2]),
+    ///         StateIssue::OpenedLeaf(vec![0, 0]),
+    ///         StateIssue::StaleOpened(vec![3]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn audit<A>(&self, items: &[TreeItem<A>]) -> Vec<StateIssue> {
+        let mut issues = Vec::new();
+        if !self.selected.is_empty() && resolve(items, &self.selected).is_err() {
+            issues.push(StateIssue::SelectionOutOfRange(self.selected.clone()));
+        }
+
+        let mut opened = self.opened.iter().collect::<Vec<_>>();
+        opened.sort_unstable();
+        for identifier in opened {
+            match resolve(items, &identifier) {
+                Ok(item) if !item.has_children() => {
+                    issues.push(StateIssue::OpenedLeaf(identifier));
+                }
+                Ok(_) => {}
+                Err(_) => issues.push(StateIssue::StaleOpened(identifier)),
+            }
+        }
+        issues
+    }
+}
+
+#[test]
+fn consistent_state_has_no_issues() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert!(state.audit(&items).is_empty());
+
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+    assert!(state.audit(&items).is_empty());
+
+    assert_eq!(
+        state.audit(&items[..1]),
+        [
+            StateIssue::SelectionOutOfRange(vec![1, 1, 0]),
+            StateIssue::StaleOpened(vec![1]),
+            StateIssue::StaleOpened(vec![1, 1]),
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+use crate::error::resolve;
+use crate::items::IterDepthFirst;
+use crate::{TreeItem, TreeItemRender};
+
+/// Partially filled cells from one eighth to seven eighths
+const EIGHTHS: [char; 7] = [
+    '\u{258f}', '\u{258e}', '\u{258d}', '\u{258c}', '\u{258b}', '\u{258a}', '\u{2589}',
+];
+
+/// Which values the bars of [`Tree::value_bars`](crate::Tree::value_bars) are relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarScale {
+    /// The largest value among the siblings fills the bar, like the directory sizes of `ncdu`
+    Siblings,
+    /// The largest value of the whole tree fills the bar
+    Global,
+}
+
+impl Default for BarScale {
+    fn default() -> Self {
+        Self::Siblings
+    }
+}
+
+impl BarScale {
+    /// Largest value the value of the item at the identifier is relative to.
+    /// `global` is the largest value of the whole tree.
+    pub(crate) fn max<A: TreeItemRender>(
+        self,
+        items: &[TreeItem<A>],
+        identifier: &[usize],
+        global: f64,
+    ) -> f64 {
+        match self {
+            Self::Siblings => {
+                let siblings = match identifier.split_last() {
+                    Some((_, [])) | None => items,
+                    Some((_, parent)) => resolve(items, parent).map_or(items, |o| &o.children),
+                };
+                max_value(siblings.iter())
+            }
+            Self::Global => global,
+        }
+    }
+}
+
+/// Largest value of all items of the tree
+pub(crate) fn global_max<A: TreeItemRender>(items: &[TreeItem<A>]) -> f64 {
+    max_value(IterDepthFirst::new(items).map(|(_, item)| item))
+}
+
+fn max_value<'a, A: TreeItemRender + 'a>(items: impl Iterator<Item = &'a TreeItem<A>>) -> f64 {
+    items
+        .filter_map(|item| item.elem.as_value())
+        .fold(0.0, f64::max)
+}
+
+/// Bar of the width filled by the fraction.
+/// With `partial` the last cell is filled in eighths, otherwise only whole cells are filled.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub(crate) fn bar(fraction: f64, width: u16, partial: bool) -> String {
+    let width = usize::from(width);
+    let eighths = (fraction.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+    let (full, rest) = if partial {
+        (eighths / 8, eighths % 8)
+    } else {
+        ((eighths + 4) / 8, 0)
+    };
+    let mut bar = "\u{2588}".repeat(full);
+    if rest > 0 {
+        bar.push(EIGHTHS[rest - 1]);
+    }
+    format!("{:<width$}", bar, width = width)
+}
+
+#[test]
+fn bar_fills_in_eighths() {
+    assert_eq!(bar(0.0, 3, true), "   ");
+    assert_eq!(bar(1.0, 3, true), "\u{2588}\u{2588}\u{2588}");
+    assert_eq!(bar(0.5, 3, true), "\u{2588}\u{258c} ");
+    assert_eq!(bar(0.5, 3, false), "\u{2588}\u{2588} ");
+    assert_eq!(bar(2.0, 2, true), "\u{2588}\u{2588}");
+}
@@ -0,0 +1,153 @@
+use tui::style::Style;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::{error, Tree, TreeItem, TreeState};
+
+impl TreeState {
+    /// Bookmark the selected node.
+    /// Returns `true` when the node was not bookmarked before.
+    ///
+    /// Cycle through the bookmarks with [`next_bookmark`](Self::next_bookmark) and
+    /// [`prev_bookmark`](Self::prev_bookmark).
+    /// The [`Tree`] shows them with its [`bookmark_symbol`](Tree::bookmark_symbol).
+    /// Bookmarks follow their nodes when the items are [reconciled](Self::reconcile).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![
+    ///     TreeItem::new_leaf("a"),
+    ///     TreeItem::new("b", vec![TreeItem::new_leaf("c")]),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// state.select(vec![0]);
+    /// state.bookmark_selected();
+    /// state.select(vec![1, 0]);
+    /// state.bookmark_selected();
+    ///
+    /// assert!(state.next_bookmark(&items));
+    /// assert_eq!(state.selected(), [0]);
+    /// assert!(state.next_bookmark(&items));
+    /// assert_eq!(state.selected(), [1, 0]);
+    /// assert_eq!(state.get_all_opened(), [vec![1]]);
+    /// ```
+    pub fn bookmark_selected(&mut self) -> bool {
+        !self.selected.is_empty() && self.bookmarks.insert(self.selected.clone())
+    }
+
+    /// Remove the bookmark of the node.
+    /// Returns `true` when the node was bookmarked.
+    pub fn remove_bookmark(&mut self, identifier: &[usize]) -> bool {
+        self.bookmarks.remove(identifier)
+    }
+
+    pub fn is_bookmarked(&self, identifier: &[usize]) -> bool {
+        self.bookmarks.contains(identifier)
+    }
+
+    /// All bookmarked nodes in the order of their identifiers
+    pub fn bookmarks(&self) -> Vec<TreeIdentifierVec> {
+        self.bookmarks.iter().cloned().collect()
+    }
+
+    /// Select the next bookmark after the selection in the order of the items and open its
+    /// ancestors.
+    /// Wraps around to the first bookmark.
+    /// Bookmarks of nodes which do not exist anymore are skipped.
+    ///
+    /// Returns `true` when a bookmark was selected.
+    pub fn next_bookmark<A>(&mut self, items: &[TreeItem<A>]) -> bool {
+        let existing = self.existing_bookmarks(items);
+        let next = existing
+            .iter()
+            .find(|identifier| **identifier > self.selected)
+            .or_else(|| existing.first());
+        self.select_bookmark(next.cloned())
+    }
+
+    /// Select the previous bookmark before the selection in the order of the items and open
+    /// its ancestors.
+    /// Wraps around to the last bookmark.
+    /// Bookmarks of nodes which do not exist anymore are skipped.
+    ///
+    /// Returns `true` when a bookmark was selected.
+    pub fn prev_bookmark<A>(&mut self, items: &[TreeItem<A>]) -> bool {
+        let existing = self.existing_bookmarks(items);
+        let previous = existing
+            .iter()
+            .rev()
+            .find(|identifier| **identifier < self.selected)
+            .or_else(|| existing.last());
+        self.select_bookmark(previous.cloned())
+    }
+
+    fn existing_bookmarks<A>(&self, items: &[TreeItem<A>]) -> Vec<TreeIdentifierVec> {
+        self.bookmarks
+            .iter()
+            .filter(|identifier| error::resolve(items, identifier).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    fn select_bookmark(&mut self, identifier: Option<TreeIdentifierVec>) -> bool {
+        match identifier {
+            Some(identifier) => {
+                self.reveal(identifier);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Reserve a gutter column in front of the indentation of every row and show the symbol in
+    /// the style for [bookmarked](TreeState::bookmark_selected) nodes
+    #[must_use]
+    pub const fn bookmark_symbol(mut self, symbol: &'a str, style: Style) -> Self {
+        self.bookmark_symbol = Some((symbol, style));
+        self
+    }
+}
+
+#[test]
+fn prev_bookmark_wraps_and_skips_missing() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert!(!state.bookmark_selected());
+    assert!(!state.prev_bookmark(&items));
+
+    for identifier in [vec![0], vec![1, 1, 0], vec![5]] {
+        state.select(identifier);
+        assert!(state.bookmark_selected());
+    }
+    state.select(vec![1]);
+    assert!(state.prev_bookmark(&items));
+    assert_eq!(state.selected(), [0]);
+    assert!(state.prev_bookmark(&items));
+    assert_eq!(state.selected(), [1, 1, 0]);
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+
+    assert!(state.remove_bookmark(&[1, 1, 0]));
+    assert!(!state.is_bookmarked(&[1, 1, 0]));
+    assert_eq!(state.bookmarks(), [vec![0], vec![5]]);
+}
+
+#[test]
+fn bookmarks_follow_reconciled_nodes() {
+    let old = crate::flatten::get_example_tree_items();
+    let mut new = crate::flatten::get_example_tree_items();
+    new[1].children.remove(1);
+    new.swap(0, 2);
+
+    let mut state = TreeState::default();
+    for identifier in [vec![0], vec![1, 1, 0], vec![1, 2]] {
+        state.select(identifier);
+        state.bookmark_selected();
+    }
+    state.reconcile(&old, &new, crate::ReconcileStrategy::Text);
+    assert_eq!(state.bookmarks(), [vec![1, 1], vec![2]]);
+}
@@ -0,0 +1,64 @@
+use crate::error::resolve;
+use crate::{text_to_string, Tree, TreeItem, TreeItemRender};
+
+const SEPARATOR: &str = " \u{25b8} ";
+
+impl<'a, A> Tree<'a, A> {
+    /// Replace the first line with a breadcrumb of the ancestors of the first rendered row when
+    /// they are scrolled off, like `src ▸ widgets ▸ …`.
+    ///
+    /// Keeps the context visible when scrolled deep into a subtree while only taking a single
+    /// line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "src",
+    ///     vec![TreeItem::new_leaf("a.rs"), TreeItem::new_leaf("b.rs")],
+    /// )];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.select(vec![0, 1]);
+    ///
+    /// let area = Rect::new(0, 0, 10, 2);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .ancestor_breadcrumb(true)
+    ///     .render(area, &mut buffer, &mut state);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["src ▸ …   ", "    b.rs  "]));
+    /// ```
+    #[must_use]
+    pub const fn ancestor_breadcrumb(mut self, enabled: bool) -> Self {
+        self.ancestor_breadcrumb = enabled;
+        self
+    }
+}
+
+/// First lines of the labels of the ancestors of the node followed by an ellipsis
+pub(crate) fn breadcrumb<A: TreeItemRender>(items: &[TreeItem<A>], identifier: &[usize]) -> String {
+    let mut result = String::new();
+    for length in 1..identifier.len() {
+        if let Ok(item) = resolve(items, &identifier[..length]) {
+            let label = text_to_string(&item.elem.as_text());
+            result += label.lines().next().unwrap_or_default();
+            result += SEPARATOR;
+        }
+    }
+    result.push('\u{2026}');
+    result
+}
+
+#[test]
+fn breadcrumb_of_nested_node() {
+    let items = crate::flatten::get_example_tree_items();
+    assert_eq!(
+        breadcrumb(&items, &[1, 1, 0]),
+        "b \u{25b8} d \u{25b8} \u{2026}"
+    );
+    assert_eq!(breadcrumb(&items, &[1]), "\u{2026}");
+}
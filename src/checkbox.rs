@@ -0,0 +1,363 @@
+use std::ops::Bound;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::error::{resolve, TreeError};
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{Tree, TreeItem, TreeState};
+
+/// Checkbox state of a node, see [`TreeState::toggle_checked`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CheckState {
+    Unchecked,
+    /// Some but not all descendants are checked
+    Partial,
+    Checked,
+}
+
+/// Symbols of the checkboxes shown by [`Tree::checkboxes`]
+///
+/// The symbols should have the same width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckSymbols<'a> {
+    pub unchecked: &'a str,
+    pub partial: &'a str,
+    pub checked: &'a str,
+}
+
+impl<'a> Default for CheckSymbols<'a> {
+    fn default() -> Self {
+        Self {
+            unchecked: "[ ] ",
+            partial: "[-] ",
+            checked: "[x] ",
+        }
+    }
+}
+
+impl<'a> CheckSymbols<'a> {
+    pub(crate) const fn symbol(&self, state: CheckState) -> &'a str {
+        match state {
+            CheckState::Unchecked => self.unchecked,
+            CheckState::Partial => self.partial,
+            CheckState::Checked => self.checked,
+        }
+    }
+
+    /// Width of the widest symbol
+    pub(crate) fn width(&self) -> usize {
+        self.unchecked
+            .width()
+            .max(self.partial.width())
+            .max(self.checked.width())
+    }
+}
+
+impl TreeState {
+    /// Check the node and all its descendants, or uncheck them when the node is checked.
+    ///
+    /// Ancestors are checked when all their children are checked and are shown as
+    /// [partially](CheckState::Partial) checked when only some descendants are.
+    /// Returns the new state of the node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{CheckState, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "src",
+    ///     vec![TreeItem::new_leaf("lib.rs"), TreeItem::new_leaf("main.rs")],
+    /// )];
+    /// let mut state = TreeState::default();
+    ///
+    /// assert_eq!(state.toggle_checked(&items, &[0, 0]), Ok(CheckState::Checked));
+    /// assert_eq!(state.check_state(&[0]), CheckState::Partial);
+    ///
+    /// state.toggle_checked(&items, &[0, 1]).unwrap();
+    /// assert_eq!(state.check_state(&[0]), CheckState::Checked);
+    ///
+    /// state.toggle_checked(&items, &[0]).unwrap();
+    /// assert!(state.checked().is_empty());
+    /// ```
+    pub fn toggle_checked<A>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: TreeIdentifier,
+    ) -> Result<CheckState, TreeError> {
+        let item = resolve(items, identifier)?;
+        let check = self.check_state(identifier) != CheckState::Checked;
+
+        let mut stack = vec![(identifier.to_vec(), item)];
+        while let Some((identifier, item)) = stack.pop() {
+            for (index, child) in item.children.iter().enumerate() {
+                let mut child_identifier = identifier.clone();
+                child_identifier.push(index);
+                stack.push((child_identifier, child));
+            }
+            if check {
+                self.checked.insert(identifier);
+            } else {
+                self.checked.remove(&identifier);
+            }
+        }
+
+        for length in (1..identifier.len()).rev() {
+            let parent = &identifier[..length];
+            let children = resolve(items, parent)?.children.len();
+            let all_checked = (0..children).all(|index| {
+                let mut child = parent.to_vec();
+                child.push(index);
+                self.checked.contains(&child)
+            });
+            if all_checked {
+                self.checked.insert(parent.to_vec());
+            } else {
+                self.checked.remove(parent);
+            }
+        }
+        Ok(self.check_state(identifier))
+    }
+
+    pub fn check_state(&self, identifier: TreeIdentifier) -> CheckState {
+        if self.checked.contains(identifier) {
+            return CheckState::Checked;
+        }
+        // Descendants directly follow their ancestor in the order of the identifiers
+        let has_checked_descendant = self
+            .checked
+            .range::<[usize], _>((Bound::Excluded(identifier), Bound::Unbounded))
+            .next()
+            .map_or(false, |next| next.starts_with(identifier));
+        if has_checked_descendant {
+            CheckState::Partial
+        } else {
+            CheckState::Unchecked
+        }
+    }
+
+    /// All checked nodes in the order of their identifiers, including checked ancestors
+    pub fn checked(&self) -> Vec<TreeIdentifierVec> {
+        self.checked.iter().cloned().collect()
+    }
+
+    /// Checked nodes without children in the order of their identifiers.
+    ///
+    /// Identifiers not referencing an existing node are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![
+    ///     TreeItem::new(
+    ///         "src",
+    ///         vec![TreeItem::new_leaf("lib.rs"), TreeItem::new_leaf("main.rs")],
+    ///     ),
+    ///     TreeItem::new_leaf("README.md"),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// state.toggle_checked(&items, &[0]).unwrap();
+    /// state.toggle_checked(&items, &[1]).unwrap();
+    ///
+    /// assert_eq!(state.checked_leaves(&items), [vec![0, 0], vec![0, 1], vec![1]]);
+    /// assert_eq!(state.checked_roots(&items), [vec![0], vec![1]]);
+    /// ```
+    pub fn checked_leaves<A>(&self, items: &[TreeItem<A>]) -> Vec<TreeIdentifierVec> {
+        self.checked
+            .iter()
+            .filter(|identifier| {
+                resolve(items, identifier).map_or(false, |item| !item.has_children())
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Smallest set of checked nodes covering every checked node: checked nodes whose parent is
+    /// not checked, in the order of their identifiers.
+    ///
+    /// Identifiers not referencing an existing node are skipped.
+    pub fn checked_roots<A>(&self, items: &[TreeItem<A>]) -> Vec<TreeIdentifierVec> {
+        let mut result: Vec<TreeIdentifierVec> = Vec::new();
+        for identifier in &self.checked {
+            // Descendants directly follow their ancestor in the order of the identifiers
+            let is_covered = result
+                .last()
+                .map_or(false, |root| identifier.starts_with(root));
+            if !is_covered && resolve(items, identifier).is_ok() {
+                result.push(identifier.clone());
+            }
+        }
+        result
+    }
+
+    pub fn clear_checked(&mut self) {
+        self.checked.clear();
+    }
+
+    /// Check the parents whose children are all checked and uncheck the others, after the
+    /// children changed like when [reconciling](Self::reconcile)
+    pub(crate) fn sync_checked_parents<A>(&mut self, items: &[TreeItem<A>]) {
+        // Post-order with an explicit stack so deep trees can not overflow the stack.
+        // A parent is visited again after its children are synced.
+        let mut stack = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (vec![index], item, false))
+            .collect::<Vec<_>>();
+        while let Some((identifier, item, children_synced)) = stack.pop() {
+            if item.children.is_empty() {
+                continue;
+            }
+            if children_synced {
+                let all_checked = (0..item.children.len()).all(|index| {
+                    let mut child = identifier.clone();
+                    child.push(index);
+                    self.checked.contains(&child)
+                });
+                if all_checked {
+                    self.checked.insert(identifier);
+                } else {
+                    self.checked.remove(&identifier);
+                }
+            } else if self.check_state(&identifier) != CheckState::Unchecked {
+                stack.push((identifier.clone(), item, true));
+                for (index, child) in item.children.iter().enumerate() {
+                    let mut child_identifier = identifier.clone();
+                    child_identifier.push(index);
+                    stack.push((child_identifier, child, false));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Show a checkbox with the [`CheckState`] of each node between its open/close symbol and
+    /// its label.
+    ///
+    /// Clicks on a checkbox are reported as [`HitTarget::Checkbox`](crate::HitTarget::Checkbox)
+    /// by [`TreeState::hit_test`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{CheckSymbols, HitTarget, Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("c")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.toggle_checked(&items, &[0, 1]).unwrap();
+    ///
+    /// let area = Rect::new(0, 0, 10, 3);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .checkboxes(CheckSymbols::default())
+    ///     .render(area, &mut buffer, &mut state);
+    /// let expected = Buffer::with_lines(vec!["▼ [-] a   ", "    [ ] b ", "    [x] c "]);
+    /// assert_eq!(buffer, expected);
+    /// assert_eq!(state.hit_test((5, 1)), Some((vec![0, 0], HitTarget::Checkbox)));
+    /// ```
+    #[must_use]
+    pub const fn checkboxes(mut self, symbols: CheckSymbols<'a>) -> Self {
+        self.check_symbols = Some(symbols);
+        self
+    }
+}
+
+#[test]
+fn unchecking_a_child_makes_the_parent_partial() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.toggle_checked(&items, &[1]), Ok(CheckState::Checked));
+    assert_eq!(state.check_state(&[1, 1, 0]), CheckState::Checked);
+
+    assert_eq!(
+        state.toggle_checked(&items, &[1, 1, 1]),
+        Ok(CheckState::Unchecked)
+    );
+    assert_eq!(state.check_state(&[1, 1]), CheckState::Partial);
+    assert_eq!(state.check_state(&[1]), CheckState::Partial);
+    assert_eq!(state.check_state(&[1, 0]), CheckState::Checked);
+    assert_eq!(state.check_state(&[0]), CheckState::Unchecked);
+    assert!(state.toggle_checked(&items, &[4]).is_err());
+}
+
+#[test]
+fn checked_roots_cover_checked_descendants() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.toggle_checked(&items, &[1, 1]).unwrap();
+    state.toggle_checked(&items, &[1, 2]).unwrap();
+    state.toggle_checked(&items, &[2]).unwrap();
+
+    assert_eq!(
+        state.checked_roots(&items),
+        [vec![1, 1], vec![1, 2], vec![2]]
+    );
+    assert_eq!(
+        state.checked_leaves(&items),
+        [vec![1, 1, 0], vec![1, 1, 1], vec![1, 2], vec![2]]
+    );
+
+    state.toggle_checked(&items, &[1, 0]).unwrap();
+    assert_eq!(state.checked_roots(&items), [vec![1], vec![2]]);
+    assert_eq!(state.checked_roots(&items[..1]), Vec::<Vec<usize>>::new());
+}
+
+#[test]
+fn unloaded_lazy_nodes_are_no_leaves() {
+    let items = vec![TreeItem::new_lazy("a"), TreeItem::new_leaf("b")];
+    let mut state = TreeState::default();
+    state.toggle_checked(&items, &[0]).unwrap();
+    state.toggle_checked(&items, &[1]).unwrap();
+    assert_eq!(state.checked_leaves(&items), [vec![1]]);
+}
+
+#[test]
+fn reconcile_updates_checked_parents() {
+    let old = vec![TreeItem::new(
+        "a",
+        vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("c")],
+    )];
+    let mut state = TreeState::default();
+    state.toggle_checked(&old, &[0, 1]).unwrap();
+
+    let new = vec![
+        TreeItem::new_leaf("x"),
+        TreeItem::new("a", vec![TreeItem::new_leaf("c")]),
+    ];
+    state.reconcile(&old, &new, crate::ReconcileStrategy::Text);
+    assert_eq!(state.checked(), [vec![1], vec![1, 0]]);
+    assert_eq!(state.check_state(&[1]), CheckState::Checked);
+
+    let newer = vec![TreeItem::new(
+        "a",
+        vec![TreeItem::new_leaf("c"), TreeItem::new_leaf("d")],
+    )];
+    state.reconcile(&new, &newer, crate::ReconcileStrategy::Text);
+    assert_eq!(state.checked(), [vec![0, 0]]);
+    assert_eq!(state.check_state(&[0]), CheckState::Partial);
+}
+
+#[test]
+fn deep_tree_syncs_parents_on_small_stack() {
+    let depth = 1_000;
+    let thread = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            let items = crate::flatten::deep_tree_items(depth);
+            let mut state = TreeState::default();
+            state.checked.insert(vec![0; depth + 1]);
+            state.sync_checked_parents(&items);
+            assert_eq!(state.checked().len(), depth + 1);
+            assert_eq!(state.check_state(&[0]), CheckState::Checked);
+        })
+        .unwrap();
+    thread.join().unwrap();
+}
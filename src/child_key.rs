@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter::FromIterator;
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{TreeItemSource, TreeState};
+
+/// Reference to a child of a parent by its position or by a key of the data source
+///
+/// See [`TreeItemSource::child_key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ChildKey {
+    /// Position of the child within its parent
+    Index(usize),
+    /// Stable key of the child provided by the data source
+    Key(u64),
+}
+
+/// Identifier of a node made of [`ChildKey`s](ChildKey) instead of positions
+pub type KeyedIdentifier = Vec<ChildKey>;
+
+/// Nodes stored within a [`TreeState`] referenced by [`ChildKey`s](ChildKey)
+///
+/// Created by [`TreeState::export_keyed`] and restored by [`TreeState::import_keyed`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeyedState {
+    pub opened: Vec<KeyedIdentifier>,
+    pub selected: KeyedIdentifier,
+    pub bookmarks: Vec<KeyedIdentifier>,
+    /// Tagged nodes per tag
+    pub tags: BTreeMap<String, Vec<KeyedIdentifier>>,
+    pub checked: Vec<KeyedIdentifier>,
+    pub multi_selected: Vec<KeyedIdentifier>,
+    /// Pairs of [linked](TreeState::link) nodes
+    pub links: Vec<(KeyedIdentifier, KeyedIdentifier)>,
+}
+
+fn keyed<S: TreeItemSource>(source: &S, identifier: TreeIdentifier) -> KeyedIdentifier {
+    (0..identifier.len())
+        .map(|depth| {
+            let index = identifier[depth];
+            source
+                .child_key(&identifier[..depth], index)
+                .map_or(ChildKey::Index(index), ChildKey::Key)
+        })
+        .collect()
+}
+
+/// Resolve the keys to an identifier.
+/// Returns the resolved part which is shorter than the keys when a key was not found.
+fn resolve_keyed<S: TreeItemSource>(source: &S, keyed: &[ChildKey]) -> TreeIdentifierVec {
+    let mut result = Vec::with_capacity(keyed.len());
+    for key in keyed {
+        let index = match key {
+            ChildKey::Index(index) => Some(*index).filter(|i| *i < source.child_count(&result)),
+            ChildKey::Key(key) => source.index_of_key(&result, *key),
+        };
+        match index {
+            Some(index) => result.push(index),
+            None => break,
+        }
+    }
+    result
+}
+
+/// Resolve the keys to an identifier when every key was found
+fn resolve_keyed_fully<S: TreeItemSource>(
+    source: &S,
+    keyed: &[ChildKey],
+) -> Option<TreeIdentifierVec> {
+    let identifier = resolve_keyed(source, keyed);
+    (!identifier.is_empty() && identifier.len() == keyed.len()).then(|| identifier)
+}
+
+/// Resolve the keyed identifiers which are found completely
+fn resolve_all<S, C>(source: &S, keyed: &[KeyedIdentifier]) -> C
+where
+    S: TreeItemSource,
+    C: FromIterator<TreeIdentifierVec>,
+{
+    keyed
+        .iter()
+        .filter_map(|keyed| resolve_keyed_fully(source, keyed))
+        .collect()
+}
+
+/// Keyed identifiers in a stable order
+fn keyed_sorted<'a, S, I>(source: &S, identifiers: I) -> Vec<KeyedIdentifier>
+where
+    S: TreeItemSource,
+    I: IntoIterator<Item = &'a TreeIdentifierVec>,
+{
+    let mut result = identifiers
+        .into_iter()
+        .map(|identifier| keyed(source, identifier))
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+impl TreeState {
+    /// Reference the opened and selected nodes by the [`ChildKey`s](ChildKey) of the source.
+    /// Bookmarks, tags, checked, multi selected and linked nodes are referenced the same way.
+    ///
+    /// Positions of children are unstable when the source receives them unordered or
+    /// paginated.
+    /// Export the state before the children of the source change and
+    /// [import](Self::import_keyed) it afterwards to keep the opened and selected nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeIdentifier, TreeItemSource, TreeState};
+    /// struct Page(Vec<u64>);
+    ///
+    /// impl TreeItemSource for Page {
+    ///     type Elem = &'static str;
+    ///
+    ///     fn child_count(&self, parent: TreeIdentifier) -> usize {
+    ///         if parent.is_empty() { self.0.len() } else { 0 }
+    ///     }
+    ///
+    ///     fn child(&self, _parent: TreeIdentifier, _index: usize) -> Self::Elem {
+    ///         "item"
+    ///     }
+    ///
+    ///     fn child_key(&self, _parent: TreeIdentifier, index: usize) -> Option<u64> {
+    ///         self.0.get(index).copied()
+    ///     }
+    ///
+    ///     fn index_of_key(&self, _parent: TreeIdentifier, key: u64) -> Option<usize> {
+    ///         self.0.iter().position(|id| *id == key)
+    ///     }
+    /// }
+    ///
+    /// let mut state = TreeState::default();
+    /// state.select(vec![1]);
+    /// let keyed = state.export_keyed(&Page(vec![10, 20]));
+    ///
+    /// // The backend delivers the next page with another order
+    /// state.import_keyed(&Page(vec![30, 20, 10]), &keyed);
+    /// assert_eq!(state.selected(), [1]);
+    /// state.import_keyed(&Page(vec![20, 30, 10]), &keyed);
+    /// assert_eq!(state.selected(), [0]);
+    /// ```
+    pub fn export_keyed<S: TreeItemSource>(&self, source: &S) -> KeyedState {
+        let mut opened = self
+            .opened
+            .iter()
+            .map(|identifier| keyed(source, &identifier))
+            .collect::<Vec<_>>();
+        opened.sort();
+        let links = self
+            .links
+            .iter()
+            .flat_map(|(a, linked)| linked.iter().filter(move |b| a < *b).map(move |b| (a, b)))
+            .map(|(a, b)| (keyed(source, a), keyed(source, b)))
+            .collect();
+        KeyedState {
+            opened,
+            selected: keyed(source, &self.selected),
+            bookmarks: keyed_sorted(source, &self.bookmarks),
+            tags: self
+                .tags
+                .iter()
+                .map(|(tag, tagged)| (tag.clone(), keyed_sorted(source, tagged)))
+                .collect(),
+            checked: keyed_sorted(source, &self.checked),
+            multi_selected: keyed_sorted(source, &self.selected_multi),
+            links,
+        }
+    }
+
+    /// Restore a [`KeyedState`] created by [`export_keyed`](Self::export_keyed) with the
+    /// current children of the source.
+    ///
+    /// Opened nodes which can not be resolved are closed and other nodes which can not be
+    /// resolved are dropped.
+    /// When the selected node can not be resolved its closest resolvable ancestor is selected.
+    /// Everything else referencing positions like [changed](Self::mark_changed) or
+    /// [transient](Self::collapse_transient) nodes and [child orders](Self::set_child_order)
+    /// is dropped as it would reference other nodes now.
+    pub fn import_keyed<S: TreeItemSource>(&mut self, source: &S, keyed: &KeyedState) {
+        self.remap_retain(&|_| None);
+        self.opened = resolve_all(source, &keyed.opened);
+        self.bookmarks = resolve_all(source, &keyed.bookmarks);
+        self.tags = keyed
+            .tags
+            .iter()
+            .map(|(tag, tagged)| (tag.clone(), resolve_all::<_, BTreeSet<_>>(source, tagged)))
+            .filter(|(_, tagged)| !tagged.is_empty())
+            .collect();
+        self.checked = resolve_all(source, &keyed.checked);
+        self.selected_multi = resolve_all(source, &keyed.multi_selected);
+        for (a, b) in &keyed.links {
+            if let (Some(a), Some(b)) = (
+                resolve_keyed_fully(source, a),
+                resolve_keyed_fully(source, b),
+            ) {
+                self.link(a, b);
+            }
+        }
+        self.select(resolve_keyed(source, &keyed.selected));
+    }
+}
+
+#[test]
+fn positional_without_keys() {
+    use crate::source::ExampleSource;
+
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![5]);
+    state.select(vec![1, 1, 7]);
+    let keyed = state.export_keyed(&ExampleSource);
+    assert_eq!(
+        keyed.selected,
+        [ChildKey::Index(1), ChildKey::Index(1), ChildKey::Index(7)]
+    );
+
+    state.import_keyed(&ExampleSource, &keyed);
+    assert_eq!(state.selected(), [1, 1]);
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+}
+
+#[cfg(test)]
+struct Keyed(Vec<u64>);
+
+#[cfg(test)]
+impl TreeItemSource for Keyed {
+    type Elem = &'static str;
+
+    fn child_count(&self, parent: TreeIdentifier) -> usize {
+        if parent.is_empty() {
+            self.0.len()
+        } else {
+            0
+        }
+    }
+
+    fn child(&self, _parent: TreeIdentifier, _index: usize) -> Self::Elem {
+        "item"
+    }
+
+    fn child_key(&self, _parent: TreeIdentifier, index: usize) -> Option<u64> {
+        self.0.get(index).copied()
+    }
+
+    fn index_of_key(&self, _parent: TreeIdentifier, key: u64) -> Option<usize> {
+        self.0.iter().position(|id| *id == key)
+    }
+}
+
+#[test]
+fn stored_nodes_follow_their_keys() {
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    state.bookmark_selected();
+    state.toggle_multi_select();
+    state.tag(vec![1], "tag");
+    state.tag(vec![2], "gone");
+    state.checked.insert(vec![1]);
+    state.link(vec![0], vec![1]);
+    state.mark_changed(vec![0], std::time::Instant::now());
+    let keyed = state.export_keyed(&Keyed(vec![10, 20, 30]));
+
+    state.import_keyed(&Keyed(vec![20, 10]), &keyed);
+    assert_eq!(state.selected(), [1]);
+    assert_eq!(state.bookmarks(), [vec![1]]);
+    assert!(state.is_multi_selected(&[1]));
+    assert_eq!(state.tagged("tag"), [vec![0]]);
+    assert!(state.tagged("gone").is_empty());
+    assert_eq!(state.checked(), [vec![0]]);
+    assert_eq!(state.links(&[1]), [vec![0]]);
+    assert!(state.changed.is_empty());
+}
@@ -0,0 +1,86 @@
+use arboard::Clipboard;
+
+use crate::{TreeItem, TreeState};
+
+impl TreeState {
+    /// Copy the text created by the formatter for the selected node to the system clipboard,
+    /// for example when pressing `y`.
+    ///
+    /// The formatter gets the elements of the selected node and its ancestors, starting at the
+    /// root, so it can copy the text of the node as well as its whole path.
+    /// Returns `Ok(false)` without touching the clipboard when the selected node does not exist.
+    ///
+    /// The [`Clipboard`] is owned by the caller and has to be kept alive, ideally for as long as
+    /// the app runs.
+    /// On Linux with X11 or Wayland the copied text is served by the process owning the
+    /// clipboard, so it can not be pasted anymore once the [`Clipboard`] is dropped.
+    ///
+    /// Requires the `clipboard` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the text could not be written to the system clipboard.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use arboard::Clipboard;
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// // Created once when the app starts
+    /// let mut clipboard = Clipboard::new()?;
+    ///
+    /// let items = vec![TreeItem::new("src", vec![TreeItem::new_leaf("lib.rs")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.select(vec![0, 0]);
+    ///
+    /// // Copies "src/lib.rs"
+    /// state.copy_selected_to_clipboard(&mut clipboard, &items, |path| {
+    ///     let labels = path.iter().map(|elem| elem.to_string()).collect::<Vec<_>>();
+    ///     labels.join("/")
+    /// })?;
+    /// # Ok::<(), arboard::Error>(())
+    /// ```
+    pub fn copy_selected_to_clipboard<A, F>(
+        &self,
+        clipboard: &mut Clipboard,
+        items: &[TreeItem<A>],
+        formatter: F,
+    ) -> Result<bool, arboard::Error>
+    where
+        F: FnOnce(&[&A]) -> String,
+    {
+        let path = match self.selected_elems(items) {
+            Some(path) => path,
+            None => return Ok(false),
+        };
+        clipboard.set_text(formatter(&path))?;
+        Ok(true)
+    }
+
+    /// Elements of the selected node and its ancestors, starting at the root
+    fn selected_elems<'a, A>(&self, items: &'a [TreeItem<A>]) -> Option<Vec<&'a A>> {
+        if self.selected.is_empty() {
+            return None;
+        }
+        let mut result = Vec::with_capacity(self.selected.len());
+        let mut items = items;
+        for index in &self.selected {
+            let item = items.get(*index)?;
+            result.push(&item.elem);
+            items = &item.children;
+        }
+        Some(result)
+    }
+}
+
+#[test]
+fn selected_elems_start_at_the_root() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.selected_elems(&items), None);
+    state.select(vec![1, 1, 0]);
+    assert_eq!(state.selected_elems(&items), Some(vec![&"b", &"d", &"e"]));
+    state.select(vec![1, 7]);
+    assert_eq!(state.selected_elems(&items), None);
+}
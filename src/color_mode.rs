@@ -0,0 +1,205 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Color;
+
+use crate::Tree;
+
+/// Colors the terminal is able to show, see [`Tree::color_mode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorMode {
+    /// Every color is shown as it is
+    TrueColor,
+    /// RGB colors are replaced by the closest of the 256 indexed colors
+    Indexed256,
+    /// All colors are replaced by the closest of the 16 named colors
+    Basic16,
+    /// All colors are reset to the terminal default, modifiers are kept
+    NoColor,
+}
+
+impl Default for ColorMode {
+    fn default() -> Self {
+        Self::TrueColor
+    }
+}
+
+/// RGB values of the 16 named colors as used by xterm
+const BASIC: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of the 6x6x6 color cube of the indexed colors 16 to 231
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn closest_cube_level(value: u8) -> usize {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|level| (i32::from(CUBE_LEVELS[*level]) - i32::from(value)).abs())
+        .unwrap_or_default()
+}
+
+/// RGB value of an indexed color
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC[usize::from(index)].1,
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[usize::from(index / 36)],
+                CUBE_LEVELS[usize::from(index / 6 % 6)],
+                CUBE_LEVELS[usize::from(index % 6)],
+            )
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Closest of the indexed colors 16 to 255
+fn closest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    let (r, g, b) = (
+        closest_cube_level(rgb.0),
+        closest_cube_level(rgb.1),
+        closest_cube_level(rgb.2),
+    );
+    let cube = 16 + 36 * r as u8 + 6 * g as u8 + b as u8;
+    let average = (u32::from(rgb.0) + u32::from(rgb.1) + u32::from(rgb.2)) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    if distance(indexed_rgb(gray), rgb) < distance(indexed_rgb(cube), rgb) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn closest_basic(rgb: (u8, u8, u8)) -> Color {
+    BASIC
+        .iter()
+        .min_by_key(|(_, basic)| distance(*basic, rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+impl ColorMode {
+    /// The closest color which can be shown in this mode
+    pub fn degrade(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::TrueColor, _) | (_, Color::Reset) => color,
+            (Self::NoColor, _) => Color::Reset,
+            (Self::Indexed256, Color::Rgb(r, g, b)) => Color::Indexed(closest_indexed((r, g, b))),
+            (Self::Basic16, Color::Rgb(r, g, b)) => closest_basic((r, g, b)),
+            (Self::Basic16, Color::Indexed(index)) => closest_basic(indexed_rgb(index)),
+            _ => color,
+        }
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Replace colors the terminal can not show by the closest ones it can.
+    ///
+    /// Styles can be specified once with RGB colors and still look right on terminals without
+    /// true color support.
+    /// Colors of the [`color_fallbacks`](Self::color_fallbacks) take precedence over the
+    /// automatically chosen ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::style::{Color, Style};
+    /// # use tui::widgets::Widget;
+    /// # use tui_tree_widget::{ColorMode, Tree, TreeItem};
+    /// let area = Rect::new(0, 0, 3, 1);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(vec![TreeItem::new_leaf("a")])
+    ///     .style(Style::default().fg(Color::Rgb(250, 10, 10)))
+    ///     .color_mode(ColorMode::Basic16)
+    ///     .render(area, &mut buffer);
+    /// assert_eq!(buffer.get(2, 0).fg, Color::LightRed);
+    /// ```
+    #[must_use]
+    pub const fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
+    }
+
+    /// Colors to use instead of a color when it can not be shown with the
+    /// [`color_mode`](Self::color_mode), like `(Color::Rgb(255, 135, 0), Color::Yellow)`.
+    ///
+    /// The fallback is used as it is, even when the color mode can not show it either.
+    #[must_use]
+    pub const fn color_fallbacks(mut self, fallbacks: &'a [(Color, Color)]) -> Self {
+        self.color_fallbacks = fallbacks;
+        self
+    }
+}
+
+/// Replace the colors within the area according to the color mode
+pub(crate) fn degrade_colors(
+    mode: ColorMode,
+    fallbacks: &[(Color, Color)],
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if mode == ColorMode::TrueColor {
+        return;
+    }
+    let degrade = |color: Color| {
+        let degraded = mode.degrade(color);
+        if degraded == color {
+            return color;
+        }
+        fallbacks
+            .iter()
+            .find(|(from, _)| *from == color)
+            .map_or(degraded, |(_, fallback)| *fallback)
+    };
+    let area = area.intersection(buf.area);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buf.get_mut(x, y);
+            cell.fg = degrade(cell.fg);
+            cell.bg = degrade(cell.bg);
+        }
+    }
+}
+
+#[test]
+fn degrades_to_closest_colors() {
+    assert_eq!(
+        ColorMode::Indexed256.degrade(Color::Rgb(255, 135, 0)),
+        Color::Indexed(208)
+    );
+    assert_eq!(
+        ColorMode::Indexed256.degrade(Color::Rgb(100, 100, 100)),
+        Color::Indexed(241)
+    );
+    assert_eq!(ColorMode::Basic16.degrade(Color::Indexed(21)), Color::Blue);
+    assert_eq!(ColorMode::Basic16.degrade(Color::Cyan), Color::Cyan);
+    assert_eq!(ColorMode::NoColor.degrade(Color::Red), Color::Reset);
+    assert_eq!(
+        ColorMode::TrueColor.degrade(Color::Rgb(1, 2, 3)),
+        Color::Rgb(1, 2, 3)
+    );
+}
@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+use similar::{ChangeTag, TextDiff};
+use tui::style::Style;
+use unicode_width::UnicodeWidthStr;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::{Tree, TreeState};
+
+impl TreeState {
+    /// Mark a node as [changed](Self::mark_changed) and remember its text before the change.
+    ///
+    /// With [`Tree::inline_diff`] the characters which are new compared to the previous text
+    /// are highlighted for a while.
+    ///
+    /// Requires the `diff` feature.
+    pub fn mark_text_changed<S: Into<String>>(
+        &mut self,
+        identifier: TreeIdentifierVec,
+        previous: S,
+        now: Instant,
+    ) {
+        self.previous_texts
+            .insert(identifier.clone(), (previous.into(), now));
+        self.mark_changed(identifier, now);
+    }
+
+    /// Text of the node before it changed when the change is not yet expired
+    pub(crate) fn previous_text(&self, identifier: &[usize]) -> Option<&str> {
+        self.previous_texts
+            .get(identifier)
+            .map(|(text, _)| text.as_str())
+    }
+
+    /// Forget previous texts which are not highlighted anymore
+    pub(crate) fn expire_previous_texts(&mut self, duration: Duration, now: Instant) {
+        self.previous_texts
+            .retain(|_, (_, changed)| now.saturating_duration_since(*changed) < duration);
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Highlight the characters of a node which are new compared to its text before it was
+    /// marked with [`TreeState::mark_text_changed`] for the duration.
+    /// Useful for watch mode trees of config values.
+    ///
+    /// Requires the `diff` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::style::{Color, Style};
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let now = Instant::now();
+    /// let mut state = TreeState::default();
+    /// state.mark_text_changed(vec![0], "retries = 3", now);
+    ///
+    /// let items = vec![TreeItem::new_leaf("retries = 5")];
+    /// let area = Rect::new(0, 0, 13, 1);
+    /// let mut buffer = Buffer::empty(area);
+    /// let style = Style::default().fg(Color::Green);
+    /// let tree = Tree::new(items).inline_diff(style, Duration::from_secs(3)).now(now);
+    /// tree.render(area, &mut buffer, &mut state);
+    /// assert_eq!(buffer.get(12, 0).fg, Color::Green);
+    /// assert_eq!(buffer.get(11, 0).fg, Color::Reset);
+    /// ```
+    #[must_use]
+    pub const fn inline_diff(mut self, style: Style, duration: Duration) -> Self {
+        self.inline_diff = Some((style, duration));
+        self
+    }
+}
+
+/// Inserted parts of the current text compared to the previous text as line, column and width
+pub(crate) fn inserted_columns(previous: &str, current: &str) -> Vec<(usize, usize, usize)> {
+    let mut result = Vec::new();
+    let mut line = 0;
+    let mut column = 0;
+    for change in TextDiff::from_chars(previous, current).iter_all_changes() {
+        let value = change.value();
+        match change.tag() {
+            ChangeTag::Delete => {}
+            _ if value == "\n" => {
+                line += 1;
+                column = 0;
+            }
+            ChangeTag::Equal => column += value.width(),
+            ChangeTag::Insert => {
+                result.push((line, column, value.width()));
+                column += value.width();
+            }
+        }
+    }
+    result
+}
+
+#[test]
+fn inserted_columns_per_line() {
+    assert_eq!(inserted_columns("ab", "ab"), []);
+    assert_eq!(inserted_columns("ab", "axb"), [(0, 1, 1)]);
+    assert_eq!(inserted_columns("a\nb", "a\nbc"), [(1, 1, 1)]);
+    assert_eq!(inserted_columns("abc", "c"), []);
+}
+
+#[test]
+fn numbering_prefix_is_not_inserted() {
+    use tui::buffer::Buffer;
+    use tui::layout::Rect;
+    use tui::style::Color;
+    use tui::widgets::StatefulWidget;
+
+    use crate::{NumberingStyle, TreeItem};
+
+    let now = Instant::now();
+    let mut state = TreeState::default();
+    state.mark_text_changed(vec![0], "ab", now);
+
+    let items = vec![TreeItem::new_leaf("abc")];
+    let area = Rect::new(0, 0, 7, 1);
+    let mut buffer = Buffer::empty(area);
+    let style = Style::default().fg(Color::Green);
+    Tree::new(items)
+        .numbering(NumberingStyle::Decimal)
+        .inline_diff(style, Duration::from_secs(3))
+        .now(now)
+        .render(area, &mut buffer, &mut state);
+    assert_eq!(buffer, {
+        let mut expected = Buffer::with_lines(vec!["  1 abc"]);
+        expected.set_style(Rect::new(6, 0, 1, 1), style);
+        expected
+    });
+}
@@ -0,0 +1,55 @@
+use tui::layout::Rect;
+
+/// Columns at the right edge of a row, allocated from the right to the left
+///
+/// Everything aligned to the right edge of a row takes its columns in a fixed order, the
+/// rightmost first, so nothing overlaps regardless of the width of the row.
+/// The label gets the [`remaining`](Self::remaining) columns.
+pub(crate) struct RightEdge {
+    /// First allocated column
+    left: u16,
+    /// First column which can be allocated
+    min_x: u16,
+}
+
+impl RightEdge {
+    pub const fn new(row: Rect, min_x: u16) -> Self {
+        Self {
+            left: row.x + row.width,
+            min_x,
+        }
+    }
+
+    /// Allocate up to `width` columns left of the already allocated ones.
+    /// Returns the x and the width, which is smaller than requested when the row is too narrow.
+    pub fn take(&mut self, width: u16) -> (u16, u16) {
+        let width = width.min(self.remaining());
+        self.left -= width;
+        (self.left, width)
+    }
+
+    /// Allocate exactly `width` columns left of the already allocated ones.
+    /// Returns the x or `None` without allocating anything when they do not fit.
+    pub fn take_exact(&mut self, width: u16) -> Option<u16> {
+        if width > self.remaining() {
+            return None;
+        }
+        Some(self.take(width).0)
+    }
+
+    /// Columns which are not allocated
+    pub const fn remaining(&self) -> u16 {
+        self.left.saturating_sub(self.min_x)
+    }
+}
+
+#[test]
+fn allocates_from_the_right() {
+    let mut edge = RightEdge::new(Rect::new(2, 0, 10, 1), 4);
+    assert_eq!(edge.remaining(), 8);
+    assert_eq!(edge.take(3), (9, 3));
+    assert_eq!(edge.take_exact(6), None);
+    assert_eq!(edge.take_exact(2), Some(7));
+    assert_eq!(edge.take(5), (4, 3));
+    assert_eq!(edge.remaining(), 0);
+}
@@ -0,0 +1,27 @@
+/// How items without any text lines are rendered
+///
+/// See [`Tree::empty_items`](crate::Tree::empty_items).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyItems {
+    /// Render empty items as a blank line so they can be seen and selected
+    Blank,
+    /// Empty items take no space and are not rendered.
+    /// They can still be selected by keyboard navigation but the selection is invisible then.
+    Skip,
+}
+
+impl Default for EmptyItems {
+    fn default() -> Self {
+        Self::Blank
+    }
+}
+
+impl EmptyItems {
+    /// Height of the row of an item with the given text height
+    pub(crate) fn height(self, text_height: usize) -> usize {
+        match self {
+            Self::Blank => text_height.max(1),
+            Self::Skip => text_height,
+        }
+    }
+}
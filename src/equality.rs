@@ -0,0 +1,184 @@
+use std::hash::{Hash, Hasher};
+
+use crate::TreeState;
+
+/// Fields of a [`TreeState`] which can be ignored by [`TreeState::equals_ignoring`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TreeStateField {
+    /// The scroll position
+    Offset,
+    /// Which nodes are [opened](TreeState::open)
+    Opened,
+    /// The selected node and the selected line within it
+    Selected,
+    /// Nodes marked with [`TreeState::mark_changed`]
+    Changed,
+    /// Whether [line navigation](TreeState::set_line_navigation) is enabled
+    LineNavigation,
+    /// Orders set with [`TreeState::set_child_order`]
+    ChildOrders,
+    /// Tags set with [`TreeState::tag`]
+    Tags,
+    /// The query of [`TreeState::set_filter`]
+    Filter,
+    /// Nodes bookmarked with [`TreeState::bookmark_selected`]
+    Bookmarks,
+    /// Which opened nodes are [transient](TreeState::collapse_transient)
+    Transient,
+    /// Which nodes are [multi selected](TreeState::toggle_multi_select)
+    MultiSelection,
+    /// Which nodes are [linked](TreeState::link)
+    Links,
+    /// Which nodes are [checked](TreeState::toggle_checked)
+    Checked,
+    /// The [grouping of leaves](TreeState::set_leaf_grouping) and which groups are expanded
+    LeafGroups,
+    /// Nodes [waiting for their children](TreeState::open_async)
+    Loading,
+}
+
+impl TreeState {
+    /// Compare two states while ignoring some fields.
+    ///
+    /// Useful for tests and change detection which are not interested in volatile fields like
+    /// the offset.
+    /// The layout of the last render, the hovered position, running arrow animations, cached
+    /// styles, rows and filter matches, [metrics](TreeState::metrics), the ids recorded for
+    /// [stable ids](TreeState::set_stable_ids), the generation of the items, the live
+    /// [item handles](crate::ItemHandle), the [observer](TreeState::set_observer) and the previous
+    /// texts of inline diffs are never compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeState, TreeStateField};
+    /// let mut a = TreeState::default();
+    /// a.select(vec![2]);
+    /// let mut b = a.clone();
+    /// b.open(vec![2]);
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.equals_ignoring(&b, &[TreeStateField::Opened]));
+    /// ```
+    pub fn equals_ignoring(&self, other: &Self, fields: &[TreeStateField]) -> bool {
+        let ignored = |field| fields.contains(&field);
+        (ignored(TreeStateField::Offset)
+            || (self.offset == other.offset
+                && self.viewport_detached == other.viewport_detached
+                && self.placement == other.placement
+                && self.horizontal_offset == other.horizontal_offset))
+            && (ignored(TreeStateField::Opened) || self.opened == other.opened)
+            && (ignored(TreeStateField::Selected)
+                || (self.selected == other.selected && self.selected_line == other.selected_line))
+            && (ignored(TreeStateField::Changed) || self.changed == other.changed)
+            && (ignored(TreeStateField::LineNavigation)
+                || self.line_navigation == other.line_navigation)
+            && (ignored(TreeStateField::ChildOrders) || self.child_orders == other.child_orders)
+            && (ignored(TreeStateField::Tags) || self.tags == other.tags)
+            && (ignored(TreeStateField::Filter) || self.filter == other.filter)
+            && (ignored(TreeStateField::Bookmarks) || self.bookmarks == other.bookmarks)
+            && (ignored(TreeStateField::Transient) || self.transient == other.transient)
+            && (ignored(TreeStateField::MultiSelection)
+                || self.selected_multi == other.selected_multi)
+            && (ignored(TreeStateField::Links) || self.links == other.links)
+            && (ignored(TreeStateField::Checked) || self.checked == other.checked)
+            && (ignored(TreeStateField::LeafGroups)
+                || (self.leaf_grouping == other.leaf_grouping
+                    && self.expanded_groups == other.expanded_groups))
+            && (ignored(TreeStateField::Loading) || self.loading == other.loading)
+    }
+}
+
+impl PartialEq for TreeState {
+    fn eq(&self, other: &Self) -> bool {
+        self.equals_ignoring(other, &[])
+    }
+}
+
+impl Eq for TreeState {}
+
+impl Hash for TreeState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state);
+        self.viewport_detached.hash(state);
+        self.placement.hash(state);
+        self.horizontal_offset.hash(state);
+
+        let mut opened = self.opened.iter().collect::<Vec<_>>();
+        opened.sort_unstable();
+        opened.hash(state);
+
+        self.selected.hash(state);
+        self.selected_line.hash(state);
+
+        let mut changed = self.changed.iter().collect::<Vec<_>>();
+        changed.sort_unstable();
+        changed.hash(state);
+
+        self.line_navigation.hash(state);
+
+        let mut child_orders = self.child_orders.iter().collect::<Vec<_>>();
+        child_orders.sort_unstable();
+        child_orders.hash(state);
+
+        let mut tags = self.tags.iter().collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.hash(state);
+
+        self.filter.hash(state);
+        self.bookmarks.hash(state);
+        self.transient.hash(state);
+
+        let mut selected_multi = self.selected_multi.iter().collect::<Vec<_>>();
+        selected_multi.sort_unstable();
+        selected_multi.hash(state);
+
+        self.links.hash(state);
+        self.checked.hash(state);
+        self.leaf_grouping.hash(state);
+        self.expanded_groups.hash(state);
+        self.loading.hash(state);
+    }
+}
+
+#[test]
+fn equal_states_have_equal_hashes() {
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash(state: &TreeState) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mut a = TreeState::default();
+    let mut b = TreeState::default();
+    for identifier in [vec![1], vec![2], vec![1, 3], vec![4]] {
+        a.open(identifier);
+    }
+    for identifier in [vec![4], vec![1, 3], vec![2], vec![1]] {
+        b.open(identifier);
+    }
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+}
+
+#[test]
+fn render_layout_is_ignored() {
+    let mut a = TreeState::default();
+    a.rendered.push(crate::hit::RenderedRow {
+        identifier: vec![0],
+        y: 0,
+        height: 1,
+        x: 0,
+        width: 1,
+        arrow_x: None,
+        arrow_width: 1,
+        checkbox_x: None,
+        label_x: 0,
+        suffix_x: None,
+        mirrored: false,
+    });
+    assert_eq!(a, TreeState::default());
+}
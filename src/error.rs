@@ -0,0 +1,101 @@
+use std::fmt;
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::TreeItem;
+
+/// Errors of the fallible [`TreeState`](crate::TreeState) methods like
+/// [`try_select`](crate::TreeState::try_select)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TreeError {
+    /// The identifier is empty and does not reference any [`TreeItem`]
+    InvalidIdentifier,
+    /// The identifier references a [`TreeItem`] which does not exist
+    OutOfBounds {
+        identifier: TreeIdentifierVec,
+        /// Position within the identifier of the first index which does not exist
+        depth: usize,
+    },
+    /// The [`TreeItem`] has no children and can not be opened
+    NoChildren(TreeIdentifierVec),
+    /// The item of the [`ItemHandle`](crate::ItemHandle) created for this identifier was
+    /// removed, the items were [invalidated](crate::TreeState::invalidate) or the handle was
+    /// released
+    StaleHandle(TreeIdentifierVec),
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidIdentifier => write!(f, "the identifier is empty"),
+            Self::OutOfBounds { identifier, depth } => write!(
+                f,
+                "the identifier {:?} does not exist: it is out of bounds at depth {}",
+                identifier, depth
+            ),
+            Self::NoChildren(identifier) => {
+                write!(f, "the item {:?} has no children", identifier)
+            }
+            Self::StaleHandle(identifier) => {
+                write!(f, "the handle of the item {:?} is stale", identifier)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TreeError {}
+
+/// Get the [`TreeItem`] referenced by the identifier or the reason why it can not be found
+pub(crate) fn resolve<'a, A>(
+    items: &'a [TreeItem<A>],
+    identifier: TreeIdentifier,
+) -> Result<&'a TreeItem<A>, TreeError> {
+    let mut current: Option<&TreeItem<A>> = None;
+    for (depth, index) in identifier.iter().enumerate() {
+        let siblings = current.map_or(items, |item| &item.children);
+        current = Some(siblings.get(*index).ok_or_else(|| TreeError::OutOfBounds {
+            identifier: identifier.to_vec(),
+            depth,
+        })?);
+    }
+    current.ok_or(TreeError::InvalidIdentifier)
+}
+
+/// Same as [`resolve`] but the [`TreeItem`] is borrowed mutably
+pub(crate) fn resolve_mut<'a, A>(
+    items: &'a mut [TreeItem<A>],
+    identifier: TreeIdentifier,
+) -> Result<&'a mut TreeItem<A>, TreeError> {
+    let (first, rest) = identifier
+        .split_first()
+        .ok_or(TreeError::InvalidIdentifier)?;
+    let out_of_bounds = |depth| TreeError::OutOfBounds {
+        identifier: identifier.to_vec(),
+        depth,
+    };
+    let mut current = items.get_mut(*first).ok_or_else(|| out_of_bounds(0))?;
+    for (depth, index) in rest.iter().enumerate() {
+        current = current
+            .children
+            .get_mut(*index)
+            .ok_or_else(|| out_of_bounds(depth + 1))?;
+    }
+    Ok(current)
+}
+
+#[test]
+fn resolve_reports_depth_of_missing_index() {
+    let items = crate::flatten::get_example_tree_items();
+    assert_eq!(resolve(&items, &[1, 1, 0]).unwrap().elem, "e");
+    assert_eq!(
+        resolve(&items, &[]).unwrap_err(),
+        TreeError::InvalidIdentifier
+    );
+    assert_eq!(
+        resolve(&items, &[1, 7, 0]).unwrap_err(),
+        TreeError::OutOfBounds {
+            identifier: vec![1, 7, 0],
+            depth: 1
+        }
+    );
+}
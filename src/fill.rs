@@ -0,0 +1,82 @@
+use tui::buffer::Buffer;
+use tui::layout::{Corner, Rect};
+use tui::style::Style;
+
+use crate::Tree;
+
+impl<'a, A> Tree<'a, A> {
+    /// Clear the lines below the last row with the style.
+    ///
+    /// By default the lines keep whatever was in the buffer before.
+    /// The rows only overwrite the cells they draw, render [`Clear`](tui::widgets::Clear) first
+    /// to clear the rest of them too.
+    /// See [`fill_symbol`](Self::fill_symbol) to mark the lines like `~` in vim.
+    #[must_use]
+    pub const fn fill_remaining(mut self, style: Style) -> Self {
+        let symbol = match self.fill_remaining {
+            Some((_, symbol)) => symbol,
+            None => "",
+        };
+        self.fill_remaining = Some((style, symbol));
+        self
+    }
+
+    /// Start every line below the last row with the symbol like `~`.
+    /// Clears the lines like [`fill_remaining`](Self::fill_remaining).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let area = Rect::new(0, 0, 4, 3);
+    /// let mut buffer = Buffer::with_lines(vec!["xxxx", "xxxx", "xxxx"]);
+    /// Tree::new(vec![TreeItem::new_leaf("a")])
+    ///     .fill_symbol("~")
+    ///     .render(area, &mut buffer);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["  ax", "~   ", "~   "]));
+    /// ```
+    #[must_use]
+    pub const fn fill_symbol(mut self, symbol: &'a str) -> Self {
+        let style = match self.fill_remaining {
+            Some((style, _)) => style,
+            None => self.style,
+        };
+        self.fill_remaining = Some((style, symbol));
+        self
+    }
+
+    /// Fill the lines of the area which are not used by the first `used_height` lines
+    pub(crate) fn fill_remaining_lines(&self, area: Rect, used_height: u16, buf: &mut Buffer) {
+        let (style, symbol) = match self.fill_remaining {
+            Some(fill) => fill,
+            None => return,
+        };
+        let height = area.height.saturating_sub(used_height);
+        let y = match self.start_corner {
+            Corner::BottomLeft => area.top(),
+            _ => area.bottom() - height,
+        };
+        for y in y..y + height {
+            buf.set_string(area.left(), y, " ".repeat(area.width as usize), style);
+            buf.set_stringn(area.left(), y, symbol, area.width as usize, style);
+        }
+    }
+}
+
+#[test]
+fn fill_above_rows_from_bottom() {
+    use tui::widgets::Widget;
+
+    let items = crate::flatten::get_example_tree_items();
+    let area = Rect::new(0, 0, 4, 5);
+    let mut buffer = Buffer::with_lines(vec!["xxxx"; 5]);
+    Tree::new(items)
+        .start_corner(Corner::BottomLeft)
+        .fill_remaining(Style::default())
+        .render(area, &mut buffer);
+    let expected = Buffer::with_lines(vec!["    ", "    ", "  hx", "\u{25b6} bx", "  ax"]);
+    assert_eq!(buffer, expected);
+}
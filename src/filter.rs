@@ -0,0 +1,366 @@
+use std::collections::HashSet;
+use std::sync::{Mutex, PoisonError};
+
+use tui::style::Style;
+use tui::text::{Span, Spans, Text};
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::search::is_match;
+use crate::{Tree, TreeItem, TreeItemRender, TreeState};
+
+/// Whether the item or any of its descendants matches the lowercase query
+fn subtree_matches<A: TreeItemRender>(item: &TreeItem<A>, query: &str) -> bool {
+    let mut stack = vec![item];
+    while let Some(item) = stack.pop() {
+        if is_match(item, query) {
+            return true;
+        }
+        stack.extend(item.children.iter());
+    }
+    false
+}
+
+/// Nodes shown by a filter query, computed once for the query and the generation of the items
+#[derive(Debug, Clone)]
+struct Matches {
+    query: String,
+    generation: u64,
+    /// Nodes matching the query themselves or with matching descendants
+    shown: HashSet<TreeIdentifierVec>,
+    /// Nodes with matching descendants
+    expanded: HashSet<TreeIdentifierVec>,
+}
+
+impl Matches {
+    /// Walks the tree with an explicit stack so deep trees can not overflow the stack
+    fn new<A: TreeItemRender>(items: &[TreeItem<A>], query: &str, generation: u64) -> Self {
+        let mut shown = HashSet::new();
+        let mut expanded = HashSet::new();
+        let mut stack = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (vec![index], item))
+            .collect::<Vec<_>>();
+        while let Some((identifier, item)) = stack.pop() {
+            for (index, child) in item.children.iter().enumerate() {
+                let mut child_identifier = identifier.clone();
+                child_identifier.push(index);
+                stack.push((child_identifier, child));
+            }
+            if !is_match(item, query) {
+                continue;
+            }
+            // Ancestors already shown were added together with their own ancestors
+            for len in (1..identifier.len()).rev() {
+                if !expanded.insert(identifier[..len].to_vec()) {
+                    break;
+                }
+                shown.insert(identifier[..len].to_vec());
+            }
+            shown.insert(identifier);
+        }
+        Self {
+            query: query.to_string(),
+            generation,
+            shown,
+            expanded,
+        }
+    }
+}
+
+/// Nodes shown by the current filter, see [`TreeState::set_filter`]
+///
+/// Flattening only borrows the state, so the matches are filled through a lock.
+#[derive(Debug, Default)]
+pub(crate) struct FilterCache(Mutex<Option<Matches>>);
+
+impl Clone for FilterCache {
+    fn clone(&self) -> Self {
+        let matches = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        Self(Mutex::new(matches))
+    }
+}
+
+impl TreeState {
+    /// Only show nodes matching the query and their ancestors.
+    /// An empty query removes the filter.
+    ///
+    /// Matching is case insensitive and done on the rendered text of the items like
+    /// [`search_matches`](Self::search_matches).
+    /// Ancestors of matches are shown expanded without changing which nodes are opened, so
+    /// removing the filter restores the previous expansion.
+    /// The matches are computed once per query, call [`invalidate`](Self::invalidate) after
+    /// the items changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeItems, TreeState};
+    /// let items = TreeItems::from(vec![
+    ///     TreeItem::new("src", vec![TreeItem::new_leaf("lib.rs"), TreeItem::new_leaf("main.rs")]),
+    ///     TreeItem::new_leaf("README.md"),
+    /// ]);
+    /// let mut state = TreeState::default();
+    ///
+    /// state.set_filter("lib");
+    /// let visible = items.flatten(&state);
+    /// assert_eq!(visible.len(), 2);
+    /// assert_eq!(visible[1].identifier, [0, 0]);
+    /// ```
+    pub fn set_filter(&mut self, query: &str) {
+        self.filter = (!query.is_empty()).then(|| query.to_lowercase());
+    }
+
+    /// The lowercase query set with [`set_filter`](Self::set_filter)
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// Compute the nodes shown by the filter unless they are known for the query and the
+    /// generation of the items already
+    pub(crate) fn refresh_filter_matches<A: TreeItemRender>(&self, items: &[TreeItem<A>]) {
+        let query = match &self.filter {
+            Some(query) => query,
+            None => return,
+        };
+        let mut matches = self
+            .filter_cache
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !self.is_current_matches(matches.as_ref()) {
+            *matches = Some(Matches::new(items, query, self.generation));
+        }
+    }
+
+    fn is_current_matches(&self, matches: Option<&Matches>) -> bool {
+        matches.map_or(false, |matches| {
+            self.filter.as_deref() == Some(matches.query.as_str())
+                && matches.generation == self.generation
+        })
+    }
+
+    /// Look up the node within the current matches.
+    /// Returns `None` when they are not computed for the current query and items.
+    fn lookup_matches(&self, lookup: impl FnOnce(&Matches) -> bool) -> Option<bool> {
+        let matches = self
+            .filter_cache
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let matches = matches.as_ref();
+        self.is_current_matches(matches)
+            .then(|| matches.map_or(false, lookup))
+    }
+
+    /// Whether the item is hidden by the filter
+    pub(crate) fn is_filtered_out<A: TreeItemRender>(
+        &self,
+        identifier: TreeIdentifier,
+        item: &TreeItem<A>,
+    ) -> bool {
+        self.filter.as_ref().map_or(false, |query| {
+            let shown = self
+                .lookup_matches(|matches| matches.shown.contains(identifier))
+                .unwrap_or_else(|| subtree_matches(item, query));
+            !shown
+        })
+    }
+
+    /// Whether the children of the item are shown.
+    /// Either the item is opened or descendants match the filter.
+    pub(crate) fn is_expanded<A: TreeItemRender>(
+        &self,
+        identifier: &[usize],
+        item: &TreeItem<A>,
+    ) -> bool {
+        self.opened.contains(identifier)
+            || self.filter.as_ref().map_or(false, |query| {
+                self.lookup_matches(|matches| matches.expanded.contains(identifier))
+                    .unwrap_or_else(|| {
+                        item.children
+                            .iter()
+                            .any(|child| subtree_matches(child, query))
+                    })
+            })
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Patch the style onto the parts of the labels matching the [filter](TreeState::set_filter).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::style::{Modifier, Style};
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let mut state = TreeState::default();
+    /// state.set_filter("B");
+    ///
+    /// let area = Rect::new(0, 0, 5, 1);
+    /// let mut buffer = Buffer::empty(area);
+    /// let bold = Style::default().add_modifier(Modifier::BOLD);
+    /// Tree::new(vec![TreeItem::new_leaf("abc")])
+    ///     .filter_match_style(bold)
+    ///     .render(area, &mut buffer, &mut state);
+    ///
+    /// let mut expected = Buffer::with_lines(vec!["  abc"]);
+    /// expected.set_style(Rect::new(3, 0, 1, 1), bold);
+    /// assert_eq!(buffer, expected);
+    /// ```
+    #[must_use]
+    pub const fn filter_match_style(mut self, style: Style) -> Self {
+        self.filter_match_style = Some(style);
+        self
+    }
+}
+
+/// Patch the style onto every case insensitive occurrence of the lowercase query within a line
+pub(crate) fn highlight_matches<'t>(text: Text<'t>, query: &str, style: Style) -> Text<'t> {
+    let query = query.chars().collect::<Vec<_>>();
+    let lines = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            // Lowercase characters with the index of the original character they stem from
+            let mut lowercase = Vec::new();
+            let mut count = 0;
+            for span in &line.0 {
+                for c in span.content.chars() {
+                    lowercase.extend(c.to_lowercase().map(|lower| (lower, count)));
+                    count += 1;
+                }
+            }
+
+            let mut matched = vec![false; count];
+            let mut start = 0;
+            while !query.is_empty() && start + query.len() <= lowercase.len() {
+                let candidate = &lowercase[start..start + query.len()];
+                if candidate.iter().map(|(c, _)| c).eq(query.iter()) {
+                    for (_, original) in candidate {
+                        matched[*original] = true;
+                    }
+                    start += query.len();
+                } else {
+                    start += 1;
+                }
+            }
+            if !matched.contains(&true) {
+                return line;
+            }
+
+            let mut spans = Vec::new();
+            let mut chars = matched.into_iter();
+            for span in line.0 {
+                let part_style = |is_match| {
+                    if is_match {
+                        span.style.patch(style)
+                    } else {
+                        span.style
+                    }
+                };
+                let mut content = String::new();
+                let mut is_match = false;
+                for c in span.content.chars() {
+                    let matched = chars.next().unwrap_or(false);
+                    if matched != is_match && !content.is_empty() {
+                        spans.push(Span::styled(
+                            std::mem::take(&mut content),
+                            part_style(is_match),
+                        ));
+                    }
+                    is_match = matched;
+                    content.push(c);
+                }
+                if !content.is_empty() {
+                    spans.push(Span::styled(content, part_style(is_match)));
+                }
+            }
+            Spans::from(spans)
+        })
+        .collect::<Vec<_>>();
+    Text::from(lines)
+}
+
+#[test]
+fn filter_shows_matches_with_ancestors() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.set_filter("E");
+    let result = crate::flatten::flatten_state(&state, &items)
+        .iter()
+        .map(|o| o.item.elem)
+        .collect::<Vec<_>>();
+    assert_eq!(result, ["b", "d", "e"]);
+    assert!(state.get_all_opened().is_empty());
+
+    state.set_filter("");
+    assert_eq!(crate::flatten::flatten_state(&state, &items).len(), 3);
+}
+
+#[test]
+fn matches_are_computed_once_per_query() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.set_filter("e");
+    state.refresh_filter_matches(&items);
+    assert!(state.is_expanded(&[1, 1], &items[1].children[1]));
+    assert!(state.is_filtered_out(&[1, 0], &items[1].children[0]));
+
+    // Known matches are used until the items are invalidated
+    items[1].children[0] = TreeItem::new_leaf("ee");
+    state.refresh_filter_matches(&items);
+    assert!(state.is_filtered_out(&[1, 0], &items[1].children[0]));
+    state.invalidate();
+    state.refresh_filter_matches(&items);
+    assert!(!state.is_filtered_out(&[1, 0], &items[1].children[0]));
+
+    state.set_filter("h");
+    assert!(!state.is_expanded(&[1], &items[1]));
+    assert!(!state.is_filtered_out(&[2], &items[2]));
+}
+
+#[test]
+fn deep_tree_filters_on_small_stack() {
+    // Every shown node is stored with an identifier as long as its depth
+    let depth = 1_000;
+    let thread = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            let items = crate::flatten::deep_tree_items(depth);
+            let mut state = TreeState::default();
+            state.set_filter("leaf");
+            assert_eq!(
+                crate::flatten::flatten_state(&state, &items).len(),
+                depth + 1
+            );
+        })
+        .unwrap();
+    thread.join().unwrap();
+}
+
+#[test]
+fn highlight_keeps_span_styles() {
+    use tui::style::Color;
+
+    let red = Style::default().fg(Color::Red);
+    let text = Text::from(Spans::from(vec![Span::raw("aB"), Span::styled("ab", red)]));
+    let blue = Style::default().bg(Color::Blue);
+    let result = highlight_matches(text, "ba", blue);
+    assert_eq!(
+        result.lines[0].0,
+        [
+            Span::raw("a"),
+            Span::styled("B", blue),
+            Span::styled("a", red.patch(blue)),
+            Span::styled("b", red),
+        ]
+    );
+}
@@ -0,0 +1,159 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::widgets::{StatefulWidget, Widget};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::flatten::flatten_state;
+use crate::search::is_match;
+use crate::{Tree, TreeItem, TreeItemRender, TreeState};
+
+/// State of a [`FilterableTree`]: the typed filter text and the [`TreeState`]
+///
+/// Requires the `textarea` feature.
+#[derive(Debug, Clone)]
+pub struct FilterableTreeState {
+    pub input: TextArea<'static>,
+    pub tree: TreeState,
+}
+
+impl Default for FilterableTreeState {
+    fn default() -> Self {
+        let mut input = TextArea::default();
+        input.set_cursor_line_style(Style::default());
+        Self {
+            input,
+            tree: TreeState::default(),
+        }
+    }
+}
+
+impl FilterableTreeState {
+    /// Handle a key press.
+    ///
+    /// The up and down arrow keys navigate the tree, everything else edits the filter text which
+    /// is applied with [`TreeState::set_filter`].
+    /// When the selection is hidden by the filter the first match is selected.
+    ///
+    /// Enter is not handled as the filter is a single line, so the app can decide what picking
+    /// the selected node means.
+    /// Returns `true` when the key was handled.
+    pub fn input<A: TreeItemRender>(
+        &mut self,
+        input: impl Into<Input>,
+        items: &[TreeItem<A>],
+    ) -> bool {
+        let input = input.into();
+        match input.key {
+            Key::Up => self.tree.key_up(items),
+            Key::Down => self.tree.key_down(items),
+            Key::Enter => return false,
+            _ => {
+                if !self.input.input(input) {
+                    return false;
+                }
+                let query = self.input.lines().join("");
+                self.tree.set_filter(&query);
+                self.select_visible(items);
+            }
+        }
+        true
+    }
+
+    /// Select the first match (or the first visible node) when the selection is not visible
+    fn select_visible<A: TreeItemRender>(&mut self, items: &[TreeItem<A>]) {
+        let visible = flatten_state(&self.tree, items);
+        if visible.iter().any(|o| o.identifier == self.tree.selected) {
+            return;
+        }
+        let query = self.tree.filter().unwrap_or_default();
+        let first = visible
+            .iter()
+            .find(|o| is_match(o.item, query))
+            .or_else(|| visible.first());
+        if let Some(first) = first {
+            self.tree.select(first.identifier.clone());
+        }
+    }
+}
+
+/// [`Tree`] with a one-line filter input above it, a find-as-you-type tree picker
+///
+/// Keys are handled by [`FilterableTreeState::input`].
+/// Requires the `textarea` feature.
+///
+/// # Example
+///
+/// ```
+/// # use tui::buffer::Buffer;
+/// # use tui::layout::Rect;
+/// # use tui::widgets::StatefulWidget;
+/// # use tui_textarea::{Input, Key};
+/// # use tui_tree_widget::{FilterableTree, FilterableTreeState, Tree, TreeItem};
+/// let items = vec![TreeItem::new("src", vec![TreeItem::new_leaf("lib.rs")])];
+/// let mut state = FilterableTreeState::default();
+///
+/// // Within the event loop
+/// let input = Input { key: Key::Char('l'), ..Input::default() };
+/// state.input(input, &items);
+///
+/// let area = Rect::new(0, 0, 10, 3);
+/// let mut buffer = Buffer::empty(area);
+/// FilterableTree::new(Tree::new(items)).render(area, &mut buffer, &mut state);
+/// assert_eq!(state.tree.selected(), [0, 0]);
+/// ```
+pub struct FilterableTree<'a, A> {
+    tree: Tree<'a, A>,
+}
+
+impl<'a, A> FilterableTree<'a, A> {
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(tree: Tree<'a, A>) -> Self {
+        Self { tree }
+    }
+}
+
+impl<'a, A: TreeItemRender> StatefulWidget for FilterableTree<'a, A> {
+    type State = FilterableTreeState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if area.height < 1 {
+            return;
+        }
+        let input_area = Rect { height: 1, ..area };
+        let tree_area = Rect {
+            y: area.y + 1,
+            height: area.height - 1,
+            ..area
+        };
+        state.input.widget().render(input_area, buf);
+        StatefulWidget::render(self.tree, tree_area, buf, &mut state.tree);
+    }
+}
+
+#[test]
+fn typing_filters_and_selects_first_match() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = FilterableTreeState::default();
+    state.tree.select(vec![0]);
+    let typed = Input {
+        key: Key::Char('f'),
+        ..Input::default()
+    };
+    assert!(state.input(typed, &items));
+    assert_eq!(state.tree.filter(), Some("f"));
+    assert_eq!(state.tree.selected(), [1, 1, 1]);
+
+    let enter = Input {
+        key: Key::Enter,
+        ..Input::default()
+    };
+    assert!(!state.input(enter, &items));
+
+    let area = Rect::new(0, 0, 7, 4);
+    let mut buffer = Buffer::empty(area);
+    FilterableTree::new(Tree::new(items)).render(area, &mut buffer, &mut state);
+    assert_eq!(buffer.get(0, 0).symbol, "f");
+    assert_eq!(buffer.get(6, 3).symbol, "f");
+}
@@ -1,11 +1,26 @@
 #![allow(clippy::implicit_hasher)]
 
+use std::collections::HashMap;
+
+use tui::text::{Span, Spans, Text};
+use tui::widgets::ListItem;
+use unicode_width::UnicodeWidthStr;
+
 use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
-use crate::TreeItem;
+use crate::leaf_groups::group_leaves;
+use crate::{
+    depth_prefix, pick_node_symbol, TreeItem, TreeItemRender, TreeState, INDENT_WIDTH,
+    NODE_CLOSED_SYMBOL, NODE_LEAF_SYMBOL, NODE_OPEN_SYMBOL,
+};
 
 pub struct Flattened<'a, A> {
     pub identifier: Vec<usize>,
     pub item: &'a TreeItem<A>,
+    /// Whether each node along the identifier is the last of its displayed siblings,
+    /// from the root item down to the item itself
+    pub last_siblings: Vec<bool>,
+    /// Amount of leaves summarized by this row, see [`TreeState::set_leaf_grouping`]
+    pub grouped: Option<usize>,
 }
 
 impl<'a, A> Flattened<'a, A> {
@@ -14,53 +29,317 @@ impl<'a, A> Flattened<'a, A> {
     }
 }
 
-/// Get a flat list of all visible [`TreeItem`s](TreeItem)
-pub fn flatten<'a, A>(
-    opened: &[TreeIdentifierVec],
+/// Configuration for [`Flattened::to_list_item`]
+#[derive(Debug, Clone, Copy)]
+pub struct ListItemConfig<'a> {
+    /// State used to determine whether the item is opened
+    pub state: &'a TreeState,
+    /// Symbol in front of nodes with hidden children,
+    /// see [`Tree::node_closed_symbol`](crate::Tree::node_closed_symbol)
+    pub node_closed_symbol: &'a str,
+    /// Symbol in front of nodes with shown children,
+    /// see [`Tree::node_open_symbol`](crate::Tree::node_open_symbol)
+    pub node_open_symbol: &'a str,
+    /// Symbol in front of items without children,
+    /// see [`Tree::node_leaf_symbol`](crate::Tree::node_leaf_symbol)
+    pub node_leaf_symbol: &'a str,
+    /// Columns of indentation per depth, see [`Tree::indent_width`](crate::Tree::indent_width)
+    pub indent_width: usize,
+}
+
+impl<'a> ListItemConfig<'a> {
+    /// Configuration with the same symbols and indentation as a default [`Tree`](crate::Tree)
+    #[must_use]
+    pub const fn new(state: &'a TreeState) -> Self {
+        Self {
+            state,
+            node_closed_symbol: NODE_CLOSED_SYMBOL,
+            node_open_symbol: NODE_OPEN_SYMBOL,
+            node_leaf_symbol: NODE_LEAF_SYMBOL,
+            indent_width: INDENT_WIDTH,
+        }
+    }
+}
+
+impl<'a, A: TreeItemRender> Flattened<'a, A> {
+    /// Create a [`ListItem`] with the same indentation and symbols as the [`Tree`](crate::Tree)
+    /// would render.
+    /// This allows reusing the visual formatting of the tree in other widgets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{flatten, ListItemConfig, TreeItem, TreeState};
+    /// # use tui::widgets::List;
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    ///
+    /// let config = ListItemConfig {
+    ///     node_closed_symbol: "+",
+    ///     node_open_symbol: "-",
+    ///     indent_width: 4,
+    ///     ..ListItemConfig::new(&state)
+    /// };
+    /// let list_items = flatten(&state.get_all_opened(), &items)
+    ///     .iter()
+    ///     .map(|o| o.to_list_item(config))
+    ///     .collect::<Vec<_>>();
+    /// let list = List::new(list_items);
+    /// ```
+    pub fn to_list_item(&self, config: ListItemConfig) -> ListItem<'a> {
+        let symbol = pick_node_symbol(
+            config.node_closed_symbol,
+            config.node_open_symbol,
+            config.node_leaf_symbol,
+            self.item.has_children(),
+            config.state.is_expanded(&self.identifier, self.item),
+        );
+        let prefix = depth_prefix(self.depth(), config.indent_width, symbol);
+        let blank = " ".repeat(prefix.width());
+
+        let mut text = self.item.elem.as_text();
+        for (index, line) in text.lines.iter_mut().enumerate() {
+            let prefix = if index == 0 { &prefix } else { &blank };
+            line.0.insert(0, Span::raw(prefix.clone()));
+        }
+        if text.lines.is_empty() {
+            text = Text::from(Spans::from(prefix));
+        }
+        ListItem::new(text).style(self.item.style)
+    }
+}
+
+/// Visible rows alongside the order their siblings are displayed in, see
+/// [`TreeState::flatten_view`]
+pub struct FlattenedView<'a, A> {
+    pub rows: Vec<Flattened<'a, A>>,
+    permutations: HashMap<TreeIdentifierVec, Vec<usize>>,
+}
+
+impl<'a, A> FlattenedView<'a, A> {
+    /// Indices within the items of the displayed children of the parent in display order.
+    /// Children hidden by a [filter](TreeState::set_filter) are not contained.
+    /// The empty identifier references the root items.
+    ///
+    /// Returns `None` when the children of the parent are not displayed.
+    pub fn logical_to_physical(&self, parent: &[usize]) -> Option<&[usize]> {
+        self.permutations.get(parent).map(Vec::as_slice)
+    }
+
+    /// Position of the node among its displayed siblings.
+    /// Returns `None` when the node is not displayed.
+    pub fn physical_to_logical(&self, identifier: &[usize]) -> Option<usize> {
+        let (index, parent) = identifier.split_last()?;
+        self.logical_to_physical(parent)?
+            .iter()
+            .position(|physical| physical == index)
+    }
+}
+
+impl TreeState {
+    /// Get the visible rows like [`TreeItems::flatten`](crate::TreeItems::flatten) together
+    /// with the permutation of the children of every displayed parent.
+    ///
+    /// A [child order](TreeState::set_child_order) or a [filter](TreeState::set_filter)
+    /// change the display order without changing the items.
+    /// The permutations allow translating between the display order and the storage order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{SortKey, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("a")];
+    /// let mut state = TreeState::default();
+    /// state.set_child_order(vec![], SortKey::Label);
+    ///
+    /// let view = state.flatten_view(&items);
+    /// assert_eq!(view.logical_to_physical(&[]), Some([1, 0].as_slice()));
+    /// assert_eq!(view.physical_to_logical(&[0]), Some(1));
+    /// ```
+    pub fn flatten_view<'a, A: TreeItemRender>(
+        &self,
+        items: &'a [TreeItem<A>],
+    ) -> FlattenedView<'a, A> {
+        self.record_flatten();
+        let mut rows = Vec::new();
+        let mut permutations = HashMap::new();
+        ordered(self, items, &mut rows, Some(&mut permutations));
+        FlattenedView { rows, permutations }
+    }
+}
+
+/// Get a flat list of all [`TreeItem`s](TreeItem) visible with the [`TreeState`] in the order
+/// they are rendered.
+/// Children of parents with a [child order](TreeState::set_child_order) are sorted accordingly
+/// and a [filter](TreeState::set_filter) hides non matching nodes.
+pub(crate) fn flatten_state<'a, A: TreeItemRender>(
+    state: &TreeState,
     items: &'a [TreeItem<A>],
 ) -> Vec<Flattened<'a, A>> {
-    internal(opened, items, &[])
+    state.refresh_filter_matches(items);
+    if let Some(rows) = state.flatten_cache.as_ref().and_then(|c| c.get(state, items)) {
+        return group_leaves(rows, state);
+    }
+    state.record_flatten();
+    let mut result = Vec::new();
+    ordered(state, items, &mut result, None);
+    if let Some(cache) = &state.flatten_cache {
+        cache.store(state, &result);
+    }
+    group_leaves(result, state)
+}
+
+/// Indices of the children which are shown in the order they are rendered
+fn display_order<A: TreeItemRender>(
+    state: &TreeState,
+    items: &[TreeItem<A>],
+    parent: TreeIdentifier,
+) -> Vec<usize> {
+    let mut indices = (0..items.len()).collect::<Vec<_>>();
+    if let Some(key) = state.child_orders.get(parent) {
+        indices.sort_by(|a, b| key.compare(&items[*a].elem, &items[*b].elem));
+    }
+    if state.filter().is_some() {
+        let mut identifier = parent.to_vec();
+        identifier.push(0);
+        indices.retain(|index| {
+            identifier[parent.len()] = *index;
+            !state.is_filtered_out(&identifier, &items[*index])
+        });
+    }
+    indices
+}
+
+/// Walks the tree with an explicit stack instead of recursion so deep trees can not overflow
+/// the stack.
+fn ordered<'a, A: TreeItemRender>(
+    state: &TreeState,
+    items: &'a [TreeItem<A>],
+    result: &mut Vec<Flattened<'a, A>>,
+    mut permutations: Option<&mut HashMap<TreeIdentifierVec, Vec<usize>>>,
+) {
+    let root_order = display_order(state, items, &[]);
+    if let Some(permutations) = permutations.as_deref_mut() {
+        permutations.insert(Vec::new(), root_order.clone());
+    }
+    // The parent, its children and the indices of its children still to visit of every level
+    let mut stack = vec![(Vec::new(), items, root_order.into_iter())];
+    while let Some((parent, siblings, indices)) = stack.last_mut() {
+        let index = match indices.next() {
+            Some(index) => index,
+            None => {
+                stack.pop();
+                continue;
+            }
+        };
+        let item = &siblings[index];
+        let mut child_identifier = parent.clone();
+        child_identifier.push(index);
+
+        let last_siblings = stack
+            .iter()
+            .map(|(_, _, indices)| indices.len() == 0)
+            .collect();
+        result.push(Flattened {
+            item,
+            identifier: child_identifier.clone(),
+            last_siblings,
+            grouped: None,
+        });
+
+        if state.is_expanded(&child_identifier, item) {
+            let order = display_order(state, &item.children, &child_identifier);
+            if let Some(permutations) = permutations.as_deref_mut() {
+                permutations.insert(child_identifier.clone(), order.clone());
+            }
+            stack.push((child_identifier, &item.children, order.into_iter()));
+        }
+    }
 }
 
-fn internal<'a, A>(
+/// Insert up to `amount` preview rows of the children below every collapsed node.
+/// Returns whether each row is a preview row alongside the rows.
+pub(crate) fn with_peek_rows<'a, A>(
+    visible: Vec<Flattened<'a, A>>,
+    state: &TreeState,
+    amount: usize,
+) -> (Vec<Flattened<'a, A>>, Vec<bool>) {
+    let mut rows = Vec::with_capacity(visible.len());
+    let mut is_peek = Vec::with_capacity(visible.len());
+    for flattened in visible {
+        let item = flattened.item;
+        let peek = if state.opened.contains(&flattened.identifier) {
+            Vec::new()
+        } else {
+            let shown = item.children.len().min(amount);
+            item.children
+                .iter()
+                .take(amount)
+                .enumerate()
+                .map(|(index, child)| {
+                    let mut identifier = flattened.identifier.clone();
+                    identifier.push(index);
+                    let mut last_siblings = flattened.last_siblings.clone();
+                    last_siblings.push(index + 1 == shown);
+                    Flattened {
+                        identifier,
+                        item: child,
+                        last_siblings,
+                        grouped: None,
+                    }
+                })
+                .collect()
+        };
+        rows.push(flattened);
+        is_peek.push(false);
+        is_peek.extend(peek.iter().map(|_| true));
+        rows.extend(peek);
+    }
+    (rows, is_peek)
+}
+
+/// Get a flat list of all visible [`TreeItem`s](TreeItem)
+pub fn flatten<'a, A>(
     opened: &[TreeIdentifierVec],
     items: &'a [TreeItem<A>],
-    current: TreeIdentifier,
 ) -> Vec<Flattened<'a, A>> {
     let mut result = Vec::new();
-
-    for (index, item) in items.iter().enumerate() {
-        let mut child_identifier = current.to_vec();
+    // The parent, its children and the index of the next child to visit of every level
+    let mut stack = vec![(Vec::new(), items, 0)];
+    while let Some((parent, siblings, next)) = stack.last_mut() {
+        let index = *next;
+        let item = match siblings.get(index) {
+            Some(item) => item,
+            None => {
+                stack.pop();
+                continue;
+            }
+        };
+        *next += 1;
+        let mut child_identifier = parent.clone();
         child_identifier.push(index);
 
+        let last_siblings = stack
+            .iter()
+            .map(|(_, siblings, next)| *next >= siblings.len())
+            .collect();
         result.push(Flattened {
             item,
             identifier: child_identifier.clone(),
+            last_siblings,
+            grouped: None,
         });
 
         if opened.contains(&child_identifier) {
-            let mut child_result = internal(opened, &item.children, &child_identifier);
-            result.append(&mut child_result);
+            stack.push((child_identifier, &item.children, 0));
         }
     }
-
     result
 }
 
 #[cfg(test)]
-fn get_naive_string_from_text(text: &tui::text::Text<'_>) -> String {
-    text.lines
-        .first()
-        .unwrap()
-        .0
-        .first()
-        .unwrap()
-        .content
-        .to_string()
-}
-
-#[cfg(test)]
-fn get_example_tree_items() -> Vec<TreeItem<&'static str>> {
+pub(crate) fn get_example_tree_items() -> Vec<TreeItem<&'static str>> {
     vec![
         TreeItem::new_leaf("a"),
         TreeItem::new(
@@ -78,6 +357,100 @@ fn get_example_tree_items() -> Vec<TreeItem<&'static str>> {
     ]
 }
 
+#[test]
+fn flatten_state_sorts_children() {
+    let items = get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.set_child_order(vec![], crate::SortKey::LabelDescending);
+    let result = flatten_state(&state, &items)
+        .iter()
+        .map(|o| o.item.elem)
+        .collect::<Vec<_>>();
+    assert_eq!(result, ["h", "b", "c", "d", "g", "a"]);
+}
+
+#[test]
+fn flatten_view_permutations() {
+    let items = get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.set_child_order(vec![1], crate::SortKey::LabelDescending);
+    let view = state.flatten_view(&items);
+    let result = view.rows.iter().map(|o| o.item.elem).collect::<Vec<_>>();
+    assert_eq!(result, ["a", "b", "g", "d", "c", "h"]);
+    assert_eq!(view.logical_to_physical(&[]), Some([0, 1, 2].as_slice()));
+    assert_eq!(view.logical_to_physical(&[1]), Some([2, 1, 0].as_slice()));
+    assert_eq!(view.logical_to_physical(&[1, 1]), None);
+    assert_eq!(view.physical_to_logical(&[1, 2]), Some(0));
+    assert_eq!(view.physical_to_logical(&[1, 1, 0]), None);
+}
+
+#[test]
+fn peek_rows_below_collapsed() {
+    let items = get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    let (rows, is_peek) = with_peek_rows(flatten_state(&state, &items), &state, 1);
+    let result = rows.iter().map(|o| o.item.elem).collect::<Vec<_>>();
+    assert_eq!(result, ["a", "b", "c", "d", "e", "g", "h"]);
+    assert_eq!(is_peek, [false, false, false, false, true, false, false]);
+}
+
+#[test]
+fn to_list_item_has_prefix() {
+    let items = get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    let config = ListItemConfig::new(&state);
+    let result = flatten(&state.get_all_opened(), &items)
+        .iter()
+        .map(|o| o.to_list_item(config))
+        .collect::<Vec<_>>();
+    let expected = [
+        ("  ", "a"),
+        ("\u{25bc} ", "b"),
+        ("    ", "c"),
+        ("  \u{25b6} ", "d"),
+        ("    ", "g"),
+        ("  ", "h"),
+    ]
+    .iter()
+    .map(|(prefix, text)| ListItem::new(Spans::from(vec![Span::raw(*prefix), Span::raw(*text)])))
+    .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn to_list_item_uses_configured_symbols() {
+    let items = get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    let config = ListItemConfig {
+        node_closed_symbol: "+",
+        node_open_symbol: "-",
+        node_leaf_symbol: ".",
+        indent_width: 3,
+        ..ListItemConfig::new(&state)
+    };
+    let result = flatten(&state.get_all_opened(), &items)
+        .iter()
+        .map(|o| o.to_list_item(config))
+        .collect::<Vec<_>>();
+    let expected = [
+        (". ", "a"),
+        ("- ", "b"),
+        ("   . ", "c"),
+        ("   + ", "d"),
+        ("   . ", "g"),
+        (". ", "h"),
+    ]
+    .iter()
+    .map(|(prefix, text)| ListItem::new(Spans::from(vec![Span::raw(*prefix), Span::raw(*text)])))
+    .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn get_opened_nothing_opened_is_top_level() {
     let items = get_example_tree_items();
@@ -124,3 +497,45 @@ fn get_opened_all_opened() {
         .collect::<Vec<&str>>();
     assert_eq!(result_text, ["a", "b", "c", "d", "e", "f", "g", "h"]);
 }
+
+#[cfg(test)]
+pub(crate) fn deep_tree_items(depth: usize) -> Vec<TreeItem<&'static str>> {
+    let mut item = TreeItem::new_leaf("leaf");
+    for _ in 0..depth {
+        item = TreeItem::new("node", vec![item]);
+    }
+    vec![item]
+}
+
+#[test]
+fn deep_tree_does_not_overflow() {
+    let items = deep_tree_items(20_000);
+    let state = TreeState::default();
+    assert_eq!(flatten_state(&state, &items).len(), 1);
+    assert_eq!(flatten(&[], &items).len(), 1);
+}
+
+#[test]
+fn deep_opened_tree_flattens_on_small_stack() {
+    // Every row has an identifier as long as its depth so the depth is kept moderate
+    let depth = 2_000;
+    let thread = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            let items = deep_tree_items(depth);
+            let mut state = TreeState::default();
+            let mut identifier = vec![0];
+            for _ in 0..depth {
+                state.open(identifier.clone());
+                identifier.push(0);
+            }
+            let view = state.flatten_view(&items);
+            assert_eq!(view.rows.len(), depth + 1);
+            assert_eq!(
+                view.logical_to_physical(&vec![0; depth]),
+                Some([0].as_slice())
+            );
+        })
+        .unwrap();
+    thread.join().unwrap();
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use crate::error::resolve;
+use crate::flatten::Flattened;
+use crate::identifier::TreeIdentifierVec;
+use crate::opened::OpenedSet;
+use crate::{SortKey, TreeItem, TreeState};
+
+/// Visible rows of the last flatten together with the parts of the state they depend on
+#[derive(Debug, Clone)]
+struct CachedRows {
+    opened: OpenedSet,
+    filter: Option<String>,
+    child_orders: HashMap<TreeIdentifierVec, SortKey>,
+    rows: Vec<(TreeIdentifierVec, Vec<bool>)>,
+}
+
+impl CachedRows {
+    fn is_valid_for(&self, state: &TreeState) -> bool {
+        self.opened == state.opened
+            && self.filter == state.filter
+            && self.child_orders == state.child_orders
+    }
+}
+
+/// Cache of the visible rows, see [`TreeState::set_flatten_cache`]
+///
+/// Flattening only borrows the state, so the cache is filled through a lock.
+#[derive(Debug, Default)]
+pub(crate) struct FlattenCache(Mutex<Option<CachedRows>>);
+
+impl Clone for FlattenCache {
+    fn clone(&self) -> Self {
+        let rows = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        Self(Mutex::new(rows))
+    }
+}
+
+impl FlattenCache {
+    /// Rows of the last flatten when neither the opened nodes, the filter nor the child orders
+    /// changed since
+    pub fn get<'a, A>(
+        &self,
+        state: &TreeState,
+        items: &'a [TreeItem<A>],
+    ) -> Option<Vec<Flattened<'a, A>>> {
+        let cached = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let cached = cached
+            .as_ref()
+            .filter(|cached| cached.is_valid_for(state))?;
+        cached
+            .rows
+            .iter()
+            .map(|(identifier, last_siblings)| {
+                let item = resolve(items, identifier).ok()?;
+                Some(Flattened {
+                    identifier: identifier.clone(),
+                    item,
+                    last_siblings: last_siblings.clone(),
+                    grouped: None,
+                })
+            })
+            .collect()
+    }
+
+    pub fn store<A>(&self, state: &TreeState, rows: &[Flattened<A>]) {
+        let rows = rows
+            .iter()
+            .map(|row| (row.identifier.clone(), row.last_siblings.clone()))
+            .collect();
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(CachedRows {
+            opened: state.opened.clone(),
+            filter: state.filter.clone(),
+            child_orders: state.child_orders.clone(),
+            rows,
+        });
+    }
+}
+
+impl TreeState {
+    /// Keep the visible rows between renders and key presses instead of walking the items
+    /// every time, for trees with a huge amount of nodes.
+    ///
+    /// The rows are computed again when nodes are opened or closed, or the
+    /// [filter](Self::set_filter) or a [child order](Self::set_child_order) changes.
+    /// Changes of the items are not noticed, call [`invalidate`](Self::invalidate) after them.
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let mut items = vec![TreeItem::new_leaf("a")];
+    /// let mut state = TreeState::default();
+    /// state.set_flatten_cache(true);
+    /// state.select_first();
+    ///
+    /// items.push(TreeItem::new_leaf("b"));
+    /// state.invalidate();
+    /// state.key_down(&items);
+    /// assert_eq!(state.selected(), [1]);
+    /// ```
+    pub fn set_flatten_cache(&mut self, enabled: bool) {
+        self.flatten_cache = enabled.then(FlattenCache::default);
+    }
+
+    /// Forget the visible rows cached by [`set_flatten_cache`](Self::set_flatten_cache).
+    /// Needed after the items changed.
+    ///
+    /// Every [`ItemHandle`](crate::ItemHandle) created before becomes stale as it is unknown
+    /// where its item went.
+    pub fn invalidate(&mut self) {
+        self.invalidate_rows();
+        self.handles.clear();
+    }
+
+    /// Same as [`invalidate`](Self::invalidate) but keeps the handles, for changes which already
+    /// moved the handles along with their items
+    pub(crate) fn invalidate_rows(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        if self.flatten_cache.is_some() {
+            self.flatten_cache = Some(FlattenCache::default());
+        }
+    }
+}
+
+#[test]
+fn cache_is_refreshed_when_opened_changes() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.set_flatten_cache(true);
+    state.set_metrics_enabled(true);
+
+    assert_eq!(crate::flatten::flatten_state(&state, &items).len(), 3);
+    assert_eq!(crate::flatten::flatten_state(&state, &items).len(), 3);
+    assert_eq!(state.metrics().unwrap().flattens(), 1);
+
+    state.open(vec![1]);
+    assert_eq!(crate::flatten::flatten_state(&state, &items).len(), 6);
+    assert_eq!(state.metrics().unwrap().flattens(), 2);
+
+    // Stale rows which no longer exist are not used
+    assert_eq!(crate::flatten::flatten_state(&state, &items[..1]).len(), 1);
+}
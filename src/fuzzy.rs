@@ -0,0 +1,97 @@
+use std::cmp::Reverse;
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::items::IterDepthFirst;
+use crate::{text_to_string, TreeItem, TreeItemRender, TreeState};
+
+/// A [`TreeItem`] matching the query of [`TreeState::fuzzy_filter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Identifier of the matching item
+    pub identifier: TreeIdentifierVec,
+    /// Score of the match, higher is better
+    pub score: i64,
+    /// Indices of the matched chars within the rendered text of the item.
+    /// Lines of multi-line items are joined with `\n`.
+    pub indices: Vec<usize>,
+}
+
+impl TreeState {
+    /// Score all items against the query with the skim algorithm.
+    ///
+    /// Returns the matches ranked by their score, best first.
+    /// Items with the same score keep their order within the tree.
+    /// The ancestors of every match are opened so the matches are shown within their tree and
+    /// the best match is selected.
+    ///
+    /// Requires the `fuzzy` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("src", vec![TreeItem::new_leaf("lib.rs")])];
+    /// let mut state = TreeState::default();
+    ///
+    /// let matches = state.fuzzy_filter("lrs", &items);
+    /// assert_eq!(matches.len(), 1);
+    /// assert_eq!(matches[0].indices, [0, 4, 5]);
+    /// assert_eq!(state.selected(), [0, 0]);
+    /// ```
+    pub fn fuzzy_filter<A: TreeItemRender>(
+        &mut self,
+        query: &str,
+        items: &[TreeItem<A>],
+    ) -> Vec<FuzzyMatch> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let matcher = SkimMatcherV2::default();
+        let mut matches = IterDepthFirst::new(items)
+            .filter_map(|(identifier, item)| {
+                let text = text_to_string(&item.elem.as_text());
+                matcher
+                    .fuzzy_indices(&text, query)
+                    .map(|(score, indices)| FuzzyMatch {
+                        identifier,
+                        score,
+                        indices,
+                    })
+            })
+            .collect::<Vec<_>>();
+        // Stable sort keeps the tree order for equal scores
+        matches.sort_by_key(|o| Reverse(o.score));
+
+        for found in &matches {
+            self.open_ancestors(&found.identifier);
+        }
+        if let Some(best) = matches.first() {
+            self.select(best.identifier.clone());
+        }
+        matches
+    }
+}
+
+#[test]
+fn fuzzy_filter_opens_ancestors_of_all_matches() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let matches = state.fuzzy_filter("e", &items);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].identifier, [1, 1, 0]);
+    assert_eq!(state.selected(), [1, 1, 0]);
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+}
+
+#[test]
+fn fuzzy_filter_empty_query_matches_nothing() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert!(state.fuzzy_filter("", &items).is_empty());
+    assert!(state.get_all_opened().is_empty());
+}
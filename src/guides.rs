@@ -0,0 +1,81 @@
+use crate::Tree;
+
+impl<'a, A> Tree<'a, A> {
+    /// Draw guide lines connecting parents with their children through the indentation like
+    /// `tree(1)` does.
+    ///
+    /// Guide lines are not drawn for rows whose indentation is limited by
+    /// [`max_indent_cols`](Self::max_indent_cols).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "a",
+    ///     vec![
+    ///         TreeItem::new("b", vec![TreeItem::new_leaf("c")]),
+    ///         TreeItem::new_leaf("d"),
+    ///     ],
+    /// )];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.open(vec![0, 0]);
+    ///
+    /// let area = Rect::new(0, 0, 8, 4);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .guide_lines(true)
+    ///     .render(area, &mut buffer, &mut state);
+    /// let expected = Buffer::with_lines(vec![
+    ///     "▼ a     ",
+    ///     "├─▼ b   ",
+    ///     "│ └─  c ",
+    ///     "└─  d   ",
+    /// ]);
+    /// assert_eq!(buffer, expected);
+    /// ```
+    #[must_use]
+    pub const fn guide_lines(mut self, guide_lines: bool) -> Self {
+        self.guide_lines = guide_lines;
+        self
+    }
+}
+
+/// Indentation of a row drawn with guide lines.
+/// `last_siblings` tells whether the item and its ancestors are the last of their siblings.
+pub(crate) fn guide_indentation(last_siblings: &[bool], indent_width: usize) -> String {
+    if indent_width == 0 {
+        return String::new();
+    }
+    let depth = last_siblings.len().saturating_sub(1);
+    let mut result = String::with_capacity(depth * indent_width * 3);
+    for (level, is_last) in last_siblings.iter().enumerate().skip(1) {
+        let (first, rest) = match (level == depth, is_last) {
+            (true, true) => ('\u{2514}', '\u{2500}'),
+            (true, false) => ('\u{251c}', '\u{2500}'),
+            (false, true) => (' ', ' '),
+            (false, false) => ('\u{2502}', ' '),
+        };
+        result.push(first);
+        result.extend(std::iter::repeat(rest).take(indent_width - 1));
+    }
+    result
+}
+
+#[test]
+fn continues_lines_of_ancestors_with_more_siblings() {
+    assert_eq!(guide_indentation(&[false], 2), "");
+    assert_eq!(
+        guide_indentation(&[false, false, true], 2),
+        "\u{2502} \u{2514}\u{2500}"
+    );
+    assert_eq!(
+        guide_indentation(&[true, true, false], 3),
+        "   \u{251c}\u{2500}\u{2500}"
+    );
+    assert_eq!(guide_indentation(&[false, true], 0), "");
+}
@@ -0,0 +1,177 @@
+use crate::error::{resolve, resolve_mut, TreeError};
+use crate::identifier::TreeIdentifierVec;
+use crate::{TreeItem, TreeState};
+
+/// Reference to a [`TreeItem`] which follows the item when the items change
+///
+/// Identifiers are positions, so an identifier kept while the items are reordered references a
+/// different item afterwards.
+/// A handle is tracked by the [`TreeState`] it was created with instead.
+/// Structural changes through the state like [`tree_ops`](crate::tree_ops) or
+/// [`reconcile`](TreeState::reconcile) move the handle along with its item.
+/// When the item is removed or the items changed in an unknown way, signalled by
+/// [`invalidate`](TreeState::invalidate), using the handle fails with
+/// [`TreeError::StaleHandle`] instead of changing the wrong item.
+///
+/// Created by [`TreeState::handle`] and [`tree_ops::insert_child`](crate::tree_ops::insert_child).
+/// [Release](TreeState::release_handle) handles which are not needed anymore.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::tree_ops::{insert_child, rotate_children};
+/// # use tui_tree_widget::{TreeError, TreeItem, TreeState};
+/// let mut items = vec![TreeItem::new_leaf("a")];
+/// let mut state = TreeState::default();
+///
+/// let handle = insert_child(&mut items, &[], 1, TreeItem::new_leaf("loading"), &mut state)?;
+/// // The handle is passed to an async task, meanwhile the items are reordered
+/// rotate_children(&mut items, &[], 1, 0, &mut state)?;
+///
+/// // The result of the task arrives
+/// let item = state.resolve_handle_mut(&mut items, &handle)?;
+/// item.add_child(TreeItem::new_leaf("loaded"));
+/// assert_eq!(items[0].children().len(), 1);
+/// state.release_handle(&handle);
+///
+/// items.clear();
+/// state.invalidate();
+/// assert!(matches!(
+///     state.resolve_handle_mut(&mut items, &handle),
+///     Err(TreeError::StaleHandle(_))
+/// ));
+/// # Ok::<(), TreeError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemHandle {
+    id: u64,
+    identifier: TreeIdentifierVec,
+}
+
+impl ItemHandle {
+    /// Identifier of the item at the time the handle was created,
+    /// see [`TreeState::handle_identifier`] for the current one
+    pub fn identifier(&self) -> &[usize] {
+        &self.identifier
+    }
+}
+
+impl TreeState {
+    /// Create an [`ItemHandle`] for the item with the identifier which follows the item through
+    /// structural changes
+    pub fn handle(&mut self, identifier: TreeIdentifierVec) -> ItemHandle {
+        let id = self.next_handle;
+        self.next_handle = self.next_handle.wrapping_add(1);
+        self.handles.insert(id, identifier.clone());
+        ItemHandle { id, identifier }
+    }
+
+    /// Forget the handle, using it afterwards fails with [`TreeError::StaleHandle`]
+    pub fn release_handle(&mut self, handle: &ItemHandle) {
+        self.handles.remove(&handle.id);
+    }
+
+    /// Whether the item of the handle is still known
+    pub fn is_current(&self, handle: &ItemHandle) -> bool {
+        self.handles.contains_key(&handle.id)
+    }
+
+    /// Current identifier of the item of the handle.
+    /// Returns `None` when the handle is stale.
+    pub fn handle_identifier(&self, handle: &ItemHandle) -> Option<&[usize]> {
+        self.handles.get(&handle.id).map(Vec::as_slice)
+    }
+
+    /// Get the [`TreeItem`] referenced by the handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TreeError::StaleHandle`] when the item of the handle is not known anymore
+    /// or an error when the identifier does not exist.
+    pub fn resolve_handle<'a, A>(
+        &self,
+        items: &'a [TreeItem<A>],
+        handle: &ItemHandle,
+    ) -> Result<&'a TreeItem<A>, TreeError> {
+        resolve(items, self.current_identifier(handle)?)
+    }
+
+    /// Same as [`resolve_handle`](Self::resolve_handle) but the [`TreeItem`] is borrowed mutably
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TreeError::StaleHandle`] when the item of the handle is not known anymore
+    /// or an error when the identifier does not exist.
+    pub fn resolve_handle_mut<'a, A>(
+        &self,
+        items: &'a mut [TreeItem<A>],
+        handle: &ItemHandle,
+    ) -> Result<&'a mut TreeItem<A>, TreeError> {
+        resolve_mut(items, self.current_identifier(handle)?)
+    }
+
+    fn current_identifier(&self, handle: &ItemHandle) -> Result<&[usize], TreeError> {
+        self.handle_identifier(handle)
+            .ok_or_else(|| TreeError::StaleHandle(handle.identifier.clone()))
+    }
+}
+
+#[test]
+fn handles_become_stale_on_unknown_changes() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let handle = state.handle(vec![1, 1, 0]);
+    state
+        .resolve_handle_mut(&mut items, &handle)
+        .unwrap()
+        .add_child(TreeItem::new_leaf("x"));
+    assert_eq!(items[1].children[1].children[0].children.len(), 1);
+
+    // Changing the state without touching the items keeps the handle
+    state.open(vec![1]);
+    state.select(vec![2]);
+    assert!(state.is_current(&handle));
+
+    state.invalidate();
+    assert_eq!(
+        state.resolve_handle(&items, &handle).unwrap_err(),
+        TreeError::StaleHandle(vec![1, 1, 0])
+    );
+    let handle = state.handle(vec![7]);
+    assert!(matches!(
+        state.resolve_handle(&items, &handle),
+        Err(TreeError::OutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn concurrent_handles_follow_their_items() {
+    use crate::tree_ops::insert_child;
+
+    let mut items = vec![TreeItem::new_leaf("a")];
+    let mut state = TreeState::default();
+    let first = insert_child(&mut items, &[], 0, TreeItem::new_leaf("first"), &mut state).unwrap();
+    let second =
+        insert_child(&mut items, &[], 0, TreeItem::new_leaf("second"), &mut state).unwrap();
+    assert_eq!(state.handle_identifier(&first), Some(&[1][..]));
+
+    state
+        .resolve_handle_mut(&mut items, &first)
+        .unwrap()
+        .add_child(TreeItem::new_leaf("x"));
+    state
+        .resolve_handle_mut(&mut items, &second)
+        .unwrap()
+        .add_child(TreeItem::new_leaf("y"));
+    assert_eq!(items[0].elem, "second");
+    assert_eq!(items[0].children[0].elem, "y");
+    assert_eq!(items[1].elem, "first");
+    assert_eq!(items[1].children[0].elem, "x");
+
+    state.release_handle(&first);
+    assert_eq!(
+        state.resolve_handle(&items, &first).unwrap_err(),
+        TreeError::StaleHandle(vec![0])
+    );
+    assert!(state.is_current(&second));
+}
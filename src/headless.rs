@@ -0,0 +1,38 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::widgets::StatefulWidget;
+
+use crate::{Tree, TreeItemRender, TreeState};
+
+impl<'a, A: TreeItemRender> Tree<'a, A> {
+    /// Render the tree into a new [`Buffer`] of the given size without a terminal.
+    ///
+    /// Useful for screenshot tools, documentation or bridges to web terminals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    ///
+    /// let buffer = Tree::new(items).render_to_buffer(6, 2, &mut state);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["\u{25bc} a   ", "    b "]));
+    /// ```
+    pub fn render_to_buffer(self, width: u16, height: u16, state: &mut TreeState) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buffer = Buffer::empty(area);
+        StatefulWidget::render(self, area, &mut buffer, state);
+        buffer
+    }
+}
+
+#[test]
+fn render_to_empty_area() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    let buffer = Tree::new(items).render_to_buffer(0, 0, &mut state);
+    assert_eq!(buffer.area, Rect::default());
+}
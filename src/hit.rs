@@ -0,0 +1,221 @@
+use crate::flatten::flatten_state;
+use crate::identifier::TreeIdentifierVec;
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+/// Part of a row which was hit by a position, returned by [`TreeState::hit_test`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HitTarget {
+    /// Highlight symbol column and indentation in front of the node symbol
+    Gutter,
+    /// The open/close symbol of a node with children
+    Arrow,
+    /// The [checkbox](crate::Tree::checkboxes) of the node
+    Checkbox,
+    /// The rendered text of the item
+    Label,
+    /// The [suffix](crate::TreeItemRender::suffix) of the item
+    Suffix,
+}
+
+/// Layout of a row as it was rendered last, used for hit testing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RenderedRow {
+    pub identifier: TreeIdentifierVec,
+    pub y: u16,
+    pub height: u16,
+    pub x: u16,
+    pub width: u16,
+    /// Position of the open/close symbol, `None` for leafs
+    pub arrow_x: Option<u16>,
+    pub arrow_width: u16,
+    /// Position of the checkbox, `None` without checkboxes
+    pub checkbox_x: Option<u16>,
+    pub label_x: u16,
+    pub suffix_x: Option<u16>,
+    /// Rendered [right to left](crate::Tree::right_to_left).
+    /// The positions above are stored left to right and mirrored when hit testing.
+    pub mirrored: bool,
+}
+
+impl RenderedRow {
+    fn target(&self, x: u16) -> HitTarget {
+        if self.suffix_x.map_or(false, |suffix_x| x >= suffix_x) {
+            HitTarget::Suffix
+        } else if x >= self.label_x {
+            HitTarget::Label
+        } else if self.checkbox_x.map_or(false, |checkbox_x| x >= checkbox_x) {
+            HitTarget::Checkbox
+        } else if self.arrow_x.map_or(false, |arrow_x| {
+            x >= arrow_x && x < arrow_x + self.arrow_width
+        }) {
+            HitTarget::Arrow
+        } else {
+            HitTarget::Gutter
+        }
+    }
+}
+
+impl TreeState {
+    /// Find the item and the part of its row at the position of the last render.
+    ///
+    /// Useful for mouse support as the position can be taken directly from the mouse event.
+    /// Returns `None` when the position is outside of the rendered rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{HitTarget, Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    ///
+    /// let area = Rect::new(0, 0, 10, 5);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items).render(area, &mut buffer, &mut state);
+    ///
+    /// assert_eq!(state.hit_test((0, 0)), Some((vec![0], HitTarget::Arrow)));
+    /// assert_eq!(state.hit_test((2, 0)), Some((vec![0], HitTarget::Label)));
+    /// assert_eq!(state.hit_test((2, 1)), None);
+    /// ```
+    pub fn hit_test(&self, position: (u16, u16)) -> Option<(TreeIdentifierVec, HitTarget)> {
+        let (x, y) = position;
+        self.rendered
+            .iter()
+            .find(|row| y >= row.y && y < row.y + row.height && x >= row.x && x < row.x + row.width)
+            .map(|row| {
+                let x = if row.mirrored {
+                    row.x + row.width - 1 - (x - row.x)
+                } else {
+                    x
+                };
+                (row.identifier.clone(), row.target(x))
+            })
+    }
+
+    /// Handle a mouse click at the position of the last render.
+    ///
+    /// Clicking the [arrow](HitTarget::Arrow) of a node toggles it, any other part of a row
+    /// selects its node.
+    /// Rows spanning multiple lines are hit on every one of their lines.
+    /// Returns what was hit, `None` when the click was outside of the rendered rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    ///
+    /// let area = Rect::new(0, 0, 10, 5);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items.clone()).render(area, &mut buffer, &mut state);
+    /// state.click_at((0, 0));
+    /// assert_eq!(state.get_all_opened(), [vec![0]]);
+    ///
+    /// Tree::new(items).render(area, &mut buffer, &mut state);
+    /// state.click_at((5, 1));
+    /// assert_eq!(state.selected(), [0, 0]);
+    /// ```
+    pub fn click_at(&mut self, position: (u16, u16)) -> Option<(TreeIdentifierVec, HitTarget)> {
+        let (identifier, target) = self.hit_test(position)?;
+        if target == HitTarget::Arrow {
+            self.toggle(identifier.clone());
+        } else {
+            self.select(identifier.clone());
+        }
+        Some((identifier, target))
+    }
+
+    /// Scroll the view down by the amount of rows like a mouse wheel does.
+    ///
+    /// The selection is moved along when it would be scrolled off.
+    /// Uses the amount of rows of the last render as the height of the view.
+    pub fn scroll_down<A: TreeItemRender>(&mut self, items: &[TreeItem<A>], rows: usize) {
+        let visible = flatten_state(self, items);
+        let page = self.rendered.len().max(1);
+        self.offset = self
+            .offset
+            .saturating_add(rows)
+            .min(visible.len().saturating_sub(page));
+        let selected = visible.iter().position(|o| o.identifier == self.selected);
+        if let (Some(selected), Some(first)) = (selected, visible.get(self.offset)) {
+            if selected < self.offset {
+                self.select(first.identifier.clone());
+            }
+        }
+    }
+
+    /// Scroll the view up by the amount of rows like a mouse wheel does.
+    ///
+    /// The selection is moved along when it would be scrolled off.
+    /// Uses the amount of rows of the last render as the height of the view.
+    pub fn scroll_up<A: TreeItemRender>(&mut self, items: &[TreeItem<A>], rows: usize) {
+        let visible = flatten_state(self, items);
+        let page = self.rendered.len().max(1);
+        self.offset = self
+            .offset
+            .saturating_sub(rows)
+            .min(visible.len().saturating_sub(1));
+        let last = (self.offset + page - 1).min(visible.len().saturating_sub(1));
+        let selected = visible.iter().position(|o| o.identifier == self.selected);
+        if let (Some(selected), Some(last)) = (selected, visible.get(last)) {
+            if selected > self.offset + page - 1 {
+                self.select(last.identifier.clone());
+            }
+        }
+    }
+}
+
+#[test]
+fn target_of_row() {
+    let row = RenderedRow {
+        identifier: vec![0, 1],
+        y: 3,
+        height: 1,
+        x: 0,
+        width: 20,
+        arrow_x: Some(4),
+        arrow_width: 1,
+        checkbox_x: None,
+        label_x: 6,
+        suffix_x: Some(16),
+        mirrored: false,
+    };
+    assert_eq!(row.target(0), HitTarget::Gutter);
+    assert_eq!(row.target(4), HitTarget::Arrow);
+    assert_eq!(row.target(5), HitTarget::Gutter);
+    assert_eq!(row.target(6), HitTarget::Label);
+    assert_eq!(row.target(16), HitTarget::Suffix);
+}
+
+#[test]
+fn scrolling_moves_selection_along() {
+    use tui::buffer::Buffer;
+    use tui::layout::Rect;
+    use tui::widgets::StatefulWidget;
+
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![0]);
+    let area = Rect::new(0, 0, 10, 3);
+    let mut buffer = Buffer::empty(area);
+    crate::Tree::new(items.clone()).render(area, &mut buffer, &mut state);
+
+    state.scroll_down(&items, 2);
+    assert_eq!(state.get_offset(), 2);
+    assert_eq!(state.selected(), [1, 0]);
+    state.scroll_down(&items, 10);
+    assert_eq!(state.get_offset(), 3);
+    assert_eq!(state.selected(), [1, 1]);
+
+    state.scroll_up(&items, 4);
+    assert_eq!(state.get_offset(), 0);
+    assert_eq!(state.selected(), [1, 0]);
+}
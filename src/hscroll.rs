@@ -0,0 +1,124 @@
+use std::borrow::Cow;
+
+use tui::text::{Span, Spans, Text};
+use unicode_width::UnicodeWidthChar;
+
+use crate::{Tree, TreeState};
+
+/// Marker of columns cut off, see [`Tree::ellipsis`]
+pub(crate) const ELLIPSIS: &str = "\u{2026}";
+
+impl TreeState {
+    /// Columns the labels are scrolled to the left
+    pub const fn horizontal_offset(&self) -> usize {
+        self.horizontal_offset
+    }
+
+    /// Scroll the labels to the left by the amount of columns to show their ends.
+    ///
+    /// The indentation and symbols stay in place.
+    /// The offset is limited to the widest visible label when rendered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a very long label")];
+    /// let mut state = TreeState::default();
+    /// state.scroll_right(7);
+    ///
+    /// let area = Rect::new(0, 0, 10, 1);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .ellipsis(true)
+    ///     .render(area, &mut buffer, &mut state);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["  …ong la…"]));
+    /// ```
+    pub fn scroll_right(&mut self, columns: usize) {
+        self.horizontal_offset = self.horizontal_offset.saturating_add(columns);
+    }
+
+    /// Scroll the labels back to the right by the amount of columns
+    pub fn scroll_left(&mut self, columns: usize) {
+        self.horizontal_offset = self.horizontal_offset.saturating_sub(columns);
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Show `…` where labels are cut off at their start by a
+    /// [horizontal scroll](TreeState::scroll_right) or at their end by the width of the area
+    #[must_use]
+    pub const fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+}
+
+/// Drop the first `columns` of each line.
+/// Wide characters cut in half are replaced by spaces.
+/// With `ellipsis` the first remaining column of cut lines shows `…` instead.
+pub(crate) fn skip_columns(text: Text, columns: usize, ellipsis: bool) -> Text {
+    if columns == 0 {
+        return text;
+    }
+    let lines = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            if ellipsis && line.width() > 0 {
+                let mut spans = skip_line_columns(line, columns + 1);
+                let style = spans.0.first().map(|span| span.style).unwrap_or_default();
+                spans.0.insert(0, Span::styled(ELLIPSIS, style));
+                spans
+            } else {
+                skip_line_columns(line, columns)
+            }
+        })
+        .collect();
+    Text { lines }
+}
+
+fn skip_line_columns(line: Spans, columns: usize) -> Spans {
+    let mut remaining = columns;
+    let mut spans = Vec::new();
+    for span in line.0 {
+        if remaining == 0 {
+            spans.push(span);
+            continue;
+        }
+        let mut content = String::new();
+        for c in span.content.chars() {
+            let width = c.width().unwrap_or(0);
+            if remaining == 0 {
+                content.push(c);
+            } else if width > remaining {
+                // The rest of a wide character
+                content.extend(std::iter::repeat(' ').take(width - remaining));
+                remaining = 0;
+            } else {
+                remaining -= width;
+            }
+        }
+        if !content.is_empty() {
+            spans.push(Span::styled(Cow::Owned(content), span.style));
+        }
+    }
+    Spans(spans)
+}
+
+#[test]
+fn skips_columns_across_spans() {
+    let text = Text::from(Spans(vec![Span::raw("ab"), Span::raw("c\u{754c}d")]));
+    let skipped = skip_columns(text.clone(), 3, false);
+    assert_eq!(skipped, Text::from(Spans(vec![Span::raw("\u{754c}d")])));
+    let skipped = skip_columns(text.clone(), 4, false);
+    assert_eq!(skipped, Text::from(Spans(vec![Span::raw(" d")])));
+    let skipped = skip_columns(text, 1, true);
+    assert_eq!(
+        skipped,
+        Text::from(Spans(vec![Span::raw(ELLIPSIS), Span::raw("c\u{754c}d")]))
+    );
+}
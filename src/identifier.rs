@@ -23,7 +23,7 @@ pub type TreeIdentifierVec = Vec<usize>;
 /// assert_eq!(branch, []);
 /// assert_eq!(leaf, None);
 /// ```
-pub fn get_without_leaf(identifier: TreeIdentifier) -> (TreeIdentifier, Option<&usize>) {
+pub fn get_without_leaf(identifier: TreeIdentifier<'_>) -> (TreeIdentifier<'_>, Option<&usize>) {
     let length = identifier.len();
     let length_without_leaf = length.saturating_sub(1);
 
@@ -32,3 +32,22 @@ pub fn get_without_leaf(identifier: TreeIdentifier) -> (TreeIdentifier, Option<&
 
     (branch, leaf)
 }
+
+/// Ancestors of the identifier which have to be opened so it is visible, starting at the root
+///
+/// Allows precomputing which nodes to open, like for opening an app at a node given as an
+/// argument.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::required_ancestors;
+/// let ancestors = required_ancestors(&[2, 4, 6]).collect::<Vec<_>>();
+/// assert_eq!(ancestors, [vec![2], vec![2, 4]]);
+/// assert_eq!(required_ancestors(&[2]).count(), 0);
+/// ```
+pub fn required_ancestors(
+    identifier: TreeIdentifier<'_>,
+) -> impl Iterator<Item = TreeIdentifierVec> + '_ {
+    (1..identifier.len()).map(move |length| identifier[..length].to_vec())
+}
@@ -0,0 +1,62 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::indentation;
+
+/// Indentation of the rendered depths built once per render instead of once per row
+///
+/// Plain indentation is a slice of a single string of spaces.
+/// Depth counters (see [`Tree::max_indent_cols`](crate::Tree::max_indent_cols)) are built once
+/// per depth.
+pub(crate) struct Indents {
+    spaces: String,
+    counters: HashMap<usize, String>,
+    indent_width: usize,
+    max_cols: Option<u16>,
+}
+
+impl Indents {
+    pub fn new<I: IntoIterator<Item = usize>>(
+        depths: I,
+        indent_width: usize,
+        max_cols: Option<u16>,
+    ) -> Self {
+        let mut spaces = 0;
+        let mut counters = HashMap::new();
+        for depth in depths.into_iter().collect::<BTreeSet<_>>() {
+            let indent = indentation(depth, indent_width, max_cols);
+            if indent.bytes().all(|b| b == b' ') {
+                spaces = spaces.max(indent.len());
+            } else {
+                counters.insert(depth, indent);
+            }
+        }
+        Self {
+            spaces: " ".repeat(spaces),
+            counters,
+            indent_width,
+            max_cols,
+        }
+    }
+
+    /// Indentation of the depth like [`indentation`]
+    pub fn get(&self, depth: usize) -> &str {
+        let width = depth * self.indent_width;
+        let max_cols = self.max_cols.map_or(width, usize::from);
+        if let Some(counter) = self.counters.get(&depth) {
+            return counter;
+        }
+        &self.spaces[..width.min(max_cols).min(self.spaces.len())]
+    }
+}
+
+#[test]
+fn matches_indentation() {
+    let indents = Indents::new(vec![0, 1, 2, 3, 12, 3], 2, Some(4));
+    for depth in [0, 1, 2, 3, 12] {
+        assert_eq!(indents.get(depth), indentation(depth, 2, Some(4)));
+    }
+
+    let indents = Indents::new(vec![5, 1], 3, None);
+    assert_eq!(indents.get(5), indentation(5, 3, None));
+    assert_eq!(indents.get(1), indentation(1, 3, None));
+}
@@ -0,0 +1,173 @@
+//! Property based testing of the [`TreeState`] invariants with [`proptest`]
+//!
+//! Random trees and sequences of [`Operation`s](Operation) are generated, applied to a
+//! [`TreeState`] and checked with [`check_invariants`] after every step and with
+//! [`check_rendered`] after rendering.
+//! Apps wrapping the tree can reuse the strategies to test their own state on top of it.
+//!
+//! Requires the `test-utils` feature.
+//!
+//! # Example
+//!
+//! ```
+//! # use proptest::prelude::*;
+//! # use tui_tree_widget::invariants::{check_invariants, operations, tree_items};
+//! # use tui_tree_widget::TreeState;
+//! proptest!(|(items in tree_items(), operations in operations())| {
+//!     let mut state = TreeState::default();
+//!     for operation in operations {
+//!         operation.apply(&mut state, &items);
+//!         prop_assert_eq!(check_invariants(&state, &items), Ok(()));
+//!     }
+//! });
+//! ```
+
+use proptest::prelude::*;
+use tui::buffer::Buffer;
+use tui::layout::{Corner, Rect};
+use tui::widgets::StatefulWidget;
+
+use crate::flatten::flatten_state;
+use crate::{Tree, TreeAction, TreeItem, TreeItemRender, TreeState};
+
+/// Labels of the generated items, some of them spanning multiple lines or none at all
+const LABELS: &[&str] = &["a", "b", "multi\nline", "tall\n\n\nitem", ""];
+
+/// Change of a [`TreeState`] as it happens in an app
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Action(TreeAction),
+    /// Render into an area of the given height, with the first row at the top or the bottom
+    Render {
+        height: u16,
+        bottom_up: bool,
+    },
+    SetLineNavigation(bool),
+}
+
+impl Operation {
+    pub fn apply<A: TreeItemRender + Clone>(&self, state: &mut TreeState, items: &[TreeItem<A>]) {
+        match self {
+            Self::Action(action) => state.dispatch(*action, items),
+            Self::Render { height, bottom_up } => {
+                let corner = if *bottom_up {
+                    Corner::BottomLeft
+                } else {
+                    Corner::TopLeft
+                };
+                let area = Rect::new(0, 0, 12, *height);
+                let mut buffer = Buffer::empty(area);
+                let tree = Tree::new(items.to_vec())
+                    .highlight_symbol(">")
+                    .start_corner(corner);
+                tree.render(area, &mut buffer, state);
+            }
+            Self::SetLineNavigation(enabled) => state.set_line_navigation(*enabled),
+        }
+    }
+}
+
+/// Random non empty trees of up to four levels
+pub fn tree_items() -> impl Strategy<Value = Vec<TreeItem<&'static str>>> {
+    let label = proptest::sample::select(LABELS);
+    let leaf = label.clone().prop_map(TreeItem::new_leaf);
+    let item = leaf.prop_recursive(3, 32, 4, move |children| {
+        (label.clone(), proptest::collection::vec(children, 0..4))
+            .prop_map(|(label, children)| TreeItem::new(label, children))
+    });
+    proptest::collection::vec(item, 1..6)
+}
+
+/// Random sequences of [`Operation`s](Operation)
+pub fn operations() -> impl Strategy<Value = Vec<Operation>> {
+    let action = proptest::sample::select(vec![
+        TreeAction::Up,
+        TreeAction::Down,
+        TreeAction::Left,
+        TreeAction::Right,
+        TreeAction::Toggle,
+        TreeAction::First,
+        TreeAction::Last,
+        TreeAction::OpenAll,
+        TreeAction::CloseAll,
+    ]);
+    let operation = prop_oneof![
+        4 => action.prop_map(Operation::Action),
+        2 => (0..8_u16, any::<bool>())
+            .prop_map(|(height, bottom_up)| Operation::Render { height, bottom_up }),
+        1 => any::<bool>().prop_map(Operation::SetLineNavigation),
+    ];
+    proptest::collection::vec(operation, 0..32)
+}
+
+/// Check the invariants of the state for the items which hold after every [`Operation`].
+///
+/// - The selection always resolves to an item or is empty
+/// - Only existing nodes with children are opened
+///
+/// Returns a description of the first violated invariant.
+pub fn check_invariants<A: TreeItemRender>(
+    state: &TreeState,
+    items: &[TreeItem<A>],
+) -> Result<(), String> {
+    if !state.selected.is_empty() {
+        if let Err(err) = crate::error::resolve(items, &state.selected) {
+            return Err(format!("selection does not resolve: {}", err));
+        }
+    }
+    for identifier in state.opened.iter() {
+        match crate::error::resolve(items, &identifier) {
+            Ok(item) if !item.children.is_empty() => {}
+            _ => return Err(format!("opened {:?} has no children", identifier)),
+        }
+    }
+    Ok(())
+}
+
+/// Check the invariants of the state for the items which hold directly after an
+/// [`Operation::Render`] into an area of at least one line.
+///
+/// - The offset never exceeds the flattened length
+/// - A visible selection is among the rendered rows
+///
+/// Returns a description of the first violated invariant.
+pub fn check_rendered<A: TreeItemRender>(
+    state: &TreeState,
+    items: &[TreeItem<A>],
+) -> Result<(), String> {
+    let visible = flatten_state(state, items);
+    if state.offset >= visible.len() {
+        return Err(format!(
+            "offset {} exceeds the {} visible rows",
+            state.offset,
+            visible.len()
+        ));
+    }
+
+    let is_visible = visible.iter().any(|o| o.identifier == state.selected);
+    let is_rendered = state
+        .rendered
+        .iter()
+        .any(|row| row.identifier == state.selected);
+    if is_visible && !is_rendered {
+        return Err(format!(
+            "selection {:?} is visible but was not rendered",
+            state.selected
+        ));
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn operations_keep_invariants(items in tree_items(), operations in operations()) {
+        let mut state = TreeState::default();
+        for operation in operations {
+            operation.apply(&mut state, &items);
+            prop_assert_eq!(check_invariants(&state, &items), Ok(()));
+            if matches!(operation, Operation::Render { height, .. } if height > 0) {
+                prop_assert_eq!(check_rendered(&state, &items), Ok(()));
+            }
+        }
+    }
+}
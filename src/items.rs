@@ -0,0 +1,163 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::flatten::{flatten_state, Flattened};
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+/// All the root [`TreeItem`s](TreeItem) of a [`Tree`](crate::Tree)
+///
+/// Dereferences to `[TreeItem<A>]` so it can be used everywhere a slice of items is expected.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{TreeItem, TreeItems};
+/// let items = TreeItems::from(vec![
+///     TreeItem::new_leaf("a"),
+///     TreeItem::new("b", vec![TreeItem::new_leaf("c")]),
+/// ]);
+///
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items.len_recursive(), 3);
+/// assert!(items.get(&[1, 0]).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreeItems<A>(pub Vec<TreeItem<A>>);
+
+impl<A> TreeItems<A> {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Get the [`TreeItem`] referenced by the identifier.
+    /// Returns `None` when the identifier is empty or does not exist.
+    pub fn get(&self, identifier: TreeIdentifier) -> Option<&TreeItem<A>> {
+        TreeItem::get(&self.0, identifier)
+    }
+
+    /// Get the [`TreeItem`] referenced by the identifier mutably.
+    /// Returns `None` when the identifier is empty or does not exist.
+    pub fn get_mut(&mut self, identifier: TreeIdentifier) -> Option<&mut TreeItem<A>> {
+        TreeItem::get_mut(&mut self.0, identifier)
+    }
+
+    /// Amount of [`TreeItem`s](TreeItem) including all their children
+    pub fn len_recursive(&self) -> usize {
+        self.iter_depth_first().count()
+    }
+
+    /// Iterate over all [`TreeItem`s](TreeItem) in depth first order, opened or not.
+    /// Every item is returned alongside its identifier.
+    pub fn iter_depth_first(&self) -> IterDepthFirst<'_, A> {
+        IterDepthFirst::new(&self.0)
+    }
+}
+
+impl<A: TreeItemRender> TreeItems<A> {
+    /// Get a flat list of all [`TreeItem`s](TreeItem) visible with the given [`TreeState`] in
+    /// the order they are rendered
+    pub fn flatten(&self, state: &TreeState) -> Vec<Flattened<'_, A>> {
+        flatten_state(state, &self.0)
+    }
+}
+
+impl<A> Default for TreeItems<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> Deref for TreeItems<A> {
+    type Target = [TreeItem<A>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<A> DerefMut for TreeItems<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<A> From<Vec<TreeItem<A>>> for TreeItems<A> {
+    fn from(items: Vec<TreeItem<A>>) -> Self {
+        Self(items)
+    }
+}
+
+impl<A> From<TreeItems<A>> for Vec<TreeItem<A>> {
+    fn from(items: TreeItems<A>) -> Self {
+        items.0
+    }
+}
+
+impl<A> FromIterator<TreeItem<A>> for TreeItems<A> {
+    fn from_iter<I: IntoIterator<Item = TreeItem<A>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Depth first iterator over [`TreeItems`]
+///
+/// Created by [`TreeItems::iter_depth_first`].
+pub struct IterDepthFirst<'a, A> {
+    stack: Vec<(TreeIdentifierVec, &'a TreeItem<A>)>,
+}
+
+impl<'a, A> IterDepthFirst<'a, A> {
+    pub(crate) fn new(items: &'a [TreeItem<A>]) -> Self {
+        let stack = items
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, item)| (vec![index], item))
+            .collect();
+        Self { stack }
+    }
+}
+
+impl<'a, A> Iterator for IterDepthFirst<'a, A> {
+    type Item = (TreeIdentifierVec, &'a TreeItem<A>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (identifier, item) = self.stack.pop()?;
+        for (index, child) in item.children.iter().enumerate().rev() {
+            let mut child_identifier = identifier.clone();
+            child_identifier.push(index);
+            self.stack.push((child_identifier, child));
+        }
+        Some((identifier, item))
+    }
+}
+
+#[test]
+fn get_returns_nested_item() {
+    let items = TreeItems::from(crate::flatten::get_example_tree_items());
+    assert_eq!(items.get(&[1, 1, 0]).unwrap().elem, "e");
+    assert_eq!(items.get(&[2]).unwrap().elem, "h");
+    assert!(items.get(&[]).is_none());
+    assert!(items.get(&[1, 5]).is_none());
+}
+
+#[test]
+fn get_mut_modifies_nested_item() {
+    let mut items = TreeItems::from(crate::flatten::get_example_tree_items());
+    items
+        .get_mut(&[1, 1])
+        .unwrap()
+        .add_child(TreeItem::new_leaf("x"));
+    assert_eq!(items.get(&[1, 1, 2]).unwrap().elem, "x");
+}
+
+#[test]
+fn iter_depth_first_visits_everything_in_order() {
+    let items = TreeItems::from(crate::flatten::get_example_tree_items());
+    let result = items
+        .iter_depth_first()
+        .map(|(_, item)| item.elem)
+        .collect::<Vec<_>>();
+    assert_eq!(result, ["a", "b", "c", "d", "e", "f", "g", "h"]);
+    assert_eq!(items.len_recursive(), 8);
+}
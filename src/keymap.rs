@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+/// Named action on a [`TreeState`] which can be bound to keys with a [`TreeKeymap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum TreeAction {
+    /// [`TreeState::key_up`]
+    Up,
+    /// [`TreeState::key_down`]
+    Down,
+    /// [`TreeState::key_left`]
+    Left,
+    /// [`TreeState::key_right`] without opening leaves
+    Right,
+    /// [`TreeState::toggle_selected_with`]
+    Toggle,
+    /// [`TreeState::select_first`]
+    First,
+    /// [`TreeState::select_last`]
+    Last,
+    /// [`TreeState::open_all`]
+    OpenAll,
+    /// [`TreeState::close_all`]
+    CloseAll,
+}
+
+/// Key without modifiers of a [`KeyChord`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Enter,
+    Tab,
+    Backspace,
+    Esc,
+}
+
+/// Key with modifiers like `ctrl+k`
+///
+/// Parsed from and displayed as the modifiers `ctrl`, `alt` and `shift` followed by the key,
+/// separated by `+`.
+/// Named keys like `up`, `pageup` or `space` are case insensitive, single chars are not.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{Key, KeyChord};
+/// let chord = "ctrl+k".parse::<KeyChord>().unwrap();
+/// assert_eq!(chord, KeyChord::new(Key::Char('k')).ctrl());
+/// assert_eq!(chord.to_string(), "ctrl+k");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct KeyChord {
+    pub key: Key,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    #[must_use]
+    pub const fn new(key: Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    #[must_use]
+    pub const fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    #[must_use]
+    pub const fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Chars carry shift themselves, like `shift+g` being `G`
+    fn normalized(self) -> Self {
+        match self.key {
+            Key::Char(c) if self.shift => Self {
+                key: Key::Char(c.to_uppercase().next().unwrap_or(c)),
+                shift: false,
+                ..self
+            },
+            _ => self,
+        }
+    }
+}
+
+/// Error when parsing a [`KeyChord`] fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyChordError(String);
+
+impl fmt::Display for ParseKeyChordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key chord {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseKeyChordError {}
+
+impl FromStr for KeyChord {
+    type Err = ParseKeyChordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseKeyChordError(s.to_string());
+        // The key itself might be a plus
+        let (modifiers, key) = match s.strip_suffix('+') {
+            Some(modifiers) => (modifiers.strip_suffix('+').unwrap_or(modifiers), "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let key = match key.to_lowercase().as_str() {
+            "up" => Key::Up,
+            "down" => Key::Down,
+            "left" => Key::Left,
+            "right" => Key::Right,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            "enter" => Key::Enter,
+            "tab" => Key::Tab,
+            "backspace" => Key::Backspace,
+            "esc" => Key::Esc,
+            "space" => Key::Char(' '),
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Key::Char(c),
+                    _ => return Err(error()),
+                }
+            }
+        };
+
+        let mut chord = Self::new(key);
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" => chord.ctrl = true,
+                "alt" => chord.alt = true,
+                "shift" => chord.shift = true,
+                _ => return Err(error()),
+            }
+        }
+        Ok(chord)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("alt+")?;
+        }
+        if self.shift {
+            f.write_str("shift+")?;
+        }
+        let key = match self.key {
+            Key::Char(' ') => "space",
+            Key::Char(c) => return write!(f, "{}", c),
+            Key::Up => "up",
+            Key::Down => "down",
+            Key::Left => "left",
+            Key::Right => "right",
+            Key::Home => "home",
+            Key::End => "end",
+            Key::PageUp => "pageup",
+            Key::PageDown => "pagedown",
+            Key::Enter => "enter",
+            Key::Tab => "tab",
+            Key::Backspace => "backspace",
+            Key::Esc => "esc",
+        };
+        f.write_str(key)
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = ParseKeyChordError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(chord: KeyChord) -> Self {
+        chord.to_string()
+    }
+}
+
+/// Bindings of [`TreeAction`s](TreeAction) to [`KeyChord`s](KeyChord)
+///
+/// With the `serde` feature the keymap can be loaded from a config file so end users can rebind
+/// the keys of the tree.
+/// The chords are written like `ctrl+k`, see [`KeyChord`].
+/// Actions in the config replace their default chords, the other actions keep them.
+/// A chord bound to several actions in the config is rejected.
+///
+/// Each chord is bound to one action only.
+/// Shift is ignored for chars as the char itself already tells whether shift was held, so `G`
+/// matches regardless of whether the backend reports it with shift or not.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{Key, KeyChord, TreeAction, TreeItem, TreeKeymap, TreeState};
+/// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+/// let mut state = TreeState::default();
+///
+/// let mut keymap = TreeKeymap::default();
+/// keymap.bind(TreeAction::Down, "ctrl+n".parse().unwrap());
+///
+/// // Within the event loop, translated from the key event of the backend
+/// let chord = KeyChord::new(Key::Char('n')).ctrl();
+/// if let Some(action) = keymap.action(chord) {
+///     state.dispatch(action, &items);
+/// }
+/// assert_eq!(state.selected(), [0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeKeymap {
+    bindings: HashMap<TreeAction, Vec<KeyChord>>,
+}
+
+impl TreeKeymap {
+    /// Keymap without any bindings
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Additionally bind the chord to the action.
+    /// A chord is bound to one action only, so it is removed from the action it was bound to
+    /// before.
+    pub fn bind(&mut self, action: TreeAction, chord: KeyChord) {
+        let chord = chord.normalized();
+        for chords in self.bindings.values_mut() {
+            chords.retain(|bound| *bound != chord);
+        }
+        self.bindings.retain(|_, chords| !chords.is_empty());
+        self.bindings.entry(action).or_default().push(chord);
+    }
+
+    /// Remove all bindings of the action
+    pub fn unbind(&mut self, action: TreeAction) {
+        self.bindings.remove(&action);
+    }
+
+    /// Chords bound to the action
+    pub fn chords(&self, action: TreeAction) -> &[KeyChord] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The action bound to the chord
+    pub fn action(&self, chord: KeyChord) -> Option<TreeAction> {
+        let chord = chord.normalized();
+        self.bindings
+            .iter()
+            .find(|(_, chords)| chords.contains(&chord))
+            .map(|(action, _)| *action)
+    }
+}
+
+impl Default for TreeKeymap {
+    /// Arrow keys and vim keys. `*` opens all nodes.
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        let defaults = [
+            (TreeAction::Up, [Key::Up, Key::Char('k')]),
+            (TreeAction::Down, [Key::Down, Key::Char('j')]),
+            (TreeAction::Left, [Key::Left, Key::Char('h')]),
+            (TreeAction::Right, [Key::Right, Key::Char('l')]),
+            (TreeAction::Toggle, [Key::Enter, Key::Char(' ')]),
+            (TreeAction::First, [Key::Home, Key::Char('g')]),
+            (TreeAction::Last, [Key::End, Key::Char('G')]),
+        ];
+        for (action, keys) in defaults {
+            for key in keys {
+                keymap.bind(action, KeyChord::new(key));
+            }
+        }
+        keymap.bind(TreeAction::OpenAll, KeyChord::new(Key::Char('*')));
+        keymap
+    }
+}
+
+/// Bindings of a [`TreeKeymap`] as written in a config
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct KeymapConfig {
+    bindings: HashMap<TreeAction, Vec<KeyChord>>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TreeKeymap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        KeymapConfig {
+            bindings: self.bindings.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TreeKeymap {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let config = KeymapConfig::deserialize(deserializer)?;
+        let mut configured = HashMap::new();
+        for (action, chords) in &config.bindings {
+            for chord in chords {
+                let chord = chord.normalized();
+                match configured.insert(chord, *action) {
+                    Some(other) if other != *action => {
+                        return Err(serde::de::Error::custom(format!(
+                            "key chord {} is bound to {:?} and {:?}",
+                            chord, other, action
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut keymap = Self::default();
+        for (action, chords) in config.bindings {
+            keymap.unbind(action);
+            for chord in chords {
+                keymap.bind(action, chord);
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+impl TreeState {
+    /// Execute the action, for example one looked up with [`TreeKeymap::action`].
+    pub fn dispatch<A: TreeItemRender>(&mut self, action: TreeAction, items: &[TreeItem<A>]) {
+        match action {
+            TreeAction::Up => self.key_up(items),
+            TreeAction::Down => self.key_down(items),
+            TreeAction::Left => self.key_left(),
+            TreeAction::Right => {
+                // Leaves and missing nodes are not opened
+                let _ = self.try_open(items, self.selected());
+            }
+            TreeAction::Toggle => {
+                self.toggle_selected_with(items);
+            }
+            TreeAction::First => self.select_first(),
+            TreeAction::Last => self.select_last(items),
+            TreeAction::OpenAll => {
+                self.open_all(items);
+            }
+            TreeAction::CloseAll => self.close_all(),
+        }
+    }
+}
+
+#[test]
+fn parse_key_chords() {
+    let parse = |s: &str| s.parse::<KeyChord>();
+    assert_eq!(parse("up"), Ok(KeyChord::new(Key::Up)));
+    assert_eq!(parse("PageDown"), Ok(KeyChord::new(Key::PageDown)));
+    assert_eq!(parse("G"), Ok(KeyChord::new(Key::Char('G'))));
+    assert_eq!(
+        parse("ctrl+alt+space"),
+        Ok(KeyChord::new(Key::Char(' ')).ctrl().alt())
+    );
+    assert_eq!(parse("ctrl++"), Ok(KeyChord::new(Key::Char('+')).ctrl()));
+    assert!(parse("hyper+k").is_err());
+    assert!(parse("kk").is_err());
+    assert!(parse("").is_err());
+}
+
+#[test]
+fn display_roundtrips() {
+    for chord in ["ctrl+k", "shift+tab", "alt+space", "+", "ctrl++"] {
+        assert_eq!(chord.parse::<KeyChord>().unwrap().to_string(), chord);
+    }
+}
+
+#[test]
+fn dispatch_open_all() {
+    let items = crate::flatten::get_example_tree_items();
+    let keymap = TreeKeymap::default();
+    let mut state = TreeState::default();
+    let action = keymap.action(KeyChord::new(Key::Char('*'))).unwrap();
+    state.dispatch(action, &items);
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn keymap_from_config() {
+    let keymap: TreeKeymap =
+        serde_json::from_str(r#"{"bindings": {"Up": ["ctrl+p"], "CloseAll": ["-", "j"]}}"#)
+            .unwrap();
+    let ctrl_p = KeyChord::new(Key::Char('p')).ctrl();
+    assert_eq!(keymap.action(ctrl_p), Some(TreeAction::Up));
+    assert_eq!(keymap.action(KeyChord::new(Key::Up)), None);
+    assert_eq!(
+        keymap.action(KeyChord::new(Key::Char('-'))),
+        Some(TreeAction::CloseAll)
+    );
+    // Not configured actions keep their defaults without the chords taken by the config
+    assert_eq!(keymap.chords(TreeAction::Down), [KeyChord::new(Key::Down)]);
+    assert_eq!(
+        keymap.action(KeyChord::new(Key::Char('*'))),
+        Some(TreeAction::OpenAll)
+    );
+
+    assert!(serde_json::from_str::<TreeKeymap>(r#"{"bindings": {"Up": ["hyper+p"]}}"#).is_err());
+    let duplicate = r#"{"bindings": {"Up": ["shift+g"], "Down": ["G"]}}"#;
+    assert!(serde_json::from_str::<TreeKeymap>(duplicate).is_err());
+
+    let json = serde_json::to_string(&keymap).unwrap();
+    assert_eq!(serde_json::from_str::<TreeKeymap>(&json).unwrap(), keymap);
+}
+
+#[test]
+fn rebinding_moves_the_chord() {
+    let mut keymap = TreeKeymap::default();
+    let chord = KeyChord::new(Key::Char('j'));
+    keymap.bind(TreeAction::Up, chord);
+    keymap.bind(TreeAction::Up, chord);
+    assert_eq!(keymap.action(chord), Some(TreeAction::Up));
+    assert_eq!(keymap.chords(TreeAction::Down), [KeyChord::new(Key::Down)]);
+    assert_eq!(keymap.chords(TreeAction::Up).len(), 3);
+
+    keymap.bind(TreeAction::Last, KeyChord::new(Key::Char('*')));
+    assert!(keymap.chords(TreeAction::OpenAll).is_empty());
+}
+
+#[test]
+fn shift_is_part_of_chars() {
+    let keymap = TreeKeymap::default();
+    // Like crossterm reports it
+    let shift_g = KeyChord::new(Key::Char('G')).shift();
+    assert_eq!(keymap.action(shift_g), Some(TreeAction::Last));
+    // Like termion reports it
+    let g = KeyChord::new(Key::Char('G'));
+    assert_eq!(keymap.action(g), Some(TreeAction::Last));
+    assert_eq!(
+        keymap.action(KeyChord::new(Key::Char('g')).shift()),
+        Some(TreeAction::Last)
+    );
+    assert_eq!(
+        keymap.action(KeyChord::new(Key::Char('g'))),
+        Some(TreeAction::First)
+    );
+    assert_eq!(keymap.action(KeyChord::new(Key::Tab).shift()), None);
+}
+
+#[test]
+fn dispatch_right_opens_unloaded_lazy_node() {
+    let items = vec![TreeItem::new_lazy("a")];
+    let mut state = TreeState::default();
+    state.select_first();
+    state.dispatch(TreeAction::Right, &items);
+    assert_eq!(state.get_all_opened(), [vec![0]]);
+}
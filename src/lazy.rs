@@ -0,0 +1,164 @@
+use crate::error::{resolve_mut, TreeError};
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+impl<A: TreeItemRender> TreeItem<A> {
+    /// Create an item whose children are loaded when it is opened with
+    /// [`TreeState::open_lazy`].
+    ///
+    /// Until then it is rendered like a closed parent.
+    /// Useful for huge trees like a filesystem or a remote API which can not be built up front.
+    pub fn new_lazy(elem: A) -> Self {
+        let mut item = Self::new_leaf(elem);
+        item.lazy = true;
+        item
+    }
+}
+
+impl<A> TreeItem<A> {
+    /// Whether the item has children or children which are not loaded yet
+    pub fn has_children(&self) -> bool {
+        self.lazy || !self.children.is_empty()
+    }
+
+    /// Whether the children are not loaded yet, see [`TreeItem::new_lazy`]
+    pub const fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+}
+
+impl TreeState {
+    /// Open the node and load its children with `load` first when they are not loaded yet.
+    ///
+    /// The loaded children are kept within the items until they are unloaded with
+    /// [`unload_children`](Self::unload_children).
+    /// The state does not own the items, so closing the node with [`close`](Self::close) keeps
+    /// them and opening it again is instant.
+    /// Call [`unload_children`](Self::unload_children) instead of closing to drop them on close.
+    /// A node without any children after loading is not opened.
+    /// Returns `true` if the node was closed and has been opened.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let mut items = vec![TreeItem::new_lazy("remote")];
+    /// let mut state = TreeState::default();
+    ///
+    /// let opened = state.open_lazy(&mut items, vec![0], |_identifier, _elem| {
+    ///     vec![TreeItem::new_leaf("first"), TreeItem::new_leaf("second")]
+    /// });
+    /// assert_eq!(opened, Ok(true));
+    /// assert_eq!(items[0].children().len(), 2);
+    /// assert!(!items[0].is_lazy());
+    /// ```
+    pub fn open_lazy<A, F>(
+        &mut self,
+        items: &mut [TreeItem<A>],
+        identifier: TreeIdentifierVec,
+        load: F,
+    ) -> Result<bool, TreeError>
+    where
+        F: FnOnce(TreeIdentifier, &A) -> Vec<TreeItem<A>>,
+    {
+        let item = resolve_mut(items, &identifier)?;
+        if item.lazy {
+            *item.children = load(&identifier, &item.elem);
+            item.lazy = false;
+            self.invalidate_rows();
+        }
+        if item.children.is_empty() {
+            return Ok(false);
+        }
+        Ok(self.open(identifier))
+    }
+
+    /// Drop the loaded children of the node so they are loaded again the next time the node is
+    /// opened with [`open_lazy`](Self::open_lazy).
+    ///
+    /// Use this to free the memory of closed subtrees or to invalidate children which are out
+    /// of date.
+    /// The node is closed and everything stored for its descendants like opened, checked,
+    /// bookmarked or tagged nodes is dropped, so the next loaded children start out clean.
+    /// Nodes outside of the subtree which are [linked](Self::link) to descendants stay open.
+    /// A selected descendant is replaced by selecting the node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node.
+    pub fn unload_children<A>(
+        &mut self,
+        items: &mut [TreeItem<A>],
+        identifier: TreeIdentifier,
+    ) -> Result<(), TreeError> {
+        let item = resolve_mut(items, identifier)?;
+        item.children.clear();
+        item.lazy = true;
+        self.loading.remove(identifier);
+        self.close(identifier);
+
+        let is_descendant =
+            |other: TreeIdentifier| other.len() > identifier.len() && other.starts_with(identifier);
+        let selects_descendant = is_descendant(&self.selected);
+        self.remap_retain(&|other| (!is_descendant(other)).then(|| other.to_vec()));
+        if selects_descendant {
+            self.select(identifier.to_vec());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn unload_closes_descendants() {
+    let mut items = vec![TreeItem::new_leaf("a"), TreeItem::new_lazy("b")];
+    let mut state = TreeState::default();
+    let load =
+        |_: TreeIdentifier, _: &&str| vec![TreeItem::new("c", vec![TreeItem::new_leaf("d")])];
+    assert_eq!(state.open_lazy(&mut items, vec![0], load), Ok(false));
+    assert_eq!(state.open_lazy(&mut items, vec![1], load), Ok(true));
+    assert_eq!(state.open_lazy(&mut items, vec![1, 0], load), Ok(true));
+    state.select(vec![1, 0, 0]);
+
+    assert_eq!(state.unload_children(&mut items, &[1]), Ok(()));
+    assert!(items[1].is_lazy());
+    assert!(items[1].has_children());
+    assert!(state.get_all_opened().is_empty());
+    assert_eq!(state.selected(), [1]);
+    assert!(state.unload_children(&mut items, &[2]).is_err());
+}
+
+#[test]
+fn unload_forgets_descendants() {
+    let mut items = vec![TreeItem::new_leaf("a"), TreeItem::new_lazy("b")];
+    let mut state = TreeState::default();
+    let load = |_: TreeIdentifier, _: &&str| {
+        vec![
+            TreeItem::new("c", vec![TreeItem::new_leaf("d")]),
+            TreeItem::new_leaf("e"),
+        ]
+    };
+    assert_eq!(state.open_lazy(&mut items, vec![1], load), Ok(true));
+    state.select(vec![1, 0, 0]);
+    state.toggle_checked(&items, &[1, 0, 0]).unwrap();
+    state.bookmark_selected();
+    state.tag(vec![1, 0], "tag");
+    state.link(vec![0], vec![1, 0]);
+    state.open(vec![0]);
+
+    assert_eq!(state.unload_children(&mut items, &[1]), Ok(()));
+    assert_eq!(state.get_all_opened(), [vec![0]]);
+    assert!(state.checked().is_empty());
+    assert!(state.bookmarks().is_empty());
+    assert!(!state.has_tag(&[1, 0], "tag"));
+    assert!(state.links(&[0]).is_empty());
+
+    // Children loaded again are not checked, bookmarked or tagged
+    assert_eq!(state.open_lazy(&mut items, vec![1], load), Ok(true));
+    assert!(state.checked().is_empty());
+    assert!(state.bookmarks().is_empty());
+    assert!(!state.has_tag(&[1, 0], "tag"));
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use tui::style::Style;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::TreeState;
+
+/// Styles computed by [`TreeItem::style_fn`](crate::TreeItem::style_fn) for one version
+#[derive(Debug, Default, Clone)]
+pub(crate) struct StyleCache {
+    version: u64,
+    styles: HashMap<TreeIdentifierVec, Style>,
+}
+
+impl TreeState {
+    /// Style of the node for the version, computed only when not yet cached.
+    /// A different version invalidates all cached styles.
+    pub(crate) fn cached_style<F>(
+        &mut self,
+        identifier: &[usize],
+        version: u64,
+        compute: F,
+    ) -> Style
+    where
+        F: FnOnce() -> Style,
+    {
+        let cache = &mut self.style_cache;
+        if cache.version != version {
+            cache.version = version;
+            cache.styles.clear();
+        }
+        if let Some(style) = cache.styles.get(identifier).copied() {
+            self.record_style_cache(true);
+            return style;
+        }
+        let style = compute();
+        cache.styles.insert(identifier.to_vec(), style);
+        self.record_style_cache(false);
+        style
+    }
+}
+
+#[test]
+fn style_is_computed_once_per_version() {
+    use tui::style::Color;
+
+    let mut state = TreeState::default();
+    let red = Style::default().fg(Color::Red);
+    let blue = Style::default().fg(Color::Blue);
+    assert_eq!(state.cached_style(&[0], 1, || red), red);
+    assert_eq!(state.cached_style(&[0], 1, || unreachable!()), red);
+    assert_eq!(state.cached_style(&[1], 1, || blue), blue);
+    assert_eq!(state.cached_style(&[0], 2, || blue), blue);
+}
@@ -0,0 +1,136 @@
+use crate::flatten::{flatten_state, Flattened};
+use crate::identifier::TreeIdentifierVec;
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+impl TreeState {
+    /// Show runs of more than `min_run` consecutive leaf siblings as a single row like
+    /// `… 240 items`, keeping huge flat sections navigable.
+    ///
+    /// The summary row has the identifier of the first leaf of the run.
+    /// [`expand_group`](Self::expand_group) shows the leaves in place.
+    /// `None` disables the grouping, which is the default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let leaves = (0..240).map(|_| TreeItem::new_leaf("line")).collect::<Vec<_>>();
+    /// let items = vec![TreeItem::new("log", leaves), TreeItem::new_leaf("other")];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.set_leaf_grouping(Some(10));
+    ///
+    /// let area = Rect::new(0, 0, 16, 3);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items.clone()).render(area, &mut buffer, &mut state);
+    /// let expected = Buffer::with_lines(vec![
+    ///     "▼ log           ",
+    ///     "    … 240 items ",
+    ///     "  other         ",
+    /// ]);
+    /// assert_eq!(buffer, expected);
+    ///
+    /// assert!(state.expand_group(&items, vec![0, 0]));
+    /// state.select(vec![0, 0]);
+    /// state.key_down(&items);
+    /// assert_eq!(state.selected(), [0, 1]);
+    /// ```
+    pub fn set_leaf_grouping(&mut self, min_run: Option<usize>) {
+        self.leaf_grouping = min_run;
+    }
+
+    /// Show the leaves summarized by the group row with the identifier in place.
+    /// Returns `false` when the identifier is not a visible group row.
+    pub fn expand_group<A: TreeItemRender>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: TreeIdentifierVec,
+    ) -> bool {
+        let is_group = flatten_state(self, items)
+            .iter()
+            .any(|row| row.grouped.is_some() && row.identifier == identifier);
+        if is_group {
+            self.expanded_groups.insert(identifier);
+        }
+        is_group
+    }
+
+    /// Summarize every expanded run of leaves again
+    pub fn collapse_groups(&mut self) {
+        self.expanded_groups.clear();
+    }
+}
+
+/// Replace runs of leaf siblings longer than the [grouping](TreeState::set_leaf_grouping) of
+/// the state by their first row
+pub(crate) fn group_leaves<'a, A: TreeItemRender>(
+    rows: Vec<Flattened<'a, A>>,
+    state: &TreeState,
+) -> Vec<Flattened<'a, A>> {
+    let min_run = match state.leaf_grouping {
+        Some(min_run) => min_run,
+        None => return rows,
+    };
+    let mut result = Vec::with_capacity(rows.len());
+    let mut rows = rows.into_iter().peekable();
+    while let Some(mut first) = rows.next() {
+        if first.item.has_children() {
+            result.push(first);
+            continue;
+        }
+        // Leaves have no rows of descendants, so the following leaves are their siblings
+        let parent = first.identifier[..first.depth()].to_vec();
+        let mut run = Vec::new();
+        while let Some(next) = rows.next_if(|next| {
+            !next.item.has_children()
+                && next.identifier.len() == parent.len() + 1
+                && next.identifier.starts_with(&parent)
+        }) {
+            run.push(next);
+        }
+        if run.len() + 1 > min_run && !state.expanded_groups.contains(&first.identifier) {
+            if let Some(last) = run.last() {
+                first.last_siblings = last.last_siblings.clone();
+            }
+            first.grouped = Some(run.len() + 1);
+            result.push(first);
+        } else {
+            result.push(first);
+            result.extend(run);
+        }
+    }
+    result
+}
+
+#[test]
+fn only_runs_longer_than_the_minimum_are_grouped() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.set_leaf_grouping(Some(1));
+
+    let rows = flatten_state(&state, &items)
+        .iter()
+        .map(|row| (row.item.elem, row.grouped))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rows,
+        [
+            ("a", None),
+            ("b", None),
+            ("c", None),
+            ("d", None),
+            ("e", Some(2)),
+            ("g", None),
+            ("h", None)
+        ]
+    );
+
+    assert!(!state.expand_group(&items, vec![1, 0]));
+    assert!(state.expand_group(&items, vec![1, 1, 0]));
+    assert_eq!(flatten_state(&state, &items).len(), 8);
+}
@@ -1,22 +1,149 @@
 #![allow(clippy::must_use_candidate)]
 #![forbid(unsafe_code)]
 
-use std::collections::HashSet;
+// ratatui is a fork of tui-rs with the same modules, so it can take its place.
+// tui-rs wins when both are enabled as the textarea feature depends on its types.
+#[cfg(all(feature = "ratatui", not(feature = "tui")))]
+extern crate ratatui as tui;
+#[cfg(not(any(feature = "tui", feature = "ratatui")))]
+compile_error!("Either the `tui` or the `ratatui` feature is required");
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+use std::time::{Duration, Instant};
 
 use tui::buffer::Buffer;
 use tui::layout::{Corner, Rect};
-use tui::style::Style;
-use tui::text::Text;
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans, Text};
 use tui::widgets::{Block, StatefulWidget, Widget};
 use unicode_width::UnicodeWidthStr;
 
+mod access;
+mod accessible;
+mod animation;
+mod audit;
+mod bars;
+mod bookmarks;
+mod breadcrumb;
+mod checkbox;
+mod child_key;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod color_mode;
+#[cfg(feature = "diff")]
+mod diff;
+mod edge;
+mod empty;
+mod equality;
+mod error;
+mod fill;
+mod filter;
+#[cfg(feature = "textarea")]
+mod filterable;
 mod flatten;
+mod flatten_cache;
+#[cfg(feature = "fuzzy")]
+mod fuzzy;
+mod guides;
+mod handle;
+mod headless;
+mod hit;
+mod hscroll;
 mod identifier;
+mod indent;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod invariants;
+mod items;
+mod keymap;
+mod lazy;
+mod lazy_style;
+mod leaf_groups;
+mod link;
+mod loading;
+mod map;
+mod marked;
+mod metrics;
+mod minimap;
+mod multi_select;
+mod numbering;
+mod observer;
+mod open_all;
+mod opened;
+mod page;
+mod partial;
+mod placement;
+mod preview;
+mod pulse;
+mod reconcile;
+mod resize;
+mod root;
+mod scroll;
+mod scrollbar;
+mod search;
+mod search_job;
+mod selection;
+#[cfg(feature = "serde")]
+mod serde_state;
+mod session;
+mod shared;
+mod sort;
+mod source;
+mod stable_ids;
+mod stats;
+mod suffix;
+mod tags;
+mod toggle;
+mod tooltip;
+mod transient;
+pub mod tree_ops;
+mod width;
+mod window;
+mod wrap;
 
-pub use crate::flatten::{flatten, Flattened};
+pub use crate::accessible::AccessibleRow;
+pub use crate::audit::StateIssue;
+pub use crate::bars::BarScale;
+pub use crate::checkbox::{CheckState, CheckSymbols};
+pub use crate::child_key::{ChildKey, KeyedIdentifier, KeyedState};
+pub use crate::color_mode::ColorMode;
+pub use crate::empty::EmptyItems;
+pub use crate::equality::TreeStateField;
+pub use crate::error::TreeError;
+#[cfg(feature = "textarea")]
+pub use crate::filterable::{FilterableTree, FilterableTreeState};
+pub use crate::flatten::{flatten, Flattened, FlattenedView, ListItemConfig};
+#[cfg(feature = "fuzzy")]
+pub use crate::fuzzy::FuzzyMatch;
+pub use crate::handle::ItemHandle;
+pub use crate::hit::HitTarget;
 pub use crate::identifier::{
-    get_without_leaf as get_identifier_without_leaf, TreeIdentifier, TreeIdentifierVec,
+    get_without_leaf as get_identifier_without_leaf, required_ancestors, TreeIdentifier,
+    TreeIdentifierVec,
 };
+pub use crate::items::{IterDepthFirst, TreeItems};
+pub use crate::keymap::{Key, KeyChord, ParseKeyChordError, TreeAction, TreeKeymap};
+pub use crate::metrics::TreeMetrics;
+pub use crate::minimap::TreeMinimap;
+pub use crate::numbering::NumberingStyle;
+pub use crate::observer::TreeObserver;
+pub use crate::open_all::OpenAllResult;
+pub use crate::placement::Placement;
+pub use crate::preview::{TreePreview, TreeWithPreview};
+pub use crate::reconcile::ReconcileStrategy;
+pub use crate::root::TreeRoot;
+pub use crate::scroll::TreeScroll;
+pub use crate::scrollbar::{ScrollMetrics, ScrollbarStyle};
+pub use crate::search::SearchScope;
+pub use crate::search_job::SearchJob;
+pub use crate::session::{LabelPath, TreeSession};
+pub use crate::shared::SharedTreeState;
+pub use crate::sort::SortKey;
+pub use crate::source::{SourceTree, TreeItemSource};
+pub use crate::stats::{tree_stats, TreeStats};
+pub use crate::suffix::ShrinkOrder;
+pub use crate::toggle::Toggled;
 
 /// Keeps the state of what is currently selected and what was opened in a [`Tree`]
 ///
@@ -29,8 +156,73 @@ pub use crate::identifier::{
 #[derive(Debug, Default, Clone)]
 pub struct TreeState {
     offset: usize,
-    opened: HashSet<TreeIdentifierVec>,
+    opened: opened::OpenedSet,
     selected: TreeIdentifierVec,
+    /// Layout of the rows of the last render, used for hit testing
+    rendered: Vec<hit::RenderedRow>,
+    /// Nodes marked as changed and when they changed
+    changed: HashMap<TreeIdentifierVec, Instant>,
+    /// Move the selection line by line through multi-line items
+    line_navigation: bool,
+    /// Line within the selected item when `line_navigation` is enabled
+    selected_line: usize,
+    /// Order of the children of a parent when rendered
+    child_orders: HashMap<TreeIdentifierVec, SortKey>,
+    /// Recently opened nodes and the amount of arrow animation frames they have shown
+    opening: HashMap<TreeIdentifierVec, usize>,
+    /// Results of [`TreeItem::style_fn`] of the rendered rows
+    style_cache: lazy_style::StyleCache,
+    /// Nodes with each tag
+    tags: HashMap<String, BTreeSet<TreeIdentifierVec>>,
+    /// Lowercase query only matching nodes and their ancestors are shown for
+    filter: Option<String>,
+    /// Opened nodes which were opened automatically to reveal another node
+    transient: BTreeSet<TreeIdentifierVec>,
+    /// Nodes bookmarked by the user
+    bookmarks: BTreeSet<TreeIdentifierVec>,
+    /// Usage counters, only collected when enabled
+    metrics: Option<TreeMetrics>,
+    /// Nodes which are opened and closed together
+    links: BTreeMap<TreeIdentifierVec, BTreeSet<TreeIdentifierVec>>,
+    /// Checked nodes, see [`TreeState::toggle_checked`]
+    checked: BTreeSet<TreeIdentifierVec>,
+    /// Nodes marked for a batch operation
+    selected_multi: HashSet<TreeIdentifierVec>,
+    /// The offset was scrolled without moving the selection which may be scrolled off
+    viewport_detached: bool,
+    /// Where the selection lands within the viewport on the next render
+    placement: Option<Placement>,
+    /// Columns the labels are scrolled to the left
+    horizontal_offset: usize,
+    /// Width and height of the area of the last render
+    last_area_size: Option<(u16, u16)>,
+    /// Position of the rendered rows within all visible rows of the last render
+    scroll_metrics: ScrollMetrics,
+    /// Position of the mouse pointer for tooltips
+    hover: Option<(u16, u16)>,
+    /// Keys of the opened and selected nodes when they were last synchronized
+    stable_ids: Option<stable_ids::StableIds>,
+    /// Visible rows of the last flatten, only kept when enabled
+    flatten_cache: Option<flatten_cache::FlattenCache>,
+    /// Nodes shown by the filter
+    filter_cache: filter::FilterCache,
+    /// Runs of more leaf siblings than this are summarized by a single row
+    leaf_grouping: Option<usize>,
+    /// First leaves of the summarized runs which are shown in place
+    expanded_groups: BTreeSet<TreeIdentifierVec>,
+    /// Nodes waiting for their children, see [`TreeState::open_async`]
+    loading: BTreeSet<TreeIdentifierVec>,
+    /// Counts the structural changes of the items
+    generation: u64,
+    /// Current identifiers of the live [`ItemHandle`s](ItemHandle) by their id
+    handles: HashMap<u64, TreeIdentifierVec>,
+    /// Id of the next [`ItemHandle`]
+    next_handle: u64,
+    /// Notified about interactions, see [`TreeState::set_observer`]
+    observer: Option<observer::Observer>,
+    /// Texts of changed nodes before their change and when they changed
+    #[cfg(feature = "diff")]
+    previous_texts: HashMap<TreeIdentifierVec, (String, Instant)>,
 }
 
 impl TreeState {
@@ -39,7 +231,7 @@ impl TreeState {
     }
 
     pub fn get_all_opened(&self) -> Vec<TreeIdentifierVec> {
-        self.opened.iter().cloned().collect()
+        self.opened.iter().collect()
     }
 
     pub fn selected(&self) -> Vec<usize> {
@@ -50,7 +242,14 @@ impl TreeState {
     where
         I: Into<Vec<usize>>,
     {
-        self.selected = identifier.into();
+        let identifier = identifier.into();
+        if identifier != self.selected && !identifier.is_empty() {
+            self.notify(|observer| observer.on_select(&identifier));
+        }
+        self.selected = identifier;
+        self.selected_line = 0;
+        self.viewport_detached = false;
+        self.placement = None;
 
         // TODO: ListState does this. Is this relevant?
         if self.selected.is_empty() {
@@ -58,26 +257,182 @@ impl TreeState {
         }
     }
 
+    /// Let [`key_up`](Self::key_up) and [`key_down`](Self::key_down) move line by line through
+    /// items spanning multiple lines before moving on to the next item.
+    /// The highlight symbol is rendered on the selected line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("first\nsecond"), TreeItem::new_leaf("next")];
+    /// let mut state = TreeState::default();
+    /// state.set_line_navigation(true);
+    /// state.select_first();
+    ///
+    /// state.key_down(&items);
+    /// assert_eq!(state.selected(), [0]);
+    /// assert_eq!(state.selected_line(), 1);
+    ///
+    /// state.key_down(&items);
+    /// assert_eq!(state.selected(), [1]);
+    /// assert_eq!(state.selected_line(), 0);
+    /// ```
+    pub fn set_line_navigation(&mut self, enabled: bool) {
+        self.line_navigation = enabled;
+        self.selected_line = 0;
+    }
+
+    /// Line within the selected item. Always `0` without
+    /// [line navigation](Self::set_line_navigation).
+    pub const fn selected_line(&self) -> usize {
+        self.selected_line
+    }
+
+    /// Select the node after validating it exists in the items.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node.
+    /// The selection is not changed in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeError, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a")];
+    /// let mut state = TreeState::default();
+    ///
+    /// assert!(state.try_select(&items, vec![0]).is_ok());
+    /// assert!(matches!(
+    ///     state.try_select(&items, vec![1]),
+    ///     Err(TreeError::OutOfBounds { .. })
+    /// ));
+    /// assert_eq!(state.selected(), [0]);
+    /// ```
+    pub fn try_select<A, I>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: I,
+    ) -> Result<(), TreeError>
+    where
+        I: Into<Vec<usize>>,
+    {
+        let identifier = identifier.into();
+        error::resolve(items, &identifier)?;
+        self.select(identifier);
+        Ok(())
+    }
+
+    /// Select the node or the nearest existing one when it does not exist.
+    ///
+    /// An index beyond the children of its parent is clamped to the last child and the indices
+    /// below it are dropped.
+    /// Use [`try_select`](Self::try_select) to reject identifiers which do not exist instead.
+    ///
+    /// Returns the selected identifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    ///
+    /// assert_eq!(state.select_clamped(&items, vec![0, 5, 1]), [0, 0]);
+    /// assert_eq!(state.select_clamped(&items, vec![3]), [0]);
+    /// ```
+    pub fn select_clamped<A, I>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: I,
+    ) -> TreeIdentifierVec
+    where
+        I: Into<Vec<usize>>,
+    {
+        let mut clamped = Vec::new();
+        let mut items = items;
+        for index in identifier.into() {
+            if items.is_empty() {
+                break;
+            }
+            let last = items.len() - 1;
+            clamped.push(index.min(last));
+            if index > last {
+                break;
+            }
+            items = &items[index].children;
+        }
+        self.select(clamped.clone());
+        clamped
+    }
+
     /// Open a tree node.
     /// Returns `true` if the node was closed and has been opened.
     /// Returns `false` if the node was already open.
+    /// Nodes [linked](Self::link) to it are opened as well.
     pub fn open(&mut self, identifier: TreeIdentifierVec) -> bool {
-        if identifier.is_empty() {
+        for linked in self.linked_group(&identifier) {
+            self.open_single(linked);
+        }
+        self.open_single(identifier)
+    }
+
+    pub(crate) fn open_single(&mut self, identifier: TreeIdentifierVec) -> bool {
+        // Opening explicitly keeps the node open when collapsing the transient nodes
+        self.transient.remove(&identifier);
+        if identifier.is_empty() || self.opened.contains(&identifier) {
             false
         } else {
+            self.opening.insert(identifier.clone(), 0);
+            self.notify(|observer| observer.on_open(&identifier));
             self.opened.insert(identifier)
         }
     }
 
+    /// Open a tree node after validating it exists in the items and has children.
+    /// Returns `true` if the node was closed and has been opened.
+    /// Returns `false` if the node was already open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node or the node has
+    /// no children.
+    pub fn try_open<A>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: TreeIdentifierVec,
+    ) -> Result<bool, TreeError> {
+        if !error::resolve(items, &identifier)?.has_children() {
+            return Err(TreeError::NoChildren(identifier));
+        }
+        Ok(self.open(identifier))
+    }
+
     /// Close a tree node.
     /// Returns `true` if the node was open and has been closed.
     /// Returns `false` if the node was already closed.
+    /// Nodes [linked](Self::link) to it are closed as well.
     pub fn close(&mut self, identifier: TreeIdentifier) -> bool {
-        self.opened.remove(identifier)
+        for linked in self.linked_group(identifier) {
+            self.close_single(&linked);
+        }
+        self.close_single(identifier)
+    }
+
+    pub(crate) fn close_single(&mut self, identifier: TreeIdentifier) -> bool {
+        self.opening.remove(identifier);
+        self.transient.remove(identifier);
+        let closed = self.opened.remove(identifier);
+        if closed {
+            self.notify(|observer| observer.on_close(identifier));
+        }
+        closed
     }
 
     /// Toggles a tree node.
     /// If the node is in opened then it calls `close()`. Otherwise it calls `open()`.
+    /// Use [`toggle_with`](Self::toggle_with) to not open leaves.
     pub fn toggle(&mut self, identifier: TreeIdentifierVec) {
         if self.opened.contains(&identifier) {
             self.close(&identifier);
@@ -93,6 +448,11 @@ impl TreeState {
     }
 
     pub fn close_all(&mut self) {
+        self.opening.clear();
+        self.transient.clear();
+        for identifier in self.opened.iter() {
+            self.notify(|observer| observer.on_close(&identifier));
+        }
         self.opened.clear();
     }
 
@@ -102,8 +462,8 @@ impl TreeState {
     }
 
     /// Select the last node.
-    pub fn select_last<A>(&mut self, items: &[TreeItem<A>]) {
-        let visible = flatten(&self.get_all_opened(), items);
+    pub fn select_last<A: TreeItemRender>(&mut self, items: &[TreeItem<A>]) {
+        let visible = flatten::flatten_state(self, items);
         let new_identifier = visible
             .last()
             .map(|o| o.identifier.clone())
@@ -113,8 +473,15 @@ impl TreeState {
 
     /// Handles the up arrow key.
     /// Moves up in the current depth or to its parent.
-    pub fn key_up<A>(&mut self, items: &[TreeItem<A>]) {
-        let visible = flatten(&self.get_all_opened(), items);
+    pub fn key_up<A: TreeItemRender>(&mut self, items: &[TreeItem<A>]) {
+        if self.line_navigation && self.selected_line > 0 {
+            self.selected_line -= 1;
+            return;
+        }
+        let visible = flatten::flatten_state(self, items);
+        if visible.is_empty() {
+            return;
+        }
         let current_identifier = self.selected();
         let current_index = visible
             .iter()
@@ -123,20 +490,37 @@ impl TreeState {
             current_index.saturating_sub(1).min(visible.len() - 1)
         });
         let new_identifier = visible[new_index].identifier.clone();
+        let moved = current_index != Some(new_index);
         self.select(new_identifier);
+        if self.line_navigation && moved {
+            self.selected_line = visible[new_index].item.height().saturating_sub(1);
+        }
     }
 
     /// Handles the down arrow key.
     /// Moves down in the current depth or into a child node.
-    pub fn key_down<A>(&mut self, items: &[TreeItem<A>]) {
-        let visible = flatten(&self.get_all_opened(), items);
+    pub fn key_down<A: TreeItemRender>(&mut self, items: &[TreeItem<A>]) {
+        let visible = flatten::flatten_state(self, items);
+        if visible.is_empty() {
+            return;
+        }
         let current_identifier = self.selected();
         let current_index = visible
             .iter()
             .position(|o| o.identifier == current_identifier);
+        if let Some(current_index) = current_index {
+            if self.line_navigation && self.selected_line + 1 < visible[current_index].item.height()
+            {
+                self.selected_line += 1;
+                return;
+            }
+        }
         let new_index = current_index.map_or(0, |current_index| {
             current_index.saturating_add(1).min(visible.len() - 1)
         });
+        if current_index == Some(new_index) {
+            return;
+        }
         let new_identifier = visible[new_index].identifier.clone();
         self.select(new_identifier);
     }
@@ -156,8 +540,90 @@ impl TreeState {
     pub fn key_right(&mut self) {
         self.open(self.selected());
     }
+
+    /// Closes the currently selected node and selects its parent.
+    /// A selected root node is closed and stays selected.
+    pub fn collapse_current_and_select_parent(&mut self) {
+        let selected = self.selected();
+        self.close(&selected);
+        let (head, _) = get_identifier_without_leaf(&selected);
+        if !head.is_empty() {
+            self.select(head);
+        }
+    }
+
+    /// Opens the currently selected node and selects its first child.
+    /// Nothing happens when the selected node has no children.
+    /// A [lazy](TreeItem::new_lazy) node whose children are not loaded yet is only opened.
+    ///
+    /// This is the inverse of [`TreeState::collapse_current_and_select_parent`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.select_first();
+    ///
+    /// state.expand_and_enter(&items);
+    /// assert_eq!(state.selected(), [0, 0]);
+    ///
+    /// state.collapse_current_and_select_parent();
+    /// assert_eq!(state.selected(), [0]);
+    /// ```
+    pub fn expand_and_enter<A>(&mut self, items: &[TreeItem<A>]) {
+        let selected = self.selected();
+        let item = match error::resolve(items, &selected) {
+            Ok(item) if item.has_children() => item,
+            _ => return,
+        };
+        let has_loaded_children = !item.children.is_empty();
+        self.open(selected.clone());
+        // Children of a lazy node have to be loaded before one can be selected
+        if has_loaded_children {
+            let mut first_child = selected;
+            first_child.push(0);
+            self.select(first_child);
+        }
+    }
+
+    /// Repeatedly [`expand_and_enter`](Self::expand_and_enter) until a leaf is selected, like
+    /// expanding the path to the first file in a file explorer.
+    /// Stops after [`DRILL_DOWN_MAX_DEPTH`] levels.
+    ///
+    /// Returns the amount of levels descended.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "src",
+    ///     vec![TreeItem::new("widgets", vec![TreeItem::new_leaf("tree.rs")])],
+    /// )];
+    /// let mut state = TreeState::default();
+    /// state.select_first();
+    ///
+    /// assert_eq!(state.drill_down(&items), 2);
+    /// assert_eq!(state.selected(), [0, 0, 0]);
+    /// assert_eq!(state.drill_down(&items), 0);
+    /// ```
+    pub fn drill_down<A>(&mut self, items: &[TreeItem<A>]) -> usize {
+        for depth in 0..DRILL_DOWN_MAX_DEPTH {
+            let before = self.selected.len();
+            self.expand_and_enter(items);
+            if self.selected.len() == before {
+                return depth;
+            }
+        }
+        DRILL_DOWN_MAX_DEPTH
+    }
 }
 
+/// Maximal amount of levels [`TreeState::drill_down`] descends at once
+pub const DRILL_DOWN_MAX_DEPTH: usize = 64;
+
 /// One item inside a [`Tree`]
 ///
 /// Can zero or more `children`.
@@ -173,24 +639,104 @@ impl TreeState {
 pub struct TreeItem<A> {
     elem: A, // TODO: text as fn of A?
     style: Style,
-    children: Vec<TreeItem<A>>,
+    children: Children<A>,
+    /// Arbitrary per node flags like "hidden", "pinned" or "modified"
+    flags: Vec<&'static str>,
+    /// Style derived from the element when rendered
+    style_fn: Option<fn(&A) -> Style>,
+    /// The children are not loaded yet, see [`TreeItem::new_lazy`]
+    lazy: bool,
+    /// Key identifying the item regardless of its position, see [`TreeItem::with_id`]
+    id: Option<u64>,
 }
 
 pub trait TreeItemRender {
-    fn as_text(&self) -> Text;
+    fn as_text(&self) -> Text<'_>;
+
+    /// Text rendered by the [`Tree`] depending on how the item is shown, like a different label
+    /// while the node is collapsed.
+    /// Defaults to [`as_text`](Self::as_text).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::text::Text;
+    /// # use tui_tree_widget::{RenderContext, TreeItemRender};
+    /// struct Folder {
+    ///     name: String,
+    ///     summary: String,
+    /// }
+    ///
+    /// impl TreeItemRender for Folder {
+    ///     fn as_text(&self) -> Text<'_> {
+    ///         Text::raw(self.name.as_str())
+    ///     }
+    ///
+    ///     fn as_text_with(&self, context: RenderContext) -> Text<'_> {
+    ///         if context.has_children && !context.is_opened {
+    ///             Text::raw(format!("{} ({})", self.name, self.summary))
+    ///         } else {
+    ///             self.as_text()
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn as_text_with(&self, _context: RenderContext) -> Text<'_> {
+        self.as_text()
+    }
+
+    /// Metadata rendered right aligned in the first line of the item, like a file size
+    fn suffix(&self) -> Option<Spans<'_>> {
+        None
+    }
+
+    /// Compare by a key used with [`SortKey::Named`]. Unknown keys should be [`Ordering::Equal`]
+    fn compare(&self, _other: &Self, _key: &str) -> Ordering {
+        Ordering::Equal
+    }
+
+    /// Numeric value visualized by [`Tree::value_bars`], like a disk usage
+    fn as_value(&self) -> Option<f64> {
+        None
+    }
+
+    /// Natural width of the label used by [`Tree::required_width`].
+    /// Defaults to the width of [`as_text`](Self::as_text).
+    fn preferred_width(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl TreeItemRender for &str {
-    fn as_text(&self) -> Text {
+    fn as_text(&self) -> Text<'_> {
         (*self).into()
     }
 }
 
+/// Plain content of a [`Text`] without any styling, lines separated by `\n`
+pub(crate) fn text_to_string(text: &Text) -> String {
+    text.lines
+        .iter()
+        .map(|spans| {
+            spans
+                .0
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 impl<A: TreeItemRender> TreeItem<A> {
     pub fn new_leaf(elem: A) -> Self {
         Self {
             style: Style::default(),
-            children: Vec::new(),
+            children: Children(Vec::new()),
+            flags: Vec::new(),
+            style_fn: None,
+            lazy: false,
+            id: None,
             elem,
         }
     }
@@ -201,7 +747,11 @@ impl<A: TreeItemRender> TreeItem<A> {
     {
         Self {
             style: Style::default(),
-            children: children.into(),
+            children: Children(children.into()),
+            flags: Vec::new(),
+            style_fn: None,
+            lazy: false,
+            id: None,
             elem,
         }
     }
@@ -232,11 +782,144 @@ impl<A: TreeItemRender> TreeItem<A> {
         self
     }
 
+    /// Derive the style from the element when the item is rendered.
+    ///
+    /// The function is only evaluated for rendered rows and its result is cached within the
+    /// [`TreeState`] until the [`Tree::style_version`] changes.
+    /// The resulting style is patched onto the [`style`](Self::style) of the item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::style::{Color, Style};
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let items = vec![TreeItem::new_leaf("old").style_fn(|elem| {
+    ///     if *elem == "old" {
+    ///         Style::default().fg(Color::DarkGray)
+    ///     } else {
+    ///         Style::default()
+    ///     }
+    /// })];
+    /// // Increase the version whenever the data the styles are derived from changes
+    /// let tree = Tree::new(items).style_version(1);
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn style_fn(mut self, style_fn: fn(&A) -> Style) -> Self {
+        self.style_fn = Some(style_fn);
+        self
+    }
+
     pub fn add_child(&mut self, child: TreeItem<A>) {
         self.children.push(child);
     }
+
+    /// Set a flag on this item.
+    /// Flags allow decorators, filters and sorters to key off per node information without
+    /// wrapping the element type.
+    /// Returns `true` if the flag was not set before.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeItem;
+    /// let mut item = TreeItem::new_leaf("leaf");
+    /// item.set_flag("pinned");
+    /// assert!(item.has_flag("pinned"));
+    /// assert!(!item.has_flag("hidden"));
+    /// ```
+    pub fn set_flag(&mut self, flag: &'static str) -> bool {
+        if self.has_flag(flag) {
+            false
+        } else {
+            self.flags.push(flag);
+            true
+        }
+    }
+
+    /// Remove a flag from this item.
+    /// Returns `true` if the flag was set before.
+    pub fn unset_flag(&mut self, flag: &str) -> bool {
+        let before = self.flags.len();
+        self.flags.retain(|o| *o != flag);
+        self.flags.len() != before
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    pub fn flags(&self) -> &[&'static str] {
+        &self.flags
+    }
 }
 
+/// Children of a [`TreeItem`] which are dropped without recursion.
+///
+/// Dropping is implemented here instead of on the item so the element can be moved out of an
+/// item, like by [`TreeItem::map`].
+#[derive(Clone)]
+pub(crate) struct Children<A>(Vec<TreeItem<A>>);
+
+impl<A> std::ops::Deref for Children<A> {
+    type Target = Vec<TreeItem<A>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<A> std::ops::DerefMut for Children<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<A: std::fmt::Debug> std::fmt::Debug for Children<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<A> Drop for Children<A> {
+    /// Dropping the children recursively would overflow the stack of very deep trees
+    fn drop(&mut self) {
+        let mut descendants = std::mem::take(&mut self.0);
+        while let Some(mut item) = descendants.pop() {
+            descendants.append(&mut item.children);
+        }
+    }
+}
+
+/// Information about a row of a [`Tree`] which is currently rendered
+///
+/// Handed to the hooks of [`Tree::row_background`] and [`Tree::row_overlay`].
+#[derive(Debug, Clone, Copy)]
+pub struct RowContext<'a> {
+    /// Identifier of the [`TreeItem`] rendered in this row
+    pub identifier: TreeIdentifier<'a>,
+    /// Index of the row in the flattened list of visible items
+    pub index: usize,
+    pub depth: usize,
+    pub is_selected: bool,
+    pub is_opened: bool,
+    pub has_children: bool,
+}
+
+/// How an item is shown by a [`Tree`], handed to [`TreeItemRender::as_text_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderContext {
+    pub depth: usize,
+    pub is_selected: bool,
+    pub is_opened: bool,
+    pub has_children: bool,
+    /// Columns left for the text right of the indentation and symbols
+    pub width: usize,
+}
+
+/// Hook called while rendering a row of a [`Tree`] with the area of the row
+pub type RowHook = fn(RowContext, Rect, &mut Buffer);
+
 /// A `Tree` which can be rendered
 ///
 /// # Example
@@ -275,6 +958,92 @@ pub struct Tree<'a, A> {
     highlight_style: Style,
     /// Symbol in front of the selected item (Shift all items to the right)
     highlight_symbol: Option<&'a str>,
+    /// Symbols in front of closed nodes, opened nodes and leaves
+    node_closed_symbol: &'a str,
+    node_open_symbol: &'a str,
+    node_leaf_symbol: &'a str,
+    /// Columns of indentation per depth
+    indent_width: usize,
+    /// Draw the indentation as lines connecting parents and children
+    guide_lines: bool,
+    /// Show where labels are cut off
+    ellipsis: bool,
+    /// Called for every row before its content is rendered
+    row_background: Option<RowHook>,
+    /// Called for every row after its content is rendered
+    row_overlay: Option<RowHook>,
+    /// Style of hidden descendants which is applied to their collapsed ancestor
+    bubble_style: Option<fn(&A) -> Option<Style>>,
+    /// Line rendered between the subtrees of root items
+    root_separator: Option<Span<'a>>,
+    /// Text and style of the row of children which are still loading
+    loading_placeholder: (&'a str, Style),
+    /// What to give up first when the label and suffix do not fit
+    shrink_priority: ShrinkOrder,
+    /// Style of rows marked with [`TreeState::mark_changed`] and how long they keep it
+    changed: Option<(Style, pulse::Pulse)>,
+    /// Time used to compute the highlight of changed rows. Defaults to [`Instant::now`]
+    now: Option<Instant>,
+    /// Amount of children previewed below collapsed nodes
+    peek_children: usize,
+    /// Style of the previewed children
+    peek_style: Style,
+    /// Mirror the layout of each row horizontally
+    right_to_left: bool,
+    /// Symbols shown instead of the opened symbol right after opening a node
+    arrow_animation: &'a [&'a str],
+    /// Version of the data [`TreeItem::style_fn`] depends on
+    style_version: u64,
+    /// Tags which get a marker column and the style of their marker
+    tag_styles: &'a [(&'a str, Style)],
+    /// How items without any text lines are rendered
+    empty_items: EmptyItems,
+    /// Maximal width of the indentation
+    max_indent_cols: Option<u16>,
+    /// Scroll position when rendered without a [`TreeState`]
+    scroll: Option<TreeScroll>,
+    /// Rows rendered at most regardless of the height of the area
+    max_rendered_rows: Option<usize>,
+    /// Title and style of the header row of each root, see [`TreeRoot`]
+    root_headers: Vec<(Spans<'a>, Style)>,
+    /// Whether the header row is highlighted and hit together with its root
+    selectable_headers: bool,
+    /// Style of the guide from the root down to the selected node
+    selected_path_style: Option<Style>,
+    /// Width and style of the bars visualizing [`TreeItemRender::as_value`]
+    value_bars: Option<(u16, Style)>,
+    bar_scale: BarScale,
+    /// Style and duration of the highlight of inserted characters of changed nodes
+    #[cfg(feature = "diff")]
+    inline_diff: Option<(Style, Duration)>,
+    /// Nodes marked by the app like bookmarks and their style
+    marked: Option<(HashSet<TreeIdentifierVec>, Style)>,
+    /// Gutter symbol in front of the marked nodes
+    marked_symbol: Option<&'a str>,
+    /// Gutter symbol and its style in front of the bookmarked nodes
+    bookmark_symbol: Option<(&'a str, Style)>,
+    /// Prefix of continuation lines when labels are wrapped
+    wrap_continuation: Option<&'a str>,
+    /// Replace the first line with the ancestors of the first row when they are scrolled off
+    ancestor_breadcrumb: bool,
+    /// Style and symbol of the lines below the last row
+    fill_remaining: Option<(Style, &'a str)>,
+    /// Style patched onto the parts of labels matching the filter
+    filter_match_style: Option<Style>,
+    /// Style of the rows within the multi selection
+    multi_highlight_style: Option<Style>,
+    /// Text shown next to the hovered row
+    tooltip: Option<fn(&A) -> Option<Text<'static>>>,
+    /// Outline numbers in front of the labels
+    numbering: Option<NumberingStyle>,
+    /// Symbols and styles of the scrollbar
+    scrollbar: Option<ScrollbarStyle<'a>>,
+    /// Symbols of the checkboxes in front of the labels
+    check_symbols: Option<CheckSymbols<'a>>,
+    /// Colors the terminal is able to show
+    color_mode: ColorMode,
+    /// Colors used instead of colors the color mode can not show
+    color_fallbacks: &'a [(Color, Color)],
 }
 
 impl<'a, A> Tree<'a, A> {
@@ -289,9 +1058,65 @@ impl<'a, A> Tree<'a, A> {
             start_corner: Corner::TopLeft,
             highlight_style: Style::default(),
             highlight_symbol: None,
+            node_closed_symbol: NODE_CLOSED_SYMBOL,
+            node_open_symbol: NODE_OPEN_SYMBOL,
+            node_leaf_symbol: NODE_LEAF_SYMBOL,
+            indent_width: INDENT_WIDTH,
+            guide_lines: false,
+            ellipsis: false,
+            row_background: None,
+            row_overlay: None,
+            bubble_style: None,
+            root_separator: None,
+            loading_placeholder: (loading::LOADING_PLACEHOLDER, Style::default()),
+            shrink_priority: ShrinkOrder::default(),
+            changed: None,
+            now: None,
+            peek_children: 0,
+            peek_style: Style::default().add_modifier(Modifier::DIM),
+            right_to_left: false,
+            arrow_animation: &[],
+            style_version: 0,
+            tag_styles: &[],
+            empty_items: EmptyItems::Blank,
+            max_indent_cols: None,
+            scroll: None,
+            max_rendered_rows: None,
+            root_headers: Vec::new(),
+            selectable_headers: false,
+            selected_path_style: None,
+            value_bars: None,
+            bar_scale: BarScale::Siblings,
+            #[cfg(feature = "diff")]
+            inline_diff: None,
+            marked: None,
+            marked_symbol: None,
+            bookmark_symbol: None,
+            wrap_continuation: None,
+            ancestor_breadcrumb: false,
+            fill_remaining: None,
+            filter_match_style: None,
+            multi_highlight_style: None,
+            tooltip: None,
+            numbering: None,
+            scrollbar: None,
+            check_symbols: None,
+            color_mode: ColorMode::TrueColor,
+            color_fallbacks: &[],
         }
     }
 
+    /// Create a tree of roots which each get a header row above their subtree.
+    pub fn from_roots(roots: Vec<TreeRoot<'a, A>>) -> Self {
+        let (items, root_headers) = roots
+            .into_iter()
+            .map(|root| (root.item, (root.title, root.style)))
+            .unzip::<_, _, Vec<_>, _>();
+        let mut tree = Self::new(items);
+        tree.root_headers = root_headers;
+        tree
+    }
+
     #[allow(clippy::missing_const_for_fn)]
     #[must_use]
     pub fn block(mut self, block: Block<'a>) -> Self {
@@ -311,6 +1136,54 @@ impl<'a, A> Tree<'a, A> {
         self
     }
 
+    /// Symbol in front of nodes with hidden children. Defaults to `▶`.
+    ///
+    /// The node symbols should have the same width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let area = Rect::new(0, 0, 8, 1);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])])
+    ///     .node_closed_symbol("[+]")
+    ///     .node_open_symbol("[-]")
+    ///     .node_leaf_symbol("   ")
+    ///     .indent_width(4)
+    ///     .render(area, &mut buffer);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["[+] a   "]));
+    /// ```
+    #[must_use]
+    pub const fn node_closed_symbol(mut self, symbol: &'a str) -> Self {
+        self.node_closed_symbol = symbol;
+        self
+    }
+
+    /// Symbol in front of nodes with shown children. Defaults to `▼`.
+    #[must_use]
+    pub const fn node_open_symbol(mut self, symbol: &'a str) -> Self {
+        self.node_open_symbol = symbol;
+        self
+    }
+
+    /// Symbol in front of items without children. Defaults to a space.
+    #[must_use]
+    pub const fn node_leaf_symbol(mut self, symbol: &'a str) -> Self {
+        self.node_leaf_symbol = symbol;
+        self
+    }
+
+    /// Columns of indentation per depth. Defaults to 2.
+    #[must_use]
+    pub const fn indent_width(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+
     #[must_use]
     pub const fn highlight_style(mut self, style: Style) -> Self {
         self.highlight_style = style;
@@ -322,140 +1195,1484 @@ impl<'a, A> Tree<'a, A> {
         self.start_corner = corner;
         self
     }
+
+    /// Highlight rows marked with [`TreeState::mark_changed`] with the style for the duration.
+    #[must_use]
+    pub const fn changed_style(mut self, style: Style, duration: Duration) -> Self {
+        self.changed = Some((
+            style,
+            pulse::Pulse {
+                duration,
+                blink_interval: None,
+            },
+        ));
+        self
+    }
+
+    /// Let the highlight of [changed rows](Self::changed_style) blink with the interval.
+    /// Has no effect without [`changed_style`](Self::changed_style).
+    #[must_use]
+    pub fn changed_blink_interval(mut self, interval: Duration) -> Self {
+        if let Some((_, pulse)) = &mut self.changed {
+            pulse.blink_interval = Some(interval);
+        }
+        self
+    }
+
+    /// Time of this frame used to highlight [changed rows](Self::changed_style).
+    /// Defaults to [`Instant::now`] when rendering.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use tui::style::{Color, Style};
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let now = Instant::now();
+    /// let mut state = TreeState::default();
+    /// state.mark_changed(vec![0], now);
+    ///
+    /// let tree = Tree::new(vec![TreeItem::new_leaf("updated")])
+    ///     .changed_style(Style::default().bg(Color::Yellow), Duration::from_secs(3))
+    ///     .changed_blink_interval(Duration::from_millis(500))
+    ///     .now(now);
+    /// ```
+    #[must_use]
+    pub const fn now(mut self, now: Instant) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Preview up to `amount` children below every collapsed node.
+    /// The preview rows are rendered with the [`peek_style`](Self::peek_style) and can not be
+    /// selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::style::{Color, Style};
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let tree = Tree::new(vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])])
+    ///     .peek_children(3)
+    ///     .peek_style(Style::default().fg(Color::DarkGray));
+    /// ```
+    #[must_use]
+    pub const fn peek_children(mut self, amount: usize) -> Self {
+        self.peek_children = amount;
+        self
+    }
+
+    /// Style of the rows shown by [`peek_children`](Self::peek_children).
+    /// Dimmed by default.
+    #[must_use]
+    pub const fn peek_style(mut self, style: Style) -> Self {
+        self.peek_style = style;
+        self
+    }
+
+    /// Mirror the layout of each row for right to left locales.
+    ///
+    /// The highlight symbol and the indentation start at the right edge, the arrows point to the
+    /// left and the [suffix](TreeItemRender::suffix) is placed at the left edge.
+    /// Labels keep their text but are aligned to the right and truncated at their left edge.
+    /// [`TreeState::hit_test`] mirrors positions accordingly.
+    #[must_use]
+    pub const fn right_to_left(mut self, right_to_left: bool) -> Self {
+        self.right_to_left = right_to_left;
+        self
+    }
+
+    /// Cycle through the frames instead of the opened symbol right after opening a node.
+    ///
+    /// Every render of the tree shows the next frame, so the animation advances with the ticks
+    /// of the event loop.
+    /// Each frame should be a single cell wide.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let tree = Tree::new(vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])])
+    ///     .arrow_animation(&["\u{25b6}", "\u{25e2}", "\u{25bc}"]);
+    /// ```
+    #[must_use]
+    pub const fn arrow_animation(mut self, frames: &'a [&'a str]) -> Self {
+        self.arrow_animation = frames;
+        self
+    }
+
+    /// Version of the data the [`TreeItem::style_fn`] styles are derived from.
+    /// Styles cached within the [`TreeState`] are evaluated again when the version changes.
+    #[must_use]
+    pub const fn style_version(mut self, version: u64) -> Self {
+        self.style_version = version;
+        self
+    }
+
+    /// Render a marker column for each of the [tags](TreeState::tag) in front of the indentation.
+    /// The marker of a tag is shown in its style when the node has the tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::style::{Color, Style};
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let styles = [
+    ///     ("staged", Style::default().fg(Color::Green)),
+    ///     ("conflicted", Style::default().fg(Color::Red)),
+    /// ];
+    /// let tree = Tree::new(vec![TreeItem::new_leaf("a")]).tag_styles(&styles);
+    /// ```
+    #[must_use]
+    pub const fn tag_styles(mut self, styles: &'a [(&'a str, Style)]) -> Self {
+        self.tag_styles = styles;
+        self
+    }
+
+    /// Decide how items without any text lines are rendered.
+    /// Defaults to [`EmptyItems::Blank`].
+    #[must_use]
+    pub const fn empty_items(mut self, policy: EmptyItems) -> Self {
+        self.empty_items = policy;
+        self
+    }
+
+    /// Limit the indentation to the amount of columns so labels stay readable in narrow panes.
+    ///
+    /// Deeper indentation is replaced by a depth counter like `⋯6»` which is right aligned within
+    /// the columns. With less than the width of the counter the indentation is cut off instead.
+    #[must_use]
+    pub const fn max_indent_cols(mut self, cols: u16) -> Self {
+        self.max_indent_cols = Some(cols);
+        self
+    }
+
+    /// Scroll position used when rendering as a [`Widget`] without a [`TreeState`].
+    /// Rendering with a [`TreeState`] uses its offset instead.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn scroll(mut self, scroll: TreeScroll) -> Self {
+        self.scroll = Some(scroll);
+        self
+    }
+
+    /// Treat the header row of a [`TreeRoot`] as part of its root: it is highlighted when the
+    /// root is selected and clicking it hits the root.
+    /// Otherwise headers are only decoration like the [`root_separator`](Self::root_separator).
+    #[must_use]
+    pub const fn selectable_headers(mut self, selectable: bool) -> Self {
+        self.selectable_headers = selectable;
+        self
+    }
+
+    /// Draw a guide line from the root down to the selected node through the indentation,
+    /// tracing where the selection lives in deep trees.
+    ///
+    /// The guide is only drawn when the whole path of the selection is visible and the
+    /// indentation is not limited by [`max_indent_cols`](Self::max_indent_cols).
+    #[must_use]
+    pub const fn selected_path_style(mut self, style: Style) -> Self {
+        self.selected_path_style = Some(style);
+        self
+    }
+
+    /// Render a bar of the width at the end of each row proportional to the value of
+    /// [`TreeItemRender::as_value`], the core of `du` like views.
+    /// Rows without a value get no bar.
+    ///
+    /// The bars are relative to the largest value among the siblings unless changed with
+    /// [`bar_scale`](Self::bar_scale).
+    #[must_use]
+    pub const fn value_bars(mut self, width: u16, style: Style) -> Self {
+        self.value_bars = Some((width, style));
+        self
+    }
+
+    /// Which values the [`value_bars`](Self::value_bars) are relative to.
+    /// Defaults to [`BarScale::Siblings`].
+    #[must_use]
+    pub const fn bar_scale(mut self, scale: BarScale) -> Self {
+        self.bar_scale = scale;
+        self
+    }
+
+    /// Render at most the amount of rows even when the area has room for more, followed by a
+    /// `+k more` row.
+    ///
+    /// Protects apps with items which are expensive to render from huge areas, like a maximized
+    /// terminal with a tiny font.
+    #[must_use]
+    pub const fn max_rendered_rows(mut self, rows: usize) -> Self {
+        self.max_rendered_rows = Some(rows);
+        self
+    }
+
+    /// Decide whether the [suffix](TreeItemRender::suffix) is hidden or the label is truncated
+    /// first when a row is too narrow.
+    #[must_use]
+    pub const fn shrink_priority(mut self, order: ShrinkOrder) -> Self {
+        self.shrink_priority = order;
+        self
+    }
+
+    /// Paint into the area of each row before its content is rendered.
+    /// Useful for zebra stripes, progress backgrounds or diff colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::style::{Color, Style};
+    /// # use tui_tree_widget::{RowContext, Tree, TreeItem};
+    /// fn zebra(context: RowContext, area: Rect, buf: &mut Buffer) {
+    ///     if context.index % 2 == 1 {
+    ///         buf.set_style(area, Style::default().bg(Color::DarkGray));
+    ///     }
+    /// }
+    ///
+    /// let tree = Tree::new(vec![TreeItem::new_leaf("a")]).row_background(zebra);
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn row_background(mut self, hook: RowHook) -> Self {
+        self.row_background = Some(hook);
+        self
+    }
+
+    /// Paint into the area of each row after its content is rendered.
+    /// Useful for overlays like trailing icons.
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn row_overlay(mut self, hook: RowHook) -> Self {
+        self.row_overlay = Some(hook);
+        self
+    }
+
+    /// Let the style of hidden descendants bubble up to their collapsed ancestor.
+    ///
+    /// For example an error deep inside a collapsed node can highlight the collapsed node.
+    /// The function is only evaluated for descendants of collapsed nodes which are currently
+    /// rendered and the search stops at the first descendant (depth first) returning a style.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::style::{Color, Style};
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let tree = Tree::new(vec![TreeItem::new("root", vec![TreeItem::new_leaf("error")])])
+    ///     .bubble_style(|elem| (*elem == "error").then(|| Style::default().fg(Color::Red)));
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn bubble_style(mut self, bubble_style: fn(&A) -> Option<Style>) -> Self {
+        self.bubble_style = Some(bubble_style);
+        self
+    }
+
+    /// Render a separator line between the subtrees of consecutive root items.
+    /// Each separator consumes one row. Separators can not be selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::text::Span;
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let tree = Tree::new(vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")])
+    ///     .root_separator(Some(Span::raw("\u{2500}".repeat(20))));
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn root_separator(mut self, separator: Option<Span<'a>>) -> Self {
+        self.root_separator = separator;
+        self
+    }
+}
+
+/// Marker of a tag in [`Tree::tag_styles`]
+const TAG_MARKER: &str = "\u{25cf}";
+
+/// Default symbols in front of the items, see [`Tree::node_closed_symbol`]
+const NODE_CLOSED_SYMBOL: &str = "\u{25b6}"; // Arrow to right
+const NODE_OPEN_SYMBOL: &str = "\u{25bc}"; // Arrow down
+const NODE_LEAF_SYMBOL: &str = " ";
+const INDENT_WIDTH: usize = 2;
+
+/// Indentation and node symbol in front of an item
+pub(crate) fn depth_prefix(depth: usize, indent_width: usize, symbol: &str) -> String {
+    format!("{}{} ", indentation(depth, indent_width, None), symbol)
+}
+
+/// Pick the closed, open or leaf symbol of a node
+pub(crate) const fn pick_node_symbol<'s>(
+    closed: &'s str,
+    open: &'s str,
+    leaf: &'s str,
+    has_children: bool,
+    is_opened: bool,
+) -> &'s str {
+    if !has_children {
+        leaf
+    } else if is_opened {
+        open
+    } else {
+        closed
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    const fn node_symbol(&self, has_children: bool, is_opened: bool) -> &'a str {
+        pick_node_symbol(
+            self.node_closed_symbol,
+            self.node_open_symbol,
+            self.node_leaf_symbol,
+            has_children,
+            is_opened,
+        )
+    }
+
+    /// Width of the widest node symbol
+    pub(crate) fn node_symbol_width(&self) -> usize {
+        self.node_closed_symbol
+            .width()
+            .max(self.node_open_symbol.width())
+            .max(self.node_leaf_symbol.width())
+    }
+}
+
+/// Indentation of the depth limited to `max_cols`.
+/// Deeper indentation is replaced by a depth counter like `⋯6»`.
+fn indentation(depth: usize, indent_width: usize, max_cols: Option<u16>) -> String {
+    let width = depth * indent_width;
+    let max_cols = max_cols.map_or(width, usize::from);
+    if width <= max_cols {
+        return " ".repeat(width);
+    }
+    let counter = format!("\u{22ef}{}\u{bb}", depth);
+    let counter_width = counter.chars().count();
+    if counter_width > max_cols {
+        return " ".repeat(max_cols);
+    }
+    format!("{:>width$}", counter, width = max_cols)
+}
+
+/// Reverse a [`depth_prefix`] for right to left rendering with the arrows pointing to the left.
+/// Numbers of depth counters keep their order.
+fn mirror_prefix(prefix: &str) -> String {
+    let mut tokens: Vec<String> = Vec::new();
+    for c in prefix.chars() {
+        match tokens.last_mut() {
+            Some(last) if c.is_ascii_digit() && last.chars().all(|c| c.is_ascii_digit()) => {
+                last.push(c);
+            }
+            _ => tokens.push(c.to_string()),
+        }
+    }
+    tokens
+        .iter()
+        .rev()
+        .map(|token| match token.as_str() {
+            "\u{25b6}" => "\u{25c0}",
+            "\u{251c}" => "\u{2524}",
+            "\u{2514}" => "\u{2518}",
+            "\u{bb}" => "\u{ab}",
+            token => token,
+        })
+        .collect()
+}
+
+/// First style provided by `bubble_style` of any descendant in depth first order
+fn bubbled_style<A>(
+    children: &[TreeItem<A>],
+    bubble_style: fn(&A) -> Option<Style>,
+) -> Option<Style> {
+    children.iter().find_map(|child| {
+        bubble_style(&child.elem).or_else(|| bubbled_style(&child.children, bubble_style))
+    })
 }
 
 impl<'a, A: TreeItemRender> StatefulWidget for Tree<'a, A> {
     type State = TreeState;
 
-    #[allow(clippy::too_many_lines)]
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        buf.set_style(area, self.style);
+        let color_mode = self.color_mode;
+        let color_fallbacks = self.color_fallbacks;
+        self.render_tree(area, buf, state, None);
+        color_mode::degrade_colors(color_mode, color_fallbacks, area, buf);
+    }
+}
+
+impl<'a, A: TreeItemRender> Tree<'a, A> {
+    /// Lay out the tree and draw it into the buffer.
+    /// With `rows` only the rows within the range are drawn, everything around them is kept.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn render_tree(
+        mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut TreeState,
+        rows: Option<Range<usize>>,
+    ) {
+        let partial = rows.is_some();
+        let redraw = |index: usize| rows.as_ref().map_or(true, |rows| rows.contains(&index));
+        if !partial {
+            buf.set_style(area, self.style);
+            state.rendered.clear();
+        }
+        let resized = state.track_area_size(area);
 
         // Get the inner area inside a possible block, otherwise use the full area
-        let area = self.block.map_or(area, |b| {
+        let area = self.block.take().map_or(area, |b| {
             let inner_area = b.inner(area);
-            b.render(area, buf);
+            if !partial {
+                b.render(area, buf);
+            }
             inner_area
         });
 
+        state.scroll_metrics = ScrollMetrics::default();
         if area.width < 1 || area.height < 1 {
             return;
         }
+        let (area, scrollbar_area) = self.split_scrollbar(area);
 
-        let visible = flatten(&state.get_all_opened(), &self.items);
+        state.sync_stable_ids(&self.items);
+        let now = self.now.unwrap_or_else(Instant::now);
+        if let Some((_, pulse)) = &self.changed {
+            state.expire_changed(pulse, now);
+        }
+        #[cfg(feature = "diff")]
+        if let Some((_, duration)) = self.inline_diff {
+            state.expire_previous_texts(duration, now);
+        }
+
+        let visible = flatten::flatten_state(state, &self.items);
+        state.record_frame(visible.len());
         if visible.is_empty() {
+            if !partial {
+                self.fill_remaining_lines(area, 0, buf);
+            }
             return;
         }
+        let (visible, is_peek) = if self.peek_children > 0 {
+            flatten::with_peek_rows(visible, state, self.peek_children)
+        } else {
+            let is_peek = vec![false; visible.len()];
+            (visible, is_peek)
+        };
         let available_height = area.height as usize;
+        let has_selection = !state.selected.is_empty();
 
-        let selected_index = if state.selected.is_empty() {
-            0
+        // Labels are wrapped to the columns left of them
+        let wrap_width = |item: &Flattened<A>| {
+            self.wrap_continuation.map(|continuation| {
+                (
+                    self.label_width(area.width, has_selection, item.depth()),
+                    continuation,
+                )
+            })
+        };
+        let texts = visible
+            .iter()
+            .zip(&is_peek)
+            .map(|(item, is_peek)| {
+                let context = RenderContext {
+                    depth: item.depth(),
+                    is_selected: !is_peek && state.selected == item.identifier,
+                    is_opened: !is_peek && state.is_expanded(&item.identifier, item.item),
+                    has_children: item.item.has_children(),
+                    width: self.label_width(area.width, has_selection, item.depth()),
+                };
+                let mut text = match item.grouped {
+                    Some(len) => Text::raw(format!("\u{2026} {} items", len)),
+                    None => item.item.elem.as_text_with(context),
+                };
+                if let (Some(style), Some(query)) = (self.filter_match_style, state.filter()) {
+                    text = filter::highlight_matches(text, query, style);
+                }
+                if let Some(style) = self.numbering {
+                    text = numbering::numbered(text, style, &item.identifier);
+                }
+                match wrap_width(item) {
+                    Some((width, continuation)) => wrap::wrap_text(text, width, continuation),
+                    None => text,
+                }
+            })
+            .collect::<Vec<_>>();
+        let widest = texts.iter().map(Text::width).max().unwrap_or_default();
+        state.horizontal_offset = state.horizontal_offset.min(widest.saturating_sub(1));
+        let texts = texts
+            .into_iter()
+            .map(|text| hscroll::skip_columns(text, state.horizontal_offset, self.ellipsis))
+            .collect::<Vec<_>>();
+
+        // Root items except the first one get a separator row in front of them
+        let has_separator = |index: usize, item: &Flattened<A>| {
+            self.root_separator.is_some() && index > 0 && item.depth() == 0
+        };
+        // Root items of a tree created from roots get a header row in front of them
+        let root_header = |item: &Flattened<A>| match item.identifier.as_slice() {
+            [root] => self.root_headers.get(*root),
+            _ => None,
+        };
+        // Opened nodes waiting for their children get a placeholder row behind them
+        let loading = visible
+            .iter()
+            .zip(&is_peek)
+            .map(|(o, is_peek)| !is_peek && state.shows_loading_placeholder(&o.identifier))
+            .collect::<Vec<_>>();
+        let heights = window::Heights::new(visible.iter().enumerate().map(|(index, item)| {
+            self.empty_items.height(texts[index].height())
+                + usize::from(has_separator(index, item))
+                + usize::from(root_header(item).is_some())
+                + usize::from(loading[index])
+        }));
+
+        // Without a selection or when scrolled away from it the offset is kept as it is
+        let selected_index = if state.selected.is_empty() || state.viewport_detached {
+            state.offset.min(visible.len() - 1)
         } else {
             visible
                 .iter()
-                .position(|o| o.identifier == state.selected)
+                .zip(&is_peek)
+                .position(|(o, is_peek)| !is_peek && o.identifier == state.selected)
                 .unwrap_or(0)
         };
 
-        let mut start = state.offset.min(selected_index);
-        let mut end = start;
-        let mut height = 0;
-        for item in visible.iter().skip(start) {
-            if height + item.item.height() > available_height {
-                break;
+        let offset = match state.placement.take() {
+            Some(placement) if !state.selected.is_empty() && !state.viewport_detached => {
+                placement.offset(&heights, selected_index, available_height, state.offset)
             }
-
-            height += item.item.height();
-            end += 1;
-        }
-
-        while selected_index >= end {
-            height = height.saturating_add(visible[end].item.height());
-            end += 1;
-            while height > available_height {
-                height = height.saturating_sub(visible[start].item.height());
-                start += 1;
+            _ => state.offset,
+        };
+        // After a resize the rows fill the area again instead of leaving lines empty at the end
+        let offset = if resized {
+            offset.min(heights.start_until(heights.len(), available_height))
+        } else {
+            offset
+        };
+        let window = |available_height| {
+            let (start, end) = heights.window(offset, selected_index, available_height);
+            // Drop rows from the start until the selection is within the capped rows
+            match self.max_rendered_rows {
+                Some(max) if end - start > max => {
+                    let start = start.max((selected_index + 1).saturating_sub(max));
+                    (start, start + max)
+                }
+                _ => (start, end),
             }
-        }
-
+        };
+        let (mut start, mut end) = window(available_height);
+        // The breadcrumb of the scrolled off ancestors of the first row takes the first line
+        let breadcrumb_y =
+            if self.ancestor_breadcrumb && visible[start].depth() > 0 && area.height > 1 {
+                let (breadcrumb_start, breadcrumb_end) = window(available_height - 1);
+                start = breadcrumb_start;
+                end = breadcrumb_end;
+                match self.start_corner {
+                    Corner::BottomLeft => Some(area.bottom() - 1),
+                    _ => Some(area.top()),
+                }
+            } else {
+                None
+            };
+        let area = match breadcrumb_y {
+            Some(y) => {
+                if !partial {
+                    let breadcrumb =
+                        breadcrumb::breadcrumb(&self.items, &visible[start].identifier);
+                    buf.set_stringn(area.left(), y, breadcrumb, area.width as usize, self.style);
+                }
+                Rect {
+                    y: area.y + u16::from(y == area.top()),
+                    height: area.height - 1,
+                    ..area
+                }
+            }
+            None => area,
+        };
         state.offset = start;
+        state.scroll_metrics = ScrollMetrics {
+            total_rows: visible.len(),
+            offset: start,
+            viewport_rows: end - start,
+        };
+
+        // Rows of the selected node and its ancestors, starting at the root
+        let selected_path_rows = self.selected_path_style.and_then(|_| {
+            (1..=state.selected.len())
+                .map(|len| {
+                    visible
+                        .iter()
+                        .zip(&is_peek)
+                        .position(|(o, is_peek)| !is_peek && o.identifier == state.selected[..len])
+                })
+                .collect::<Option<Vec<_>>>()
+        });
+
+        let global_max = match (self.value_bars, self.bar_scale) {
+            (Some(_), BarScale::Global) => bars::global_max(&self.items),
+            _ => 0.0,
+        };
 
         let highlight_symbol = self.highlight_symbol.unwrap_or("");
         let blank_symbol = " ".repeat(highlight_symbol.width());
 
         let mut current_height = 0;
-        let has_selection = !state.selected.is_empty();
         #[allow(clippy::cast_possible_truncation)]
-        for item in visible.iter().skip(state.offset).take(end - start) {
+        let indents = indent::Indents::new(
+            visible.iter().map(|item| item.depth()),
+            self.indent_width,
+            self.max_indent_cols,
+        );
+        for (index, item) in visible
+            .iter()
+            .enumerate()
+            .skip(state.offset)
+            .take(end - start)
+        {
+            // Only a selected row taller than the area does not fit and is clipped
+            let height = (heights.get(index) as u16).min(area.height - current_height);
             #[allow(clippy::single_match_else)] // Keep same as List impl
             let (x, y) = match self.start_corner {
                 Corner::BottomLeft => {
-                    current_height += item.item.height() as u16;
+                    current_height += height;
                     (area.left(), area.bottom() - current_height)
                 }
                 _ => {
                     let pos = (area.left(), area.top() + current_height);
-                    current_height += item.item.height() as u16;
+                    current_height += height;
                     pos
                 }
             };
+            if !redraw(index) {
+                continue;
+            }
+            if partial {
+                // The lines of the row still show what was drawn before
+                for line in y..y + height {
+                    for column in area.left()..area.right() {
+                        buf.get_mut(column, line).reset();
+                    }
+                }
+                buf.set_style(Rect::new(x, y, area.width, height), self.style);
+                state
+                    .rendered
+                    .retain(|row| row.identifier != item.identifier);
+            }
+
+            // The separator is in front of the item in reading direction
+            let separator = self
+                .root_separator
+                .as_ref()
+                .filter(|_| has_separator(index, item));
+            let item_height = height.saturating_sub(u16::from(separator.is_some()));
+            let y = match separator {
+                Some(separator) if height > 0 => {
+                    let (separator_y, item_y) = match self.start_corner {
+                        Corner::BottomLeft => (y + item_height, y),
+                        _ => (y, y + 1),
+                    };
+                    buf.set_span(x, separator_y, separator, area.width);
+                    item_y
+                }
+                _ => y,
+            };
+            let header = root_header(item).filter(|_| item_height > 0);
+            let header_height = u16::from(header.is_some());
+            let item_height = item_height - header_height;
+            let (y, header_y) = match header {
+                Some((title, style)) => {
+                    let (header_y, item_y) = match self.start_corner {
+                        Corner::BottomLeft => (y + item_height, y),
+                        _ => (y, y + 1),
+                    };
+                    let header_area = Rect::new(x, header_y, area.width, 1);
+                    buf.set_style(header_area, *style);
+                    buf.set_spans(x, header_y, title, area.width);
+                    (item_y, header_y)
+                }
+                None => (y, y),
+            };
+            // The placeholder is behind the item in reading direction
+            let has_placeholder = loading[index] && item_height > 1;
+            let item_height = item_height - u16::from(has_placeholder);
+            let (y, placeholder_y) = match (has_placeholder, self.start_corner) {
+                (false, _) => (y, None),
+                (true, Corner::BottomLeft) => (y + 1, Some(y)),
+                (true, _) => (y, Some(y + item_height)),
+            };
             let area = Rect {
                 x,
                 y,
                 width: area.width,
-                height: item.item.height() as u16,
+                height: item_height,
+            };
+            if area.height == 0 {
+                continue;
+            }
+            // Area of the row including a selectable header
+            let row_area = if self.selectable_headers {
+                Rect {
+                    y: y.min(header_y),
+                    height: area.height + header_height,
+                    ..area
+                }
+            } else {
+                area
+            };
+
+            let is_peek = is_peek[index];
+            let is_selected = !is_peek && state.selected == item.identifier;
+            let is_marked = !is_peek
+                && self
+                    .marked
+                    .as_ref()
+                    .map_or(false, |(marked, _)| marked.contains(&item.identifier));
+            let context = RowContext {
+                identifier: &item.identifier,
+                index,
+                depth: item.depth(),
+                is_selected,
+                is_opened: !is_peek && state.is_expanded(&item.identifier, item.item),
+                has_children: item.item.has_children(),
             };
 
-            let item_style = self.style.patch(item.item.style);
+            let mut item_style = self.style.patch(item.item.style);
+            if let Some(style_fn) = item.item.style_fn {
+                let style = state.cached_style(&item.identifier, self.style_version, || {
+                    style_fn(&item.item.elem)
+                });
+                item_style = item_style.patch(style);
+            }
+            if is_peek {
+                item_style = item_style.patch(self.peek_style);
+            }
+            if let Some(bubble_style) = self.bubble_style.filter(|_| !context.is_opened) {
+                if let Some(style) = bubbled_style(&item.item.children, bubble_style) {
+                    item_style = item_style.patch(style);
+                }
+            }
             buf.set_style(area, item_style);
+            if let Some(hook) = self.row_background {
+                hook(context, area, buf);
+            }
+
+            // Positions are computed left to right and mirrored when writing right to left
+            let mirror = |logical_x: u16, width: u16| {
+                if self.right_to_left {
+                    area.right() - (logical_x - area.left()) - width
+                } else {
+                    logical_x
+                }
+            };
 
-            let is_selected = state.selected == item.identifier;
             let after_highlight_symbol_x = if has_selection {
-                let symbol = if is_selected {
-                    highlight_symbol
+                let (symbol, symbol_y) = if is_selected {
+                    let line = (state.selected_line as u16).min(area.height.saturating_sub(1));
+                    (highlight_symbol, y + line)
                 } else {
-                    &blank_symbol
+                    (blank_symbol.as_str(), y)
                 };
-                let (x, _) = buf.set_stringn(x, y, symbol, area.width as usize, item_style);
-                x
+                let width = (symbol.width() as u16).min(area.width);
+                buf.set_stringn(
+                    mirror(x, width),
+                    symbol_y,
+                    symbol,
+                    width as usize,
+                    item_style,
+                );
+                x + width
             } else {
                 x
             };
 
+            // One marker column per tag style, filled when the node has the tag
+            let after_markers_x = {
+                let mut marker_x = after_highlight_symbol_x;
+                for (tag, style) in self.tag_styles {
+                    if marker_x >= area.right() {
+                        break;
+                    }
+                    if state.has_tag(&item.identifier, tag) {
+                        let style = item_style.patch(*style);
+                        buf.set_string(mirror(marker_x, 1), y, TAG_MARKER, style);
+                    }
+                    marker_x += 1;
+                }
+                if let Some((symbol, style)) = self.bookmark_symbol {
+                    let width = (symbol.width() as u16).min(area.right().saturating_sub(marker_x));
+                    if !is_peek && state.is_bookmarked(&item.identifier) {
+                        let style = item_style.patch(style);
+                        let x = mirror(marker_x, width);
+                        buf.set_stringn(x, y, symbol, width as usize, style);
+                    }
+                    marker_x += width;
+                }
+                if let Some(symbol) = self.marked_symbol {
+                    let width = (symbol.width() as u16).min(area.right().saturating_sub(marker_x));
+                    if let Some((_, style)) = self.marked.as_ref().filter(|_| is_marked) {
+                        let style = item_style.patch(*style);
+                        let x = mirror(marker_x, width);
+                        buf.set_stringn(x, y, symbol, width as usize, style);
+                    }
+                    marker_x += width;
+                }
+                marker_x
+            };
+
+            if let Some(placeholder_y) = placeholder_y {
+                let (text, style) = self.loading_placeholder;
+                let placeholder_x = after_markers_x + self.prefix_width(item.depth() + 1) as u16;
+                let width = (text.width() as u16).min(area.right().saturating_sub(placeholder_x));
+                let x = mirror(placeholder_x, width);
+                let style = self.style.patch(style);
+                buf.set_stringn(x, placeholder_y, text, width as usize, style);
+            }
+
+            let fits = self.max_indent_cols.map_or(true, |max| {
+                item.depth() * self.indent_width <= usize::from(max)
+            });
+            let guided;
+            let indent = if self.guide_lines && fits {
+                guided = guides::guide_indentation(&item.last_siblings, self.indent_width);
+                &guided
+            } else {
+                indents.get(item.depth())
+            };
             let after_depth_x = {
-                let symbol = if item.item.children.is_empty() {
-                    " "
-                } else if state.opened.contains(&item.identifier) {
-                    "\u{25bc}" // Arrow down
+                let max_width = area.width.saturating_sub(after_markers_x - x);
+                let symbol = state
+                    .arrow_frame(&item.identifier, self.arrow_animation)
+                    .filter(|_| context.is_opened)
+                    .unwrap_or_else(|| self.node_symbol(context.has_children, context.is_opened));
+                let parts = [indent, symbol, " "];
+                let full_width = parts.iter().map(|part| part.width()).sum::<usize>();
+                let width = full_width.min(max_width as usize) as u16;
+                if self.right_to_left {
+                    let string = parts
+                        .concat()
+                        .chars()
+                        .take(width as usize)
+                        .collect::<String>();
+                    let string = mirror_prefix(&string);
+                    buf.set_string(mirror(after_markers_x, width), y, string, item_style);
                 } else {
-                    "\u{25b6}" // Arrow to right
-                };
-                let string = format!("{:>width$}{} ", "", symbol, width = item.depth() * 2);
-                let max_width = area.width.saturating_sub(after_highlight_symbol_x - x);
-                let (x, _) = buf.set_stringn(
-                    after_highlight_symbol_x,
-                    y,
-                    string,
-                    max_width as usize,
-                    item_style,
-                );
-                x
+                    // Written part by part to avoid building the prefix for every row
+                    let mut part_x = after_markers_x;
+                    for part in parts {
+                        let remaining = (after_markers_x + width).saturating_sub(part_x);
+                        part_x = buf
+                            .set_stringn(part_x, y, part, remaining as usize, item_style)
+                            .0;
+                    }
+                }
+                after_markers_x + width
+            };
+
+            // The guide runs below the symbol of each ancestor down to the next node of the path
+            if let (Some(style), Some(path_rows), true) =
+                (self.selected_path_style, &selected_path_rows, fits)
+            {
+                let style = item_style.patch(style);
+                for (depth, rows) in path_rows.windows(2).enumerate() {
+                    let guide_x = after_markers_x + (depth * self.indent_width) as u16;
+                    if guide_x + 1 >= after_depth_x {
+                        break;
+                    }
+                    let lines = if index == rows[0] {
+                        1..area.height
+                    } else if rows[0] < index && index < rows[1] {
+                        0..area.height
+                    } else if index == rows[1] {
+                        let corner = if self.right_to_left {
+                            "\u{2518}"
+                        } else {
+                            "\u{2514}"
+                        };
+                        buf.set_string(mirror(guide_x, 1), y, corner, style);
+                        buf.set_string(mirror(guide_x + 1, 1), y, "\u{2500}", style);
+                        continue;
+                    } else {
+                        continue;
+                    };
+                    for line in lines {
+                        buf.set_string(mirror(guide_x, 1), y + line, "\u{2502}", style);
+                    }
+                }
+            }
+
+            // Right aligned parts take their columns from the right edge, the rightmost first
+            let label_x = match self.check_symbols {
+                Some(symbols) => {
+                    let width = (symbols.width() as u16).min(area.right() - after_depth_x);
+                    if !is_peek {
+                        let symbol = symbols.symbol(state.check_state(&item.identifier));
+                        let x = mirror(after_depth_x, width);
+                        buf.set_stringn(x, y, symbol, width as usize, item_style);
+                    }
+                    after_depth_x + width
+                }
+                None => after_depth_x,
             };
 
-            let max_element_width = area.width.saturating_sub(after_depth_x - x);
-            for (j, line) in item.item.elem.as_text().lines.iter().enumerate() {
-                buf.set_spans(after_depth_x, y + j as u16, line, max_element_width);
+            let mut right_edge = edge::RightEdge::new(area, label_x);
+
+            let bar = self
+                .value_bars
+                .map(|(width, style)| (right_edge.take(width), style));
+            if let (Some(((bar_x, bar_width), style)), Some(value)) =
+                (bar, item.item.elem.as_value())
+            {
+                let max = self
+                    .bar_scale
+                    .max(&self.items, &item.identifier, global_max);
+                let fraction = if max > 0.0 { value / max } else { 0.0 };
+                // Partial cells only grow to the right
+                let bar = bars::bar(fraction, bar_width, !self.right_to_left);
+                let bar = if self.right_to_left {
+                    bar.chars().rev().collect()
+                } else {
+                    bar
+                };
+                let style = item_style.patch(style);
+                buf.set_string(mirror(bar_x, bar_width), y, bar, style);
+            }
+
+            let text = &texts[index];
+            let suffix = item
+                .grouped
+                .map_or_else(|| item.item.elem.suffix(), |_| None);
+            let (max_element_width, show_suffix) = self.shrink_priority.layout(
+                right_edge.remaining() as usize,
+                text.width(),
+                suffix.as_ref().map(Spans::width),
+            );
+            let suffix_x = suffix.filter(|_| show_suffix).and_then(|suffix| {
+                let width = suffix.width() as u16;
+                let suffix_x = right_edge.take_exact(width)?;
+                buf.set_spans(mirror(suffix_x, width), y, &suffix, width);
+                Some(suffix_x)
+            });
+            for (j, line) in text.lines.iter().take(area.height as usize).enumerate() {
+                let width = line.width().min(max_element_width) as u16;
+                let line_x = mirror(label_x, width);
+                buf.set_spans(line_x, y + j as u16, line, width);
+                if self.ellipsis && width > 0 && line.width() > max_element_width {
+                    let x = line_x + width - 1;
+                    buf.set_string(x, y + j as u16, hscroll::ELLIPSIS, Style::default());
+                }
+            }
+            // Columns of scrolled labels do not match the previous text
+            #[cfg(feature = "diff")]
+            if let (Some((style, _)), Some(previous), 0) = (
+                self.inline_diff,
+                state.previous_text(&item.identifier),
+                state.horizontal_offset,
+            ) {
+                // Both texts get the same prefix and wrapping to compare the same columns
+                let prefix = self
+                    .numbering
+                    .map(|style| numbering::prefix(style, &item.identifier))
+                    .unwrap_or_default();
+                let shown = |raw: &str| {
+                    let raw = prefix.clone() + raw;
+                    match wrap_width(item) {
+                        Some((width, continuation)) => {
+                            text_to_string(&wrap::wrap_text(Text::raw(raw), width, continuation))
+                        }
+                        None => raw,
+                    }
+                };
+                let current = shown(&text_to_string(&item.item.elem.as_text()));
+                // Labels which differ from the raw text, like a collapsed summary, are not compared
+                let inserted = if item.grouped.is_none() && current == text_to_string(text) {
+                    diff::inserted_columns(&shown(previous), &current)
+                } else {
+                    Vec::new()
+                };
+                for (j, column, width) in inserted {
+                    let line_width = match text.lines.get(j) {
+                        Some(line) if j < area.height as usize => {
+                            line.width().min(max_element_width)
+                        }
+                        _ => continue,
+                    };
+                    if column + width <= line_width {
+                        let line_x = mirror(label_x, line_width as u16);
+                        let area = Rect::new(line_x + column as u16, y + j as u16, width as u16, 1);
+                        buf.set_style(area, style);
+                    }
+                }
+            }
+            if !is_peek {
+                state.rendered.push(hit::RenderedRow {
+                    identifier: item.identifier.clone(),
+                    y: row_area.y,
+                    height: row_area.height,
+                    x,
+                    width: area.width,
+                    arrow_x: context
+                        .has_children
+                        .then(|| after_markers_x + indent.width() as u16)
+                        .filter(|arrow_x| *arrow_x < after_depth_x),
+                    arrow_width: self.node_symbol_width() as u16,
+                    checkbox_x: self.check_symbols.map(|_| after_depth_x),
+                    label_x,
+                    suffix_x,
+                    mirrored: self.right_to_left,
+                });
+            }
+            if let Some((style, pulse)) = &self.changed {
+                let is_on = state
+                    .changed
+                    .get(&item.identifier)
+                    .map_or(false, |changed| pulse.is_on(*changed, now));
+                if is_on {
+                    buf.set_style(area, *style);
+                }
+            }
+            if let Some((_, style)) = self.marked.as_ref().filter(|_| is_marked) {
+                buf.set_style(area, *style);
+            }
+            if let Some(style) = self.multi_highlight_style {
+                if !is_peek && state.is_multi_selected(&item.identifier) {
+                    buf.set_style(area, style);
+                }
             }
             if is_selected {
-                buf.set_style(area, self.highlight_style);
+                buf.set_style(row_area, self.highlight_style);
             }
+            if let Some(hook) = self.row_overlay {
+                hook(context, area, buf);
+            }
+        }
+        if partial {
+            return;
         }
+        let more = visible.len() - end;
+        if self.max_rendered_rows.is_some() && more > 0 && current_height < area.height {
+            let y = match self.start_corner {
+                Corner::BottomLeft => area.bottom() - current_height - 1,
+                _ => area.top() + current_height,
+            };
+            let indicator = format!("+{} more", more);
+            buf.set_stringn(area.left(), y, indicator, area.width as usize, self.style);
+            current_height += 1;
+        }
+        self.fill_remaining_lines(area, current_height, buf);
+        if let Some(scrollbar_area) = scrollbar_area {
+            self.render_scrollbar(scrollbar_area, state.scroll_metrics, buf);
+        }
+        self.render_tooltip(area, buf, state);
+        state.advance_arrow_animation(self.arrow_animation.len());
     }
 }
 
 impl<'a, A: TreeItemRender> Widget for Tree<'a, A> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let mut state = TreeState::default();
+        if let Some(scroll) = &self.scroll {
+            state.offset = scroll.resolve(&flatten::flatten_state(&state, &self.items));
+        }
         StatefulWidget::render(self, area, buf, &mut state);
     }
 }
+
+#[test]
+fn root_separator_renders_between_roots() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+
+    let area = Rect::new(0, 0, 6, 8);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).root_separator(Some(Span::raw("------")));
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+
+    let expected = Buffer::with_lines(vec![
+        "  a   ",
+        "------",
+        "\u{25bc} b   ",
+        "    c ",
+        "  \u{25b6} d ",
+        "    g ",
+        "------",
+        "  h   ",
+    ]);
+    assert_eq!(buffer, expected);
+}
+
+#[test]
+fn peek_children_are_not_selectable() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![1, 0]);
+
+    let area = Rect::new(0, 0, 6, 5);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).peek_children(2).highlight_symbol(">");
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+
+    let mut expected = Buffer::with_lines(vec![
+        "   a  ",
+        " \u{25b6} b  ",
+        "     c",
+        "   \u{25b6} d",
+        "   h  ",
+    ]);
+    expected.set_style(
+        Rect::new(0, 2, 6, 2),
+        Style::default().add_modifier(Modifier::DIM),
+    );
+    assert_eq!(buffer, expected);
+    assert_eq!(state.rendered.len(), 3);
+}
+
+#[test]
+fn right_to_left_mirrors_rows() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1, 1]);
+
+    let area = Rect::new(0, 0, 7, 4);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).highlight_symbol(">").right_to_left(true);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+
+    let expected = Buffer::with_lines(vec![
+        "   a   ",
+        "   b \u{25bc} ",
+        " c     ",
+        " d \u{25c0}  >",
+    ]);
+    assert_eq!(buffer, expected);
+    assert_eq!(state.hit_test((3, 3)), Some((vec![1, 1], HitTarget::Arrow)));
+    assert_eq!(state.hit_test((1, 3)), Some((vec![1, 1], HitTarget::Label)));
+    assert_eq!(
+        state.hit_test((6, 3)),
+        Some((vec![1, 1], HitTarget::Gutter))
+    );
+}
+
+#[test]
+fn tag_markers_in_front_of_indentation() {
+    use tui::style::Color;
+
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.tag(vec![0], "staged");
+    state.tag(vec![1], "conflicted");
+    state.tag(vec![2], "staged");
+    state.tag(vec![2], "conflicted");
+
+    let area = Rect::new(0, 0, 6, 3);
+    let mut buffer = Buffer::empty(area);
+    let staged = Style::default().fg(Color::Green);
+    let conflicted = Style::default().fg(Color::Red);
+    let styles = [("staged", staged), ("conflicted", conflicted)];
+    let tree = Tree::new(items).tag_styles(&styles);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+
+    let mut expected = Buffer::with_lines(vec![
+        "\u{25cf}   a ",
+        " \u{25cf}\u{25b6} b ",
+        "\u{25cf}\u{25cf}  h ",
+    ]);
+    expected.set_style(Rect::new(0, 0, 1, 1), staged);
+    expected.set_style(Rect::new(1, 1, 1, 1), conflicted);
+    expected.set_style(Rect::new(0, 2, 1, 1), staged);
+    expected.set_style(Rect::new(1, 2, 1, 1), conflicted);
+    assert_eq!(buffer, expected);
+    assert_eq!(state.hit_test((2, 1)), Some((vec![1], HitTarget::Arrow)));
+}
+
+#[test]
+fn empty_items_policy() {
+    let items = vec![
+        TreeItem::new_leaf(""),
+        TreeItem::new_leaf("a"),
+        TreeItem::new_leaf(""),
+    ];
+    let mut state = TreeState::default();
+    state.select(vec![2]);
+
+    let area = Rect::new(0, 0, 4, 3);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items.clone()).highlight_symbol(">");
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    assert_eq!(buffer, Buffer::with_lines(vec!["    ", "   a", ">   "]));
+
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items)
+        .highlight_symbol(">")
+        .empty_items(EmptyItems::Skip);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    assert_eq!(buffer, Buffer::with_lines(vec!["   a", "    ", "    "]));
+    assert_eq!(state.hit_test((0, 1)), None);
+}
+
+#[test]
+fn indentation_is_limited() {
+    assert_eq!(indentation(2, 2, None), "    ");
+    assert_eq!(indentation(2, 2, Some(4)), "    ");
+    assert_eq!(indentation(3, 2, Some(4)), " \u{22ef}3\u{bb}");
+    assert_eq!(indentation(12, 2, Some(4)), "\u{22ef}12\u{bb}");
+    assert_eq!(indentation(3, 2, Some(2)), "  ");
+    assert_eq!(
+        mirror_prefix(&format!("{}\u{25b6} ", indentation(12, 2, Some(4)))),
+        " \u{25c0}\u{ab}12\u{22ef}"
+    );
+}
+
+#[test]
+fn selected_item_taller_than_area_is_clipped() {
+    let items = vec![
+        TreeItem::new_leaf("a"),
+        TreeItem::new_leaf("b\nc\nd"),
+        TreeItem::new_leaf("e"),
+    ];
+    let mut state = TreeState::default();
+    state.select(vec![1]);
+
+    let area = Rect::new(0, 0, 4, 2);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(Tree::new(items.clone()), area, &mut buffer, &mut state);
+    assert_eq!(buffer, Buffer::with_lines(vec!["  b ", "  c "]));
+    assert_eq!(state.get_offset(), 1);
+
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).start_corner(Corner::BottomLeft);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    assert_eq!(buffer, Buffer::with_lines(vec!["  b ", "  c "]));
+}
+
+#[test]
+fn navigation_without_items() {
+    let items: Vec<TreeItem<&str>> = Vec::new();
+    let mut state = TreeState::default();
+    state.key_up(&items);
+    state.key_down(&items);
+    state.select_last(&items);
+    assert!(state.selected().is_empty());
+}
+
+#[test]
+fn max_rendered_rows_shows_indicator() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1, 2]);
+
+    let area = Rect::new(0, 0, 8, 5);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).max_rendered_rows(2);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let expected = Buffer::with_lines(vec![
+        "  \u{25b6} d   ",
+        "    g   ",
+        "+1 more ",
+        "        ",
+        "        ",
+    ]);
+    assert_eq!(buffer, expected);
+    assert_eq!(state.get_offset(), 3);
+}
+
+#[test]
+fn root_headers_above_their_subtree() {
+    use tui::style::Color;
+
+    let roots = vec![
+        TreeRoot::new("one", TreeItem::new("a", vec![TreeItem::new_leaf("b")])),
+        TreeRoot::new("two", TreeItem::new_leaf("c")),
+    ];
+    let mut state = TreeState::default();
+    state.open(vec![0]);
+    state.select(vec![1]);
+
+    let area = Rect::new(0, 0, 6, 5);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::from_roots(roots.clone()).highlight_style(Style::default().fg(Color::Red));
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let mut expected = Buffer::with_lines(vec![
+        "one   ",
+        "\u{25bc} a   ",
+        "    b ",
+        "two   ",
+        "  c   ",
+    ]);
+    expected.set_style(Rect::new(0, 4, 6, 1), Style::default().fg(Color::Red));
+    assert_eq!(buffer, expected);
+    assert_eq!(state.hit_test((0, 3)), None);
+
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::from_roots(roots)
+        .highlight_style(Style::default().fg(Color::Red))
+        .selectable_headers(true);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    expected.set_style(Rect::new(0, 3, 6, 1), Style::default().fg(Color::Red));
+    assert_eq!(buffer, expected);
+    assert_eq!(state.hit_test((0, 3)).map(|(id, _)| id), Some(vec![1]));
+}
+
+#[test]
+fn selected_path_guide() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 1]);
+
+    let area = Rect::new(0, 0, 8, 8);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).selected_path_style(Style::default());
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let expected = Buffer::with_lines(vec![
+        "  a     ",
+        "\u{25bc} b     ",
+        "\u{2502}   c   ",
+        "\u{2514}\u{2500}\u{25bc} d   ",
+        "  \u{2502}   e ",
+        "  \u{2514}\u{2500}  f ",
+        "    g   ",
+        "  h     ",
+    ]);
+    assert_eq!(buffer, expected);
+}
+
+#[test]
+fn value_bars_relative_to_siblings() {
+    #[derive(Clone, Copy)]
+    struct Size(&'static str, f64);
+
+    impl TreeItemRender for Size {
+        fn as_text(&self) -> Text<'_> {
+            Text::raw(self.0)
+        }
+
+        fn as_value(&self) -> Option<f64> {
+            Some(self.1)
+        }
+    }
+
+    let items = vec![
+        TreeItem::new(Size("a", 8.0), vec![TreeItem::new_leaf(Size("b", 2.0))]),
+        TreeItem::new_leaf(Size("c", 4.0)),
+    ];
+    let mut state = TreeState::default();
+    state.open(vec![0]);
+
+    let area = Rect::new(0, 0, 8, 3);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items.clone()).value_bars(2, Style::default());
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let expected = Buffer::with_lines(vec![
+        "\u{25bc} a   \u{2588}\u{2588}",
+        "    b \u{2588}\u{2588}",
+        "  c   \u{2588} ",
+    ]);
+    assert_eq!(buffer, expected);
+
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items)
+        .value_bars(2, Style::default())
+        .bar_scale(BarScale::Global);
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let expected = Buffer::with_lines(vec![
+        "\u{25bc} a   \u{2588}\u{2588}",
+        "    b \u{258c} ",
+        "  c   \u{2588} ",
+    ]);
+    assert_eq!(buffer, expected);
+}
+
+#[test]
+fn select_clamped_to_nearest() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.select_clamped(&items, vec![1, 1, 0]), [1, 1, 0]);
+    assert_eq!(state.select_clamped(&items, vec![1, 7, 0]), [1, 2]);
+    assert_eq!(state.select_clamped(&items, vec![0, 3]), [0]);
+    assert_eq!(state.selected(), [0]);
+
+    let empty: Vec<TreeItem<&str>> = Vec::new();
+    assert!(state.select_clamped(&empty, vec![2]).is_empty());
+}
+
+#[test]
+fn continuation_lines_align_below_first_line() {
+    let items = vec![TreeItem::new(
+        "a",
+        vec![TreeItem::new(
+            "first\nsecond",
+            vec![TreeItem::new_leaf("c")],
+        )],
+    )];
+    let mut state = TreeState::default();
+    state.open(vec![0]);
+    state.select(vec![0, 0]);
+
+    let area = Rect::new(0, 0, 14, 3);
+    let mut buffer = Buffer::empty(area);
+    let tree = Tree::new(items).highlight_symbol(">> ");
+    StatefulWidget::render(tree, area, &mut buffer, &mut state);
+    let expected = Buffer::with_lines(vec![
+        "   \u{25bc} a        ",
+        ">>   \u{25b6} first  ",
+        "       second ",
+    ]);
+    assert_eq!(buffer, expected);
+}
+
+#[test]
+fn text_depends_on_render_context() {
+    #[derive(Clone)]
+    struct Counted(&'static str);
+
+    impl TreeItemRender for Counted {
+        fn as_text(&self) -> Text<'_> {
+            self.0.into()
+        }
+
+        fn as_text_with(&self, context: RenderContext) -> Text<'_> {
+            if context.has_children && !context.is_opened {
+                format!("{}+", self.0).into()
+            } else {
+                self.as_text()
+            }
+        }
+    }
+
+    let items = vec![TreeItem::new(
+        Counted("a"),
+        vec![TreeItem::new_leaf(Counted("b"))],
+    )];
+    let mut state = TreeState::default();
+
+    let area = Rect::new(0, 0, 6, 2);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(Tree::new(items.clone()), area, &mut buffer, &mut state);
+    assert_eq!(buffer, Buffer::with_lines(vec!["\u{25b6} a+  ", "      "]));
+
+    state.open(vec![0]);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(Tree::new(items), area, &mut buffer, &mut state);
+    assert_eq!(buffer, Buffer::with_lines(vec!["\u{25bc} a   ", "    b "]));
+}
+
+#[test]
+fn drill_down_opens_unloaded_lazy_node() {
+    let items = vec![TreeItem::new("a", vec![TreeItem::new_lazy("b")])];
+    let mut state = TreeState::default();
+    assert_eq!(state.try_open(&items, vec![0, 0]), Ok(true));
+    state.close_all();
+
+    state.select_first();
+    assert_eq!(state.drill_down(&items), 1);
+    assert_eq!(state.selected(), [0, 0]);
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![0], vec![0, 0]]);
+}
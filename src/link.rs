@@ -0,0 +1,111 @@
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::TreeState;
+
+impl TreeState {
+    /// Link two nodes so opening or closing one of them opens or closes the other one too.
+    ///
+    /// Links are transitive: all nodes connected by links form a group which is expanded
+    /// together, like the same directory within mirrored before and after trees of one widget.
+    /// Linking a node to itself does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let mut state = TreeState::default();
+    /// state.link(vec![0, 1], vec![1, 1]);
+    /// state.link(vec![1, 1], vec![2, 1]);
+    ///
+    /// state.open(vec![2, 1]);
+    /// let mut opened = state.get_all_opened();
+    /// opened.sort();
+    /// assert_eq!(opened, [vec![0, 1], vec![1, 1], vec![2, 1]]);
+    ///
+    /// state.unlink(&[0, 1], &[1, 1]);
+    /// state.close(&[0, 1]);
+    /// assert_eq!(state.get_all_opened().len(), 2);
+    /// ```
+    pub fn link(&mut self, a: TreeIdentifierVec, b: TreeIdentifierVec) {
+        if a == b || a.is_empty() || b.is_empty() {
+            return;
+        }
+        self.links.entry(a.clone()).or_default().insert(b.clone());
+        self.links.entry(b).or_default().insert(a);
+    }
+
+    /// Remove the link between the two nodes.
+    /// Returns `true` when they were linked.
+    pub fn unlink(&mut self, a: TreeIdentifier, b: TreeIdentifier) -> bool {
+        let removed = self.remove_link(a, b);
+        self.remove_link(b, a);
+        removed
+    }
+
+    /// Remove all links of the node
+    pub fn unlink_all(&mut self, identifier: TreeIdentifier) {
+        for other in self.links.remove(identifier).unwrap_or_default() {
+            self.remove_link(&other, identifier);
+        }
+    }
+
+    /// Nodes directly linked to the node
+    pub fn links(&self, identifier: TreeIdentifier) -> Vec<TreeIdentifierVec> {
+        self.links
+            .get(identifier)
+            .map(|links| links.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn remove_link(&mut self, from: TreeIdentifier, to: TreeIdentifier) -> bool {
+        let links = match self.links.get_mut(from) {
+            Some(links) => links,
+            None => return false,
+        };
+        let removed = links.remove(to);
+        if links.is_empty() {
+            self.links.remove(from);
+        }
+        removed
+    }
+
+    /// All nodes connected to the node by links, without the node itself.
+    /// Every node is only visited once so cyclic links end.
+    pub(crate) fn linked_group(&self, identifier: TreeIdentifier) -> Vec<TreeIdentifierVec> {
+        if !self.links.contains_key(identifier) {
+            return Vec::new();
+        }
+        let mut group = vec![identifier.to_vec()];
+        let mut next = 0;
+        while let Some(current) = group.get(next) {
+            let linked = self.links.get(current).into_iter().flatten();
+            let unvisited = linked
+                .filter(|linked| !group.contains(linked))
+                .cloned()
+                .collect::<Vec<_>>();
+            group.extend(unvisited);
+            next += 1;
+        }
+        group.remove(0);
+        group
+    }
+}
+
+#[test]
+fn cyclic_links_end() {
+    let mut state = TreeState::default();
+    state.link(vec![0], vec![1]);
+    state.link(vec![1], vec![2]);
+    state.link(vec![2], vec![0]);
+    state.link(vec![3], vec![3]);
+
+    assert!(state.open(vec![1]));
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![0], vec![1], vec![2]]);
+
+    state.unlink_all(&[2]);
+    assert_eq!(state.links(&[0]), [vec![1]]);
+    assert!(state.close(&[2]));
+    assert_eq!(state.get_all_opened().len(), 2);
+    assert!(!state.unlink(&[3], &[3]));
+}
@@ -0,0 +1,136 @@
+use tui::style::Style;
+
+use crate::error::{resolve, resolve_mut, TreeError};
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{Tree, TreeItem, TreeState};
+
+/// Text of the placeholder row of children which are still loading
+pub(crate) const LOADING_PLACEHOLDER: &str = "Loading\u{2026}";
+
+impl TreeState {
+    /// Open the node without waiting for its children to be loaded.
+    ///
+    /// Until the children are passed to [`resolve_children`](Self::resolve_children), the
+    /// opened node shows a [placeholder row](Tree::loading_placeholder) instead.
+    /// Returns `true` when the children of the node have to be loaded, which is only the case
+    /// for [lazy](TreeItem::new_lazy) nodes not already loading.
+    /// Other nodes are opened right away like with [`open`](Self::open).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let mut items = vec![TreeItem::new_lazy("remote"), TreeItem::new_leaf("local")];
+    /// let mut state = TreeState::default();
+    /// assert_eq!(state.open_async(&items, vec![0]), Ok(true));
+    ///
+    /// let area = Rect::new(0, 0, 14, 3);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items.clone()).render(area, &mut buffer, &mut state);
+    /// let expected = Buffer::with_lines(vec![
+    ///     "▼ remote      ",
+    ///     "    Loading…  ",
+    ///     "  local       ",
+    /// ]);
+    /// assert_eq!(buffer, expected);
+    ///
+    /// // Later, when the children arrived
+    /// let children = vec![TreeItem::new_leaf("file")];
+    /// assert_eq!(state.resolve_children(&mut items, &[0], children), Ok(true));
+    /// assert_eq!(items[0].children().len(), 1);
+    /// ```
+    pub fn open_async<A>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: TreeIdentifierVec,
+    ) -> Result<bool, TreeError> {
+        let is_lazy = resolve(items, &identifier)?.lazy;
+        self.open(identifier.clone());
+        Ok(is_lazy && self.loading.insert(identifier))
+    }
+
+    /// Replace the placeholder of a node opened with [`open_async`](Self::open_async) by its
+    /// loaded children.
+    /// A node without any children is closed.
+    ///
+    /// Returns `false` without changing the items when the node is not loading anymore, for
+    /// example because its children were [unloaded](Self::unload_children) in the meantime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the identifier does not reference an existing node.
+    pub fn resolve_children<A>(
+        &mut self,
+        items: &mut [TreeItem<A>],
+        identifier: TreeIdentifier,
+        children: Vec<TreeItem<A>>,
+    ) -> Result<bool, TreeError> {
+        let item = resolve_mut(items, identifier)?;
+        if !self.loading.remove(identifier) {
+            return Ok(false);
+        }
+        *item.children = children;
+        item.lazy = false;
+        if item.children.is_empty() {
+            self.close(identifier);
+        }
+        self.invalidate_rows();
+        Ok(true)
+    }
+
+    /// Whether the node waits for its children passed to
+    /// [`resolve_children`](Self::resolve_children)
+    pub fn is_loading(&self, identifier: TreeIdentifier) -> bool {
+        self.loading.contains(identifier)
+    }
+
+    /// Whether the row of the node is followed by the placeholder of its loading children
+    pub(crate) fn shows_loading_placeholder(&self, identifier: TreeIdentifier) -> bool {
+        self.loading.contains(identifier) && self.opened.contains(identifier)
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Text and style of the placeholder row shown below nodes whose children are still loading,
+    /// see [`TreeState::open_async`].
+    /// Defaults to `Loading…`.
+    #[must_use]
+    pub const fn loading_placeholder(mut self, text: &'a str, style: Style) -> Self {
+        self.loading_placeholder = (text, style);
+        self
+    }
+}
+
+#[test]
+fn late_results_are_ignored_after_unload() {
+    let mut items = vec![TreeItem::new_lazy("a"), TreeItem::new("b", vec![])];
+    let mut state = TreeState::default();
+    assert_eq!(state.open_async(&items, vec![0]), Ok(true));
+    assert_eq!(state.open_async(&items, vec![0]), Ok(false));
+    assert_eq!(state.open_async(&items, vec![1]), Ok(false));
+    assert!(state.is_loading(&[0]));
+    assert!(state.audit(&items).iter().all(|issue| !matches!(
+        issue,
+        crate::StateIssue::OpenedLeaf(identifier) if identifier == &[0]
+    )));
+
+    state.unload_children(&mut items, &[0]).unwrap();
+    assert!(!state.is_loading(&[0]));
+    let children = vec![TreeItem::new_leaf("c")];
+    assert_eq!(
+        state.resolve_children(&mut items, &[0], children),
+        Ok(false)
+    );
+    assert!(items[0].children().is_empty());
+
+    assert_eq!(state.open_async(&items, vec![0]), Ok(true));
+    assert_eq!(state.resolve_children(&mut items, &[0], vec![]), Ok(true));
+    assert!(state.get_all_opened().iter().all(|opened| opened != &[0]));
+}
@@ -0,0 +1,89 @@
+use crate::{Children, TreeItem};
+
+impl<A> TreeItem<A> {
+    /// Convert the element of the item and all its descendants.
+    ///
+    /// The structure, styles, flags, keys and lazy children are kept, so identifiers and the
+    /// [`TreeState`](crate::TreeState) stay valid for the converted items.
+    /// A [`style_fn`](Self::style_fn) depends on the old element type and is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::text::Text;
+    /// # use tui_tree_widget::{TreeItem, TreeItemRender};
+    /// struct File {
+    ///     name: &'static str,
+    ///     size: u64,
+    /// }
+    ///
+    /// impl TreeItemRender for File {
+    ///     fn as_text(&self) -> Text<'_> {
+    ///         Text::raw(self.name)
+    ///     }
+    /// }
+    ///
+    /// let item = TreeItem::new("src", vec![TreeItem::new_leaf("lib.rs")]);
+    /// let files = item.map(|name| File { name, size: 4096 });
+    /// assert_eq!(files.children().len(), 1);
+    /// ```
+    pub fn map<B>(self, f: impl Fn(A) -> B) -> TreeItem<B> {
+        self.map_with(&f)
+    }
+
+    fn map_with<B, F: Fn(A) -> B>(mut self, f: &F) -> TreeItem<B> {
+        TreeItem {
+            elem: f(self.elem),
+            style: self.style,
+            children: Children(
+                std::mem::take(&mut *self.children)
+                    .into_iter()
+                    .map(|child| child.map_with(f))
+                    .collect(),
+            ),
+            flags: self.flags,
+            style_fn: None,
+            lazy: self.lazy,
+            id: self.id,
+        }
+    }
+
+    /// Like [`map`](Self::map) but keeps the item and converts references to the elements
+    pub fn map_ref<B>(&self, f: impl Fn(&A) -> B) -> TreeItem<B> {
+        self.map_ref_with(&f)
+    }
+
+    fn map_ref_with<B, F: Fn(&A) -> B>(&self, f: &F) -> TreeItem<B> {
+        TreeItem {
+            elem: f(&self.elem),
+            style: self.style,
+            children: Children(
+                self.children
+                    .iter()
+                    .map(|child| child.map_ref_with(f))
+                    .collect(),
+            ),
+            flags: self.flags.clone(),
+            style_fn: None,
+            lazy: self.lazy,
+            id: self.id,
+        }
+    }
+}
+
+#[test]
+fn map_keeps_structure() {
+    let items = crate::flatten::get_example_tree_items();
+    let lengths = items
+        .iter()
+        .map(|item| item.map_ref(|elem| elem.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(lengths[1].children[1].children[1].elem, 1);
+
+    let upper = items
+        .into_iter()
+        .map(|item| item.map(|elem| elem.to_uppercase()))
+        .collect::<Vec<_>>();
+    assert_eq!(upper[1].children[1].children[1].elem, "F");
+    assert_eq!(upper[1].children.len(), 3);
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use tui::style::Style;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::Tree;
+
+impl<'a, A> Tree<'a, A> {
+    /// Render the nodes of an app provided set like bookmarks or breakpoints with the style.
+    ///
+    /// The marked nodes are independent of the selection and of the [tags](crate::TreeState::tag)
+    /// kept in the [`TreeState`](crate::TreeState).
+    /// See [`marked_symbol`](Self::marked_symbol) for a gutter in front of the marked nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashSet;
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::style::{Color, Style};
+    /// # use tui::widgets::Widget;
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+    /// let breakpoints = HashSet::from([vec![1]]);
+    /// let style = Style::default().fg(Color::Red);
+    ///
+    /// let area = Rect::new(0, 0, 5, 2);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .marked(breakpoints, style)
+    ///     .marked_symbol("*")
+    ///     .render(area, &mut buffer);
+    ///
+    /// let mut expected = Buffer::with_lines(vec!["   a ", "*  b "]);
+    /// expected.set_style(Rect::new(0, 1, 5, 1), style);
+    /// assert_eq!(buffer, expected);
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn marked(mut self, identifiers: HashSet<TreeIdentifierVec>, style: Style) -> Self {
+        self.marked = Some((identifiers, style));
+        self
+    }
+
+    /// Reserve a gutter column in front of the indentation of every row and show the symbol in
+    /// it for the [marked](Self::marked) nodes
+    #[must_use]
+    pub const fn marked_symbol(mut self, symbol: &'a str) -> Self {
+        self.marked_symbol = Some(symbol);
+        self
+    }
+}
+
+#[test]
+fn marked_style_below_highlight() {
+    use tui::buffer::Buffer;
+    use tui::layout::Rect;
+    use tui::style::Color;
+    use tui::widgets::StatefulWidget;
+
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = crate::TreeState::default();
+    state.select(vec![0]);
+    let marked = Style::default().fg(Color::Red);
+    let highlight = Style::default().bg(Color::Blue);
+
+    let area = Rect::new(0, 0, 5, 2);
+    let mut buffer = Buffer::empty(area);
+    Tree::new(items)
+        .marked(HashSet::from([vec![0], vec![1]]), marked)
+        .highlight_style(highlight)
+        .render(area, &mut buffer, &mut state);
+
+    let mut expected = Buffer::with_lines(vec!["  a  ", "\u{25b6} b  "]);
+    expected.set_style(Rect::new(0, 0, 5, 2), marked);
+    expected.set_style(Rect::new(0, 0, 5, 1), highlight);
+    assert_eq!(buffer, expected);
+}
@@ -0,0 +1,145 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::TreeState;
+
+/// Counters of how a [`TreeState`] is used
+///
+/// Allows apps to display or record the performance of the widget and to verify the caches are
+/// effective.
+/// Disabled by default, see [`TreeState::set_metrics_enabled`].
+#[derive(Debug, Default)]
+pub struct TreeMetrics {
+    frames_rendered: u64,
+    /// Flattening only borrows the state
+    flattens: AtomicU64,
+    style_cache_hits: u64,
+    style_cache_misses: u64,
+    max_visible_rows: usize,
+}
+
+impl Clone for TreeMetrics {
+    fn clone(&self) -> Self {
+        Self {
+            frames_rendered: self.frames_rendered,
+            flattens: AtomicU64::new(self.flattens()),
+            style_cache_hits: self.style_cache_hits,
+            style_cache_misses: self.style_cache_misses,
+            max_visible_rows: self.max_visible_rows,
+        }
+    }
+}
+
+impl TreeMetrics {
+    pub const fn frames_rendered(&self) -> u64 {
+        self.frames_rendered
+    }
+
+    /// How often the visible rows were computed, by rendering as well as by navigating
+    pub fn flattens(&self) -> u64 {
+        self.flattens.load(Ordering::Relaxed)
+    }
+
+    /// How often the style of [`TreeItem::style_fn`](crate::TreeItem::style_fn) was cached
+    pub const fn style_cache_hits(&self) -> u64 {
+        self.style_cache_hits
+    }
+
+    /// How often the style of [`TreeItem::style_fn`](crate::TreeItem::style_fn) was computed
+    pub const fn style_cache_misses(&self) -> u64 {
+        self.style_cache_misses
+    }
+
+    /// Share of the styles taken from the cache, `None` before any style was needed
+    #[allow(clippy::cast_precision_loss)]
+    pub fn style_cache_hit_rate(&self) -> Option<f64> {
+        let total = self.style_cache_hits + self.style_cache_misses;
+        (total > 0).then(|| self.style_cache_hits as f64 / total as f64)
+    }
+
+    /// Most visible rows of the tree of any render
+    pub const fn max_visible_rows(&self) -> usize {
+        self.max_visible_rows
+    }
+}
+
+impl TreeState {
+    /// Start or stop collecting [`TreeMetrics`].
+    /// Stopping discards the collected metrics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+    /// let mut state = TreeState::default();
+    /// state.set_metrics_enabled(true);
+    ///
+    /// let area = Rect::new(0, 0, 10, 3);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items.clone()).render(area, &mut buffer, &mut state);
+    /// state.key_down(&items);
+    ///
+    /// let metrics = state.metrics().unwrap();
+    /// assert_eq!(metrics.frames_rendered(), 1);
+    /// assert_eq!(metrics.flattens(), 2);
+    /// assert_eq!(metrics.max_visible_rows(), 2);
+    /// ```
+    pub fn set_metrics_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.metrics = None;
+        } else if self.metrics.is_none() {
+            self.metrics = Some(TreeMetrics::default());
+        }
+    }
+
+    /// Metrics collected since they were enabled with
+    /// [`set_metrics_enabled`](Self::set_metrics_enabled)
+    pub const fn metrics(&self) -> Option<&TreeMetrics> {
+        self.metrics.as_ref()
+    }
+
+    pub(crate) fn record_frame(&mut self, visible_rows: usize) {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.frames_rendered += 1;
+            metrics.max_visible_rows = metrics.max_visible_rows.max(visible_rows);
+        }
+    }
+
+    pub(crate) fn record_flatten(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.flattens.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_style_cache(&mut self, hit: bool) {
+        if let Some(metrics) = &mut self.metrics {
+            if hit {
+                metrics.style_cache_hits += 1;
+            } else {
+                metrics.style_cache_misses += 1;
+            }
+        }
+    }
+}
+
+#[test]
+fn style_cache_hit_rate() {
+    let mut state = TreeState::default();
+    state.cached_style(&[0], 0, Default::default);
+    state.set_metrics_enabled(true);
+    assert_eq!(state.metrics().unwrap().style_cache_hit_rate(), None);
+
+    state.cached_style(&[0], 0, Default::default);
+    state.cached_style(&[0], 0, Default::default);
+    state.cached_style(&[1], 0, Default::default);
+    state.cached_style(&[1], 0, Default::default);
+    let metrics = state.metrics().unwrap();
+    assert_eq!(metrics.style_cache_hits(), 3);
+    assert_eq!(metrics.style_cache_hit_rate(), Some(0.75));
+
+    state.set_metrics_enabled(false);
+    assert!(state.metrics().is_none());
+}
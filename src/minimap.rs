@@ -0,0 +1,159 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::widgets::{Block, StatefulWidget, Widget};
+
+use crate::flatten::flatten_state;
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+/// Compressed overview of a [`Tree`](crate::Tree) sharing its [`TreeState`]
+///
+/// Every line of the minimap represents one or more visible rows of the tree.
+/// Lines containing the selected item or opened nodes are marked, which helps with orientation
+/// in very large trees.
+///
+/// # Example
+///
+/// ```
+/// # use tui::backend::TestBackend;
+/// # use tui::layout::{Constraint, Direction, Layout};
+/// # use tui::Terminal;
+/// # use tui_tree_widget::{Tree, TreeItem, TreeMinimap, TreeState};
+/// # fn main() -> std::io::Result<()> {
+/// #     let mut terminal = Terminal::new(TestBackend::new(32, 32)).unwrap();
+/// let mut state = TreeState::default();
+/// let items = vec![TreeItem::new_leaf("leaf")];
+///
+/// terminal.draw(|f| {
+///     let chunks = Layout::default()
+///         .direction(Direction::Horizontal)
+///         .constraints([Constraint::Min(0), Constraint::Length(1)])
+///         .split(f.size());
+///
+///     f.render_stateful_widget(Tree::new(items.clone()), chunks[0], &mut state);
+///     f.render_stateful_widget(TreeMinimap::new(&items), chunks[1], &mut state);
+/// })?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreeMinimap<'a, A> {
+    block: Option<Block<'a>>,
+    items: &'a [TreeItem<A>],
+    /// Style used as a base style for the widget
+    style: Style,
+    /// Style used for the line containing the selected item
+    highlight_style: Style,
+    /// Symbol for lines containing only closed nodes or leafs
+    symbol: &'a str,
+    /// Symbol for lines containing an opened node
+    opened_symbol: &'a str,
+    /// Symbol for the line containing the selected item
+    selected_symbol: &'a str,
+}
+
+impl<'a, A> TreeMinimap<'a, A> {
+    pub fn new(items: &'a [TreeItem<A>]) -> Self {
+        Self {
+            block: None,
+            items,
+            style: Style::default(),
+            highlight_style: Style::default(),
+            symbol: "\u{2591}",          // Light shade
+            opened_symbol: "\u{2592}",   // Medium shade
+            selected_symbol: "\u{2588}", // Full block
+        }
+    }
+
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn symbols(mut self, symbol: &'a str, opened: &'a str, selected: &'a str) -> Self {
+        self.symbol = symbol;
+        self.opened_symbol = opened;
+        self.selected_symbol = selected;
+        self
+    }
+}
+
+impl<'a, A: TreeItemRender> StatefulWidget for TreeMinimap<'a, A> {
+    type State = TreeState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        buf.set_style(area, self.style);
+
+        // Get the inner area inside a possible block, otherwise use the full area
+        let area = self.block.map_or(area, |b| {
+            let inner_area = b.inner(area);
+            b.render(area, buf);
+            inner_area
+        });
+
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        let visible = flatten_state(state, self.items);
+        if visible.is_empty() {
+            return;
+        }
+
+        let available_height = area.height as usize;
+        let rows_per_line = (visible.len() + available_height - 1) / available_height;
+
+        #[allow(clippy::cast_possible_truncation)]
+        for (line, chunk) in visible.chunks(rows_per_line).enumerate() {
+            let y = area.top() + line as u16;
+            let (symbol, style) = if chunk.iter().any(|o| o.identifier == state.selected) {
+                (self.selected_symbol, self.style.patch(self.highlight_style))
+            } else if chunk.iter().any(|o| state.opened.contains(&o.identifier)) {
+                (self.opened_symbol, self.style)
+            } else {
+                (self.symbol, self.style)
+            };
+            let string = symbol.repeat(area.width as usize);
+            buf.set_stringn(area.left(), y, string, area.width as usize, style);
+        }
+    }
+}
+
+impl<'a, A: TreeItemRender> Widget for TreeMinimap<'a, A> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = TreeState::default();
+        StatefulWidget::render(self, area, buf, &mut state);
+    }
+}
+
+#[test]
+fn minimap_compresses_rows() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 1]);
+
+    let area = Rect::new(0, 0, 1, 4);
+    let mut buffer = Buffer::empty(area);
+    StatefulWidget::render(TreeMinimap::new(&items), area, &mut buffer, &mut state);
+
+    // 8 visible rows on 4 lines: [a b] [c d] [e f] [g h]
+    let expected = Buffer::with_lines(vec!["\u{2592}", "\u{2592}", "\u{2588}", "\u{2591}"]);
+    assert_eq!(buffer, expected);
+}
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use tui::style::Style;
+
+use crate::flatten::flatten_state;
+use crate::identifier::TreeIdentifierVec;
+use crate::{Tree, TreeItem, TreeItemRender, TreeState};
+
+impl TreeState {
+    /// Add the selected node to the multi selection or remove it when it is already part of it.
+    /// Returns `true` when the node is part of the multi selection afterwards.
+    ///
+    /// The multi selection is independent of the [`selected`](Self::selected) node and meant
+    /// for batch operations on several nodes.
+    /// The [`Tree`] shows it with its [`multi_highlight_style`](Tree::multi_highlight_style).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let mut state = TreeState::default();
+    /// state.select(vec![0]);
+    /// assert!(state.toggle_multi_select());
+    /// state.select(vec![2]);
+    /// assert!(state.toggle_multi_select());
+    /// assert!(state.is_multi_selected(&[0]));
+    ///
+    /// assert!(!state.toggle_multi_select());
+    /// assert!(!state.is_multi_selected(&[2]));
+    /// ```
+    pub fn toggle_multi_select(&mut self) -> bool {
+        if self.selected.is_empty() {
+            return false;
+        }
+        if self.selected_multi.remove(&self.selected) {
+            false
+        } else {
+            self.selected_multi.insert(self.selected.clone())
+        }
+    }
+
+    /// Add all visible nodes from `from` to `to` (both inclusive) to the multi selection like a
+    /// shift click.
+    /// The order of `from` and `to` does not matter.
+    ///
+    /// Returns `false` without changing the multi selection when either node is not visible.
+    pub fn select_range<A: TreeItemRender>(
+        &mut self,
+        items: &[TreeItem<A>],
+        from: &[usize],
+        to: &[usize],
+    ) -> bool {
+        let visible = flatten_state(self, items);
+        let position = |identifier: &[usize]| {
+            visible
+                .iter()
+                .position(|o| o.identifier.as_slice() == identifier)
+        };
+        let (from, to) = match (position(from), position(to)) {
+            (Some(from), Some(to)) => (from.min(to), from.max(to)),
+            _ => return false,
+        };
+        self.selected_multi
+            .extend(visible[from..=to].iter().map(|o| o.identifier.clone()));
+        true
+    }
+
+    pub fn clear_multi_selection(&mut self) {
+        self.selected_multi.clear();
+    }
+
+    pub fn is_multi_selected(&self, identifier: &[usize]) -> bool {
+        self.selected_multi.contains(identifier)
+    }
+
+    /// All nodes of the multi selection in an unspecified order
+    pub fn multi_selected(&self) -> &HashSet<TreeIdentifierVec> {
+        &self.selected_multi
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Style of the rows within the [multi selection](TreeState::toggle_multi_select).
+    /// The [`highlight_style`](Self::highlight_style) of the selected row is applied on top.
+    #[must_use]
+    pub const fn multi_highlight_style(mut self, style: Style) -> Self {
+        self.multi_highlight_style = Some(style);
+        self
+    }
+}
+
+#[test]
+fn range_in_visible_order() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    assert!(state.select_range(&items, &[2], &[1, 0]));
+    let mut selected = state.multi_selected().iter().cloned().collect::<Vec<_>>();
+    selected.sort();
+    assert_eq!(selected, [vec![1, 0], vec![1, 1], vec![1, 2], vec![2]]);
+
+    assert!(!state.select_range(&items, &[0], &[1, 1, 0]));
+    state.clear_multi_selection();
+    assert!(state.multi_selected().is_empty());
+}
@@ -0,0 +1,134 @@
+use tui::text::{Span, Spans, Text};
+
+use crate::Tree;
+
+/// Numbers of the levels of the outline numbers shown by [`Tree::numbering`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberingStyle {
+    /// `1.2.3`
+    Decimal,
+    /// `a.b.c`, continued with `aa` after `z`
+    Alpha,
+    /// `i.ii.iii`
+    Roman,
+}
+
+impl NumberingStyle {
+    /// Number of the child at the index within its parent
+    fn number(self, index: usize) -> String {
+        let number = index + 1;
+        match self {
+            Self::Decimal => number.to_string(),
+            Self::Alpha => alpha(number),
+            Self::Roman => roman(number),
+        }
+    }
+
+    /// Outline number of the node like `1.2.3`
+    pub fn outline(self, identifier: &[usize]) -> String {
+        identifier
+            .iter()
+            .map(|index| self.number(*index))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+fn alpha(mut number: usize) -> String {
+    let mut result = Vec::new();
+    while number > 0 {
+        number -= 1;
+        result.push(b'a' + (number % 26) as u8);
+        number /= 26;
+    }
+    result.reverse();
+    String::from_utf8(result).unwrap_or_default()
+}
+
+fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            result += numeral;
+            number -= value;
+        }
+    }
+    result
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Show the outline number derived from the position of each node in front of its label,
+    /// like in a document outline or legal text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{NumberingStyle, Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    ///
+    /// let area = Rect::new(0, 0, 10, 2);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .numbering(NumberingStyle::Decimal)
+    ///     .render(area, &mut buffer, &mut state);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["▼ 1 a     ", "    1.1 b "]));
+    /// ```
+    #[must_use]
+    pub const fn numbering(mut self, style: NumberingStyle) -> Self {
+        self.numbering = Some(style);
+        self
+    }
+}
+
+/// Outline number of the node followed by the space to the label
+pub(crate) fn prefix(style: NumberingStyle, identifier: &[usize]) -> String {
+    style.outline(identifier) + " "
+}
+
+/// Prepend the outline number of the node to the first line of the text
+pub(crate) fn numbered<'t>(
+    text: Text<'t>,
+    style: NumberingStyle,
+    identifier: &[usize],
+) -> Text<'t> {
+    let mut lines = text.lines;
+    let number = Span::raw(prefix(style, identifier));
+    match lines.first_mut() {
+        Some(first) => first.0.insert(0, number),
+        None => lines.push(Spans::from(number)),
+    }
+    Text::from(lines)
+}
+
+#[test]
+fn outline_numbers() {
+    assert_eq!(NumberingStyle::Decimal.outline(&[0, 1, 11]), "1.2.12");
+    assert_eq!(
+        NumberingStyle::Alpha.outline(&[0, 25, 26, 701]),
+        "a.z.aa.zz"
+    );
+    assert_eq!(
+        NumberingStyle::Roman.outline(&[3, 8, 1993]),
+        "iv.ix.mcmxciv"
+    );
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::identifier::TreeIdentifier;
+use crate::TreeState;
+
+/// Gets notified about the interactions with a tree, see [`TreeState::set_observer`]
+///
+/// Allows attaching sounds, logging or analytics without wrapping every call of the
+/// [`TreeState`].
+/// Every method does nothing by default.
+/// Observers are shared between clones of the state, so they only get `&self` and need interior
+/// mutability to record something.
+pub trait TreeObserver: Send + Sync {
+    /// The node was opened by [`TreeState::open`] or a method using it
+    fn on_open(&self, _identifier: TreeIdentifier) {}
+
+    /// The node was closed by [`TreeState::close`] or a method using it
+    fn on_close(&self, _identifier: TreeIdentifier) {}
+
+    /// Another node was selected by [`TreeState::select`] or a method using it
+    fn on_select(&self, _identifier: TreeIdentifier) {}
+
+    /// The selected node was activated by [`TreeState::activate_selected`]
+    fn on_activate(&self, _identifier: TreeIdentifier) {}
+}
+
+/// The [`TreeObserver`] of a [`TreeState`]
+#[derive(Clone)]
+pub(crate) struct Observer(Arc<dyn TreeObserver>);
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Observer")
+    }
+}
+
+impl TreeState {
+    /// Notify the observer about interactions like opening, closing and selecting nodes.
+    /// `None` removes the observer.
+    ///
+    /// Replacing the opened nodes or the selection in bulk like restoring a
+    /// [session](Self::import_session) or remapping them after the items changed is not
+    /// notified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// # use std::sync::Arc;
+    /// # use tui_tree_widget::{TreeIdentifier, TreeObserver, TreeState};
+    /// #[derive(Default)]
+    /// struct OpenCounter(AtomicUsize);
+    ///
+    /// impl TreeObserver for OpenCounter {
+    ///     fn on_open(&self, _identifier: TreeIdentifier) {
+    ///         self.0.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// let counter = Arc::new(OpenCounter::default());
+    /// let mut state = TreeState::default();
+    /// state.set_observer(Some(counter.clone()));
+    ///
+    /// state.open(vec![0]);
+    /// state.open(vec![0]);
+    /// state.toggle(vec![1]);
+    /// assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+    /// ```
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn TreeObserver>>) {
+        self.observer = observer.map(Observer);
+    }
+
+    /// Activate the selected node like pressing enter on it.
+    /// Only notifies the [observer](Self::set_observer), the app decides what activating does.
+    pub fn activate_selected(&self) {
+        if !self.selected.is_empty() {
+            self.notify(|observer| observer.on_activate(&self.selected));
+        }
+    }
+
+    pub(crate) fn notify(&self, event: impl FnOnce(&dyn TreeObserver)) {
+        if let Some(Observer(observer)) = &self.observer {
+            event(observer.as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+struct Recorder(std::sync::Mutex<Vec<String>>);
+
+#[cfg(test)]
+impl TreeObserver for Recorder {
+    fn on_open(&self, identifier: TreeIdentifier) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("open {:?}", identifier));
+    }
+
+    fn on_close(&self, identifier: TreeIdentifier) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("close {:?}", identifier));
+    }
+
+    fn on_select(&self, identifier: TreeIdentifier) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("select {:?}", identifier));
+    }
+
+    fn on_activate(&self, identifier: TreeIdentifier) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("activate {:?}", identifier));
+    }
+}
+
+#[test]
+fn observer_is_notified_about_changes_only() {
+    let items = crate::flatten::get_example_tree_items();
+    let recorder = Arc::new(Recorder::default());
+    let mut state = TreeState::default();
+    state.set_observer(Some(recorder.clone()));
+
+    state.activate_selected();
+    state.select_first();
+    state.key_down(&items);
+    state.key_right();
+    state.key_right();
+    state.activate_selected();
+    state.select(vec![1]);
+    state.key_left();
+    state.open(vec![1]);
+    state.close_all();
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        [
+            "select [0]",
+            "select [1]",
+            "open [1]",
+            "activate [1]",
+            "close [1]",
+            "open [1]",
+            "close [1]"
+        ]
+    );
+}
@@ -0,0 +1,248 @@
+use std::collections::VecDeque;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::{error, TreeItem, TreeState};
+
+/// Outcome of opening many nodes at once with a budget like
+/// [`TreeState::open_all_within`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenAllResult {
+    /// Every node with children is opened, `opened` of them were closed before
+    Complete { opened: usize },
+    /// The budget ran out. `remaining` nodes with children are still closed.
+    Truncated { opened: usize, remaining: usize },
+}
+
+impl TreeState {
+    /// Amount of closed nodes with children including the given nodes themselves.
+    /// Walks the tree with an explicit stack so deep trees can not overflow the stack.
+    fn count_closed_parents<A>(&self, mut stack: Vec<(TreeIdentifierVec, &TreeItem<A>)>) -> usize {
+        let mut count = 0;
+        while let Some((identifier, item)) = stack.pop() {
+            if !item.has_children() {
+                continue;
+            }
+            for (index, child) in item.children.iter().enumerate() {
+                let mut child_identifier = identifier.clone();
+                child_identifier.push(index);
+                stack.push((child_identifier, child));
+            }
+            if !self.opened.contains(&identifier) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Open every node with children, the counterpart to [`close_all`](Self::close_all).
+    ///
+    /// See [`open_all_within`](Self::open_all_within) to limit the amount of visible rows of
+    /// huge trees.
+    /// Returns the amount of nodes which were closed before.
+    pub fn open_all<A>(&mut self, items: &[TreeItem<A>]) -> usize {
+        let roots = (0..items.len()).map(|index| (vec![index], &items[index], 0));
+        self.open_depth_first(roots.collect(), None)
+    }
+
+    /// Open the node and its descendants with children up to `depth_limit` levels below the
+    /// node.
+    /// `Some(0)` only opens the node itself, `None` opens all descendants.
+    ///
+    /// Nothing is opened when the node does not exist.
+    /// Returns the amount of nodes which were closed before.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "a",
+    ///     vec![TreeItem::new("b", vec![TreeItem::new("c", vec![TreeItem::new_leaf("d")])])],
+    /// )];
+    /// let mut state = TreeState::default();
+    ///
+    /// assert_eq!(state.open_recursive(vec![0], &items, Some(1)), 2);
+    /// assert!(!state.get_all_opened().contains(&vec![0, 0, 0]));
+    /// assert_eq!(state.open_all(&items), 1);
+    /// ```
+    pub fn open_recursive<A>(
+        &mut self,
+        identifier: TreeIdentifierVec,
+        items: &[TreeItem<A>],
+        depth_limit: Option<usize>,
+    ) -> usize {
+        match error::resolve(items, &identifier) {
+            Ok(item) => self.open_depth_first(vec![(identifier, item, 0)], depth_limit),
+            Err(_) => 0,
+        }
+    }
+
+    fn open_depth_first<A>(
+        &mut self,
+        mut stack: Vec<(TreeIdentifierVec, &TreeItem<A>, usize)>,
+        depth_limit: Option<usize>,
+    ) -> usize {
+        let mut opened = 0;
+        while let Some((identifier, item, depth)) = stack.pop() {
+            if !item.has_children() {
+                continue;
+            }
+            if depth_limit.map_or(true, |limit| depth < limit) {
+                for (index, child) in item.children.iter().enumerate() {
+                    let mut child_identifier = identifier.clone();
+                    child_identifier.push(index);
+                    stack.push((child_identifier, child, depth + 1));
+                }
+            }
+            if self.open(identifier) {
+                opened += 1;
+            }
+        }
+        opened
+    }
+
+    /// Open every node with children as long as at most `budget` rows become visible.
+    ///
+    /// Opening all nodes of a huge tree would make every render flatten all of them.
+    /// Nodes are opened level by level so the budget is spent on the upper levels first.
+    /// Apps can warn about a [`OpenAllResult::Truncated`] instead of freezing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{OpenAllResult, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "a",
+    ///     vec![TreeItem::new("b", vec![TreeItem::new_leaf("c")])],
+    /// )];
+    /// let mut state = TreeState::default();
+    ///
+    /// let result = state.open_all_within(&items, 1);
+    /// assert_eq!(result, OpenAllResult::Truncated { opened: 1, remaining: 1 });
+    /// assert_eq!(state.get_all_opened(), [vec![0]]);
+    /// ```
+    pub fn open_all_within<A>(&mut self, items: &[TreeItem<A>], budget: usize) -> OpenAllResult {
+        let roots = (0..items.len()).map(|index| (vec![index], &items[index]));
+        self.open_level_order(roots.collect(), budget)
+    }
+
+    /// Open the node and all its descendants with children as long as at most `budget` rows
+    /// become visible.
+    /// See [`open_all_within`](Self::open_all_within).
+    ///
+    /// Nothing is opened when the node does not exist.
+    pub fn open_recursive_within<A>(
+        &mut self,
+        identifier: TreeIdentifierVec,
+        items: &[TreeItem<A>],
+        budget: usize,
+    ) -> OpenAllResult {
+        match error::resolve(items, &identifier) {
+            Ok(item) => self.open_level_order(VecDeque::from(vec![(identifier, item)]), budget),
+            Err(_) => OpenAllResult::Complete { opened: 0 },
+        }
+    }
+
+    fn open_level_order<A>(
+        &mut self,
+        mut queue: VecDeque<(TreeIdentifierVec, &TreeItem<A>)>,
+        budget: usize,
+    ) -> OpenAllResult {
+        let mut remaining_budget = budget;
+        let mut opened = 0;
+        while let Some((identifier, item)) = queue.pop_front() {
+            if !item.has_children() {
+                continue;
+            }
+            if item.children.len() > remaining_budget {
+                queue.push_front((identifier, item));
+                let remaining = self.count_closed_parents(queue.into_iter().collect());
+                return if remaining == 0 {
+                    OpenAllResult::Complete { opened }
+                } else {
+                    OpenAllResult::Truncated { opened, remaining }
+                };
+            }
+            remaining_budget -= item.children.len();
+            for (index, child) in item.children.iter().enumerate() {
+                let mut child_identifier = identifier.clone();
+                child_identifier.push(index);
+                queue.push_back((child_identifier, child));
+            }
+            if self.open(identifier) {
+                opened += 1;
+            }
+        }
+        OpenAllResult::Complete { opened }
+    }
+}
+
+#[test]
+fn open_all_within_budget() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(
+        state.open_all_within(&items, 3),
+        OpenAllResult::Truncated {
+            opened: 1,
+            remaining: 1
+        }
+    );
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+
+    state.close_all();
+    assert_eq!(
+        state.open_all_within(&items, 5),
+        OpenAllResult::Complete { opened: 2 }
+    );
+    // Already opened nodes are neither counted as opened nor as remaining
+    assert_eq!(
+        state.open_recursive_within(vec![1, 1], &items, 1),
+        OpenAllResult::Complete { opened: 0 }
+    );
+    state.close(&[1, 1]);
+    assert_eq!(
+        state.open_all_within(&items, 5),
+        OpenAllResult::Complete { opened: 1 }
+    );
+    state.close(&[1, 1]);
+    assert_eq!(
+        state.open_all_within(&items, 3),
+        OpenAllResult::Truncated {
+            opened: 0,
+            remaining: 1
+        }
+    );
+    assert_eq!(
+        state.open_recursive_within(vec![7], &items, 10),
+        OpenAllResult::Complete { opened: 0 }
+    );
+}
+
+#[test]
+fn open_all_opens_every_parent() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.open_recursive(vec![1], &items, Some(0)), 1);
+    assert_eq!(state.open_all(&items), 1);
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+    assert_eq!(state.open_recursive(vec![7], &items, None), 0);
+}
+
+#[test]
+fn open_all_opens_unloaded_lazy_nodes() {
+    let items = vec![
+        TreeItem::new("a", vec![TreeItem::new_lazy("b")]),
+        TreeItem::new_lazy("c"),
+    ];
+    let mut state = TreeState::default();
+    assert_eq!(state.open_all(&items), 3);
+
+    state.close_all();
+    assert_eq!(
+        state.open_all_within(&items, 1),
+        OpenAllResult::Complete { opened: 3 }
+    );
+}
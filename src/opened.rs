@@ -0,0 +1,304 @@
+use std::collections::{BTreeSet, HashMap};
+use std::iter::FromIterator;
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Opened children of a single parent
+#[derive(Debug, Clone)]
+enum Children {
+    /// Few opened children compared to their indices
+    Sparse(BTreeSet<usize>),
+    /// One bit per child index and the amount of set bits
+    Bits { words: Vec<u64>, len: usize },
+}
+
+impl Children {
+    fn contains(&self, index: usize) -> bool {
+        match self {
+            Self::Sparse(set) => set.contains(&index),
+            Self::Bits { words, .. } => words
+                .get(index / WORD_BITS)
+                .map_or(false, |word| word & (1 << (index % WORD_BITS)) != 0),
+        }
+    }
+
+    /// Switches to bits once they need no more words than there are entries.
+    /// Bits switch back when they need more than twice the words, so alternately inserting and
+    /// removing at the threshold does not convert every time.
+    fn insert(&mut self, index: usize) -> bool {
+        match self {
+            Self::Sparse(set) => {
+                if !set.insert(index) {
+                    return false;
+                }
+                self.switch_to_bits_when_dense();
+                true
+            }
+            Self::Bits { words, len } => {
+                let word = index / WORD_BITS;
+                if word >= words.len() {
+                    if word >= 2 * (*len + 1) {
+                        let mut set = self.iter().collect::<BTreeSet<_>>();
+                        set.insert(index);
+                        *self = Self::Sparse(set);
+                        return true;
+                    }
+                    words.resize(word + 1, 0);
+                }
+                let bit = 1 << (index % WORD_BITS);
+                if words[word] & bit != 0 {
+                    return false;
+                }
+                words[word] |= bit;
+                *len += 1;
+                true
+            }
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> bool {
+        match self {
+            Self::Sparse(set) => {
+                if !set.remove(&index) {
+                    return false;
+                }
+                // Removing the largest index might leave fewer words needed
+                self.switch_to_bits_when_dense();
+                true
+            }
+            Self::Bits { words, len } => {
+                let bit = 1 << (index % WORD_BITS);
+                match words.get_mut(index / WORD_BITS) {
+                    Some(word) if *word & bit != 0 => *word &= !bit,
+                    _ => return false,
+                }
+                *len -= 1;
+                // Every trailing word is popped once after it was added
+                while words.last() == Some(&0) {
+                    words.pop();
+                }
+                if words.len() > 2 * *len {
+                    *self = Self::Sparse(self.iter().collect());
+                }
+                true
+            }
+        }
+    }
+
+    fn switch_to_bits_when_dense(&mut self) {
+        if let Self::Sparse(set) = self {
+            let max = set.iter().next_back().copied().unwrap_or_default();
+            if max / WORD_BITS < set.len() {
+                let mut words = vec![0; max / WORD_BITS + 1];
+                for index in set.iter() {
+                    words[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+                }
+                let len = set.len();
+                *self = Self::Bits { words, len };
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Sparse(set) => set.is_empty(),
+            Self::Bits { len, .. } => *len == 0,
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        match self {
+            Self::Sparse(set) => Box::new(set.iter().copied()),
+            Self::Bits { words, .. } => {
+                Box::new(words.iter().enumerate().flat_map(|(word, bits)| {
+                    (0..WORD_BITS)
+                        .filter(move |bit| bits & (1 << bit) != 0)
+                        .map(move |bit| word * WORD_BITS + bit)
+                }))
+            }
+        }
+    }
+}
+
+/// Set of opened identifiers stored per parent.
+///
+/// Parents with many opened children (like after opening a whole level) store a bit per child
+/// instead of a full identifier per child which keeps the memory bounded.
+/// Parents with only a few opened children fall back to a set of their indices.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OpenedSet {
+    parents: HashMap<TreeIdentifierVec, Children>,
+}
+
+impl OpenedSet {
+    pub fn contains(&self, identifier: TreeIdentifier) -> bool {
+        match identifier.split_last() {
+            Some((index, parent)) => self
+                .parents
+                .get(parent)
+                .map_or(false, |children| children.contains(*index)),
+            None => false,
+        }
+    }
+
+    pub fn insert(&mut self, identifier: TreeIdentifierVec) -> bool {
+        let mut parent = identifier;
+        let index = match parent.pop() {
+            Some(index) => index,
+            None => return false,
+        };
+        self.parents
+            .entry(parent)
+            .or_insert_with(|| Children::Sparse(BTreeSet::new()))
+            .insert(index)
+    }
+
+    pub fn remove(&mut self, identifier: TreeIdentifier) -> bool {
+        let (index, parent) = match identifier.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        let children = match self.parents.get_mut(parent) {
+            Some(children) => children,
+            None => return false,
+        };
+        let removed = children.remove(*index);
+        if children.is_empty() {
+            self.parents.remove(parent);
+        }
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.parents.clear();
+    }
+
+    /// Indices of the opened children of the parent in ascending order
+    pub fn children(&self, parent: TreeIdentifier) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .get(parent)
+            .into_iter()
+            .flat_map(Children::iter)
+    }
+
+    /// All opened identifiers in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = TreeIdentifierVec> + '_ {
+        self.parents.iter().flat_map(|(parent, children)| {
+            children.iter().map(move |index| {
+                let mut identifier = parent.clone();
+                identifier.push(index);
+                identifier
+            })
+        })
+    }
+}
+
+impl FromIterator<TreeIdentifierVec> for OpenedSet {
+    fn from_iter<I: IntoIterator<Item = TreeIdentifierVec>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for identifier in iter {
+            set.insert(identifier);
+        }
+        set
+    }
+}
+
+impl PartialEq for OpenedSet {
+    /// Equal when the same identifiers are opened regardless of how they are stored
+    fn eq(&self, other: &Self) -> bool {
+        self.parents.len() == other.parents.len()
+            && self.parents.iter().all(|(parent, children)| {
+                other
+                    .parents
+                    .get(parent)
+                    .map_or(false, |other| children.iter().eq(other.iter()))
+            })
+    }
+}
+
+impl Eq for OpenedSet {}
+
+#[test]
+fn whole_level_is_stored_as_bits() {
+    let mut set = (0..200).map(|index| vec![3, index]).collect::<OpenedSet>();
+    assert!(matches!(
+        set.parents.get(&vec![3]),
+        Some(Children::Bits { .. })
+    ));
+    assert!(set.contains(&[3, 199]));
+    assert!(!set.contains(&[3, 200]));
+    assert!(!set.contains(&[3]));
+
+    assert!(set.remove(&[3, 42]));
+    assert!(!set.remove(&[3, 42]));
+    assert!(!set.contains(&[3, 42]));
+    assert_eq!(set.iter().count(), 199);
+}
+
+#[test]
+fn sparse_and_bits_are_equal() {
+    let sparse = [vec![0], vec![1, 500]]
+        .iter()
+        .cloned()
+        .collect::<OpenedSet>();
+    let mut bits = (0..100).map(|index| vec![1, index]).collect::<OpenedSet>();
+    bits.insert(vec![1, 500]);
+    bits.insert(vec![0]);
+    for index in 0..100 {
+        bits.remove(&[1, index]);
+    }
+    assert!(matches!(
+        bits.parents.get(&vec![1]),
+        Some(Children::Sparse(_))
+    ));
+    assert_eq!(sparse, bits);
+}
+
+#[test]
+fn density_decides_between_bits_and_sparse() {
+    let mut set = (0..4).map(|index| vec![index]).collect::<OpenedSet>();
+    assert!(
+        matches!(set.parents.get(&vec![]), Some(Children::Bits { words, .. }) if words.len() == 1)
+    );
+
+    // A single far index does not grow the words
+    set.insert(vec![1_000_000]);
+    assert!(matches!(
+        set.parents.get(&vec![]),
+        Some(Children::Sparse(_))
+    ));
+    assert!(set.remove(&[1_000_000]));
+    assert!(matches!(
+        set.parents.get(&vec![]),
+        Some(Children::Bits { .. })
+    ));
+
+    let mut set = (0..200).map(|index| vec![index]).collect::<OpenedSet>();
+    for index in 0..199 {
+        set.remove(&[index]);
+    }
+    assert!(matches!(
+        set.parents.get(&vec![]),
+        Some(Children::Sparse(_))
+    ));
+    assert_eq!(set.iter().collect::<Vec<_>>(), [vec![199]]);
+
+    // Alternating at the threshold keeps the bits
+    let mut set = [vec![0], vec![64]].iter().cloned().collect::<OpenedSet>();
+    assert!(matches!(
+        set.parents.get(&vec![]),
+        Some(Children::Bits { len: 2, .. })
+    ));
+    set.remove(&[0]);
+    assert!(matches!(
+        set.parents.get(&vec![]),
+        Some(Children::Bits { len: 1, .. })
+    ));
+    set.insert(vec![0]);
+    assert!(matches!(
+        set.parents.get(&vec![]),
+        Some(Children::Bits { len: 2, .. })
+    ));
+}
@@ -0,0 +1,106 @@
+use crate::flatten::flatten_state;
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+impl TreeState {
+    /// Move the selection down by the amount of visible rows.
+    /// Stops at the last row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = (0..100).map(|_| TreeItem::new_leaf("row")).collect::<Vec<_>>();
+    /// let mut state = TreeState::default();
+    /// state.select_first();
+    ///
+    /// state.key_page_down(&items, 30);
+    /// assert_eq!(state.selected(), [30]);
+    /// state.key_page_down(&items, 80);
+    /// assert_eq!(state.selected(), [99]);
+    /// state.key_page_up(&items, 30);
+    /// assert_eq!(state.selected(), [69]);
+    /// ```
+    pub fn key_page_down<A: TreeItemRender>(&mut self, items: &[TreeItem<A>], page_size: usize) {
+        self.select_relative_rows(items, |index| index.saturating_add(page_size));
+    }
+
+    /// Move the selection up by the amount of visible rows.
+    /// Stops at the first row.
+    pub fn key_page_up<A: TreeItemRender>(&mut self, items: &[TreeItem<A>], page_size: usize) {
+        self.select_relative_rows(items, |index| index.saturating_sub(page_size));
+    }
+
+    /// Move the selection down by half the rows of the last render
+    pub fn key_half_page_down<A: TreeItemRender>(&mut self, items: &[TreeItem<A>]) {
+        let half = (self.scroll_metrics.viewport_rows / 2).max(1);
+        self.key_page_down(items, half);
+    }
+
+    /// Move the selection up by half the rows of the last render
+    pub fn key_half_page_up<A: TreeItemRender>(&mut self, items: &[TreeItem<A>]) {
+        let half = (self.scroll_metrics.viewport_rows / 2).max(1);
+        self.key_page_up(items, half);
+    }
+
+    pub(crate) fn select_relative_rows<A, F>(&mut self, items: &[TreeItem<A>], new_index: F)
+    where
+        A: TreeItemRender,
+        F: FnOnce(usize) -> usize,
+    {
+        let visible = flatten_state(self, items);
+        if visible.is_empty() {
+            return;
+        }
+        let current_index = visible.iter().position(|o| o.identifier == self.selected);
+        let new_index = current_index.map_or(0, new_index).min(visible.len() - 1);
+        if current_index != Some(new_index) {
+            self.select(visible[new_index].identifier.clone());
+        }
+    }
+
+    /// Scroll the view down by the amount of rows without moving the selection.
+    ///
+    /// The selection may be scrolled off this way.
+    /// The view jumps back to the selection the next time it or the size of the area changes.
+    /// See [`scroll_down`](Self::scroll_down) to move the selection along instead.
+    pub fn scroll_viewport_down<A: TreeItemRender>(&mut self, items: &[TreeItem<A>], rows: usize) {
+        let visible = flatten_state(self, items);
+        self.offset = self
+            .offset
+            .saturating_add(rows)
+            .min(visible.len().saturating_sub(1));
+        self.viewport_detached = true;
+    }
+
+    /// Scroll the view up by the amount of rows without moving the selection.
+    ///
+    /// See [`scroll_viewport_down`](Self::scroll_viewport_down).
+    pub fn scroll_viewport_up(&mut self, rows: usize) {
+        self.offset = self.offset.saturating_sub(rows);
+        self.viewport_detached = true;
+    }
+}
+
+#[test]
+fn detached_viewport_keeps_selection_off_screen() {
+    use tui::buffer::Buffer;
+    use tui::layout::Rect;
+    use tui::widgets::StatefulWidget;
+
+    let items = (0..10)
+        .map(|_| TreeItem::new_leaf("row"))
+        .collect::<Vec<_>>();
+    let mut state = TreeState::default();
+    state.select_first();
+    state.scroll_viewport_down(&items, 5);
+
+    let area = Rect::new(0, 0, 5, 3);
+    let mut buffer = Buffer::empty(area);
+    crate::Tree::new(items.clone()).render(area, &mut buffer, &mut state);
+    assert_eq!(state.get_offset(), 5);
+    assert_eq!(state.selected(), [0]);
+
+    state.key_down(&items);
+    crate::Tree::new(items).render(area, &mut buffer, &mut state);
+    assert_eq!(state.get_offset(), 1);
+}
@@ -0,0 +1,90 @@
+use std::ops::Range;
+
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+
+use crate::{color_mode, Tree, TreeItemRender, TreeState};
+
+impl<'a, A: TreeItemRender> Tree<'a, A> {
+    /// Redraw only the rows within the range, like after a single item changed.
+    ///
+    /// The range indexes the visible items like [`RowContext::index`](crate::RowContext::index).
+    /// The tree is laid out like a full render, so the offset is updated the same way, but only
+    /// the lines of these rows are written into the buffer.
+    /// Everything else like the block or the scrollbar keeps what was drawn before.
+    /// [`TreeState::hit_test`] gets the positions of these rows, the other rows keep the
+    /// positions of the previous render.
+    /// Rows outside of the area are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+    /// let mut state = TreeState::default();
+    ///
+    /// let area = Rect::new(0, 0, 4, 2);
+    /// let mut buffer = Buffer::with_lines(vec!["xxxx", "xxxx"]);
+    /// Tree::new(items).render_rows(1..2, area, &mut buffer, &mut state);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["xxxx", "  b "]));
+    /// ```
+    pub fn render_rows(
+        self,
+        rows: Range<usize>,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut TreeState,
+    ) {
+        let color_mode = self.color_mode;
+        let color_fallbacks = self.color_fallbacks;
+        self.render_tree(area, buf, state, Some(rows));
+        color_mode::degrade_colors(color_mode, color_fallbacks, area, buf);
+    }
+}
+
+#[test]
+fn rows_are_indexed_regardless_of_the_offset() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![2]);
+
+    let area = Rect::new(0, 0, 6, 2);
+    let mut buffer = Buffer::with_lines(vec!["xxxxxx", "xxxxxx"]);
+    Tree::new(items).render_rows(4..5, area, &mut buffer, &mut state);
+    assert_eq!(state.get_offset(), 4);
+    assert_eq!(buffer, Buffer::with_lines(vec!["    g ", "xxxxxx"]));
+}
+
+#[test]
+fn other_rows_and_the_block_are_kept() {
+    use tui::widgets::{Block, Borders, StatefulWidget};
+
+    use crate::TreeItem;
+
+    let mut state = TreeState::default();
+    let area = Rect::new(0, 0, 5, 4);
+    let mut buffer = Buffer::empty(area);
+    let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+    Tree::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .render(area, &mut buffer, &mut state);
+
+    // Only the second row is drawn again, the first one keeps its changed cell
+    buffer.set_string(3, 1, "x", tui::style::Style::default());
+    let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("c")];
+    Tree::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .render_rows(1..2, area, &mut buffer, &mut state);
+    let expected = Buffer::with_lines(vec![
+        "\u{250c}\u{2500}\u{2500}\u{2500}\u{2510}",
+        "\u{2502}  x\u{2502}",
+        "\u{2502}  c\u{2502}",
+        "\u{2514}\u{2500}\u{2500}\u{2500}\u{2518}",
+    ]);
+    assert_eq!(buffer, expected);
+    assert_eq!(state.hit_test((3, 1)).map(|(id, _)| id), Some(vec![0]));
+    assert_eq!(state.hit_test((3, 2)).map(|(id, _)| id), Some(vec![1]));
+}
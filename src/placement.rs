@@ -0,0 +1,94 @@
+use crate::identifier::TreeIdentifierVec;
+use crate::window::Heights;
+use crate::{SearchScope, TreeItem, TreeItemRender, TreeState};
+
+/// Where a row jumped to lands within the viewport, see [`TreeState::reveal_at`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// Scroll as little as needed to show the row
+    Nearest,
+    /// Show the row as the first one
+    Top,
+    /// Show the row in the middle
+    Center,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self::Nearest
+    }
+}
+
+impl Placement {
+    /// Offset showing the selected row at this placement.
+    /// The rows at the end are not scrolled up further than needed to fill the lines.
+    pub(crate) fn offset(
+        self,
+        heights: &Heights,
+        selected: usize,
+        available: usize,
+        offset: usize,
+    ) -> usize {
+        let start = match self {
+            Self::Nearest => return offset,
+            Self::Top => selected,
+            Self::Center => {
+                let above = available.saturating_sub(heights.get(selected)) / 2;
+                heights.start_until(selected, above)
+            }
+        };
+        start.min(heights.start_until(heights.len(), available))
+    }
+}
+
+impl TreeState {
+    /// Like [`reveal`](Self::reveal) but places the node within the viewport when the tree is
+    /// rendered next.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Placement, Tree, TreeItem, TreeState};
+    /// let items = (0..100).map(|_| TreeItem::new_leaf("row")).collect::<Vec<_>>();
+    /// let mut state = TreeState::default();
+    /// state.reveal_at(vec![50], Placement::Center);
+    ///
+    /// let area = Rect::new(0, 0, 10, 9);
+    /// Tree::new(items).render(area, &mut Buffer::empty(area), &mut state);
+    /// assert_eq!(state.get_offset(), 46);
+    /// ```
+    pub fn reveal_at(&mut self, identifier: TreeIdentifierVec, placement: Placement) {
+        self.reveal(identifier);
+        self.placement = Some(placement);
+    }
+
+    /// Like [`search_next`](Self::search_next) but places the selected match within the
+    /// viewport when the tree is rendered next.
+    pub fn search_next_at<A: TreeItemRender>(
+        &mut self,
+        items: &[TreeItem<A>],
+        query: &str,
+        scope: SearchScope,
+        placement: Placement,
+    ) -> bool {
+        let found = self.search_next(items, query, scope);
+        if found {
+            self.placement = Some(placement);
+        }
+        found
+    }
+}
+
+#[test]
+fn placement_keeps_the_viewport_filled() {
+    let heights = Heights::new(vec![1, 2, 1, 1, 1, 1]);
+    assert_eq!(Placement::Nearest.offset(&heights, 3, 3, 1), 1);
+    assert_eq!(Placement::Top.offset(&heights, 2, 3, 0), 2);
+    assert_eq!(Placement::Top.offset(&heights, 5, 3, 0), 3);
+    assert_eq!(Placement::Center.offset(&heights, 3, 3, 0), 2);
+    assert_eq!(Placement::Center.offset(&heights, 3, 4, 0), 2);
+    assert_eq!(Placement::Center.offset(&heights, 0, 3, 4), 0);
+}
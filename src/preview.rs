@@ -0,0 +1,122 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::text::Text;
+use tui::widgets::{Block, Paragraph, StatefulWidget, Widget, Wrap};
+
+use crate::error::resolve;
+use crate::{Tree, TreeItemRender, TreeState};
+
+/// Content shown for the selected node next to the tree by [`TreeWithPreview`], like the
+/// beginning of a file
+pub trait TreePreview {
+    fn preview(&self) -> Text<'_>;
+}
+
+/// [`Tree`] on the left with the [preview](TreePreview) of the selected node on the right
+///
+/// # Example
+///
+/// ```
+/// # use tui::buffer::Buffer;
+/// # use tui::layout::Rect;
+/// # use tui::text::Text;
+/// # use tui::widgets::StatefulWidget;
+/// # use tui_tree_widget::{Tree, TreeItem, TreeItemRender, TreePreview, TreeState, TreeWithPreview};
+/// #[derive(Clone)]
+/// struct File {
+///     name: &'static str,
+///     content: &'static str,
+/// }
+///
+/// impl TreeItemRender for File {
+///     fn as_text(&self) -> Text<'_> {
+///         Text::raw(self.name)
+///     }
+/// }
+///
+/// impl TreePreview for File {
+///     fn preview(&self) -> Text<'_> {
+///         Text::raw(self.content)
+///     }
+/// }
+///
+/// let items = vec![TreeItem::new_leaf(File { name: "a.txt", content: "hello" })];
+/// let mut state = TreeState::default();
+/// state.select_first();
+///
+/// let area = Rect::new(0, 0, 14, 1);
+/// let mut buffer = Buffer::empty(area);
+/// TreeWithPreview::new(Tree::new(items)).render(area, &mut buffer, &mut state);
+/// assert_eq!(buffer, Buffer::with_lines(vec!["  a.txt hello "]));
+/// ```
+pub struct TreeWithPreview<'a, A> {
+    tree: Tree<'a, A>,
+    preview_block: Option<Block<'a>>,
+    tree_width: Option<u16>,
+}
+
+impl<'a, A> TreeWithPreview<'a, A> {
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn new(tree: Tree<'a, A>) -> Self {
+        Self {
+            tree,
+            preview_block: None,
+            tree_width: None,
+        }
+    }
+
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn preview_block(mut self, block: Block<'a>) -> Self {
+        self.preview_block = Some(block);
+        self
+    }
+
+    /// Fixed width of the tree.
+    /// By default the tree is as wide as [`Tree::required_width`] but at most half of the area.
+    #[must_use]
+    pub const fn tree_width(mut self, width: u16) -> Self {
+        self.tree_width = Some(width);
+        self
+    }
+}
+
+impl<'a, A: TreeItemRender + TreePreview> StatefulWidget for TreeWithPreview<'a, A> {
+    type State = TreeState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let tree_width = self
+            .tree_width
+            .unwrap_or_else(|| {
+                let required = self.tree.required_width(state);
+                u16::try_from(required)
+                    .unwrap_or(u16::MAX)
+                    .min(area.width / 2)
+            })
+            .min(area.width);
+        let tree_area = Rect {
+            width: tree_width,
+            ..area
+        };
+        // The preview starts after a gap of one column
+        let preview_x = (tree_area.right() + 1).min(area.right());
+        let preview_area = Rect {
+            x: preview_x,
+            width: area.right() - preview_x,
+            ..area
+        };
+
+        // The preview borrows the items so it is rendered before the tree consumes them
+        let text = resolve(&self.tree.items, &state.selected)
+            .map(|item| item.elem.preview())
+            .unwrap_or_default();
+        let mut paragraph = Paragraph::new(text).wrap(Wrap { trim: false });
+        if let Some(block) = self.preview_block {
+            paragraph = paragraph.block(block);
+        }
+        paragraph.render(preview_area, buf);
+
+        StatefulWidget::render(self.tree, tree_area, buf, state);
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use crate::identifier::TreeIdentifierVec;
+use crate::TreeState;
+
+/// How rows marked with [`TreeState::mark_changed`] are highlighted
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Pulse {
+    /// How long a change is highlighted
+    pub duration: Duration,
+    /// Toggle the highlight on and off with this interval, otherwise it stays on
+    pub blink_interval: Option<Duration>,
+}
+
+impl Pulse {
+    /// Whether a change marked at `changed` is highlighted at `now`.
+    /// Only depends on the time passed, not on the amount of frames rendered.
+    pub fn is_on(&self, changed: Instant, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(changed);
+        if elapsed >= self.duration {
+            return false;
+        }
+        self.blink_interval
+            .filter(|interval| !interval.is_zero())
+            .map_or(true, |interval| {
+                (elapsed.as_nanos() / interval.as_nanos()) % 2 == 0
+            })
+    }
+}
+
+impl TreeState {
+    /// Mark a node as changed at the given time.
+    ///
+    /// The [`Tree`](crate::Tree) highlights the row for a while with the style of
+    /// [`Tree::changed_style`](crate::Tree::changed_style).
+    /// Useful for log or watch trees where users need to notice updates.
+    pub fn mark_changed(&mut self, identifier: TreeIdentifierVec, now: Instant) {
+        self.changed.insert(identifier, now);
+    }
+
+    /// Nodes marked as changed which are not yet expired
+    pub fn get_all_changed(&self) -> Vec<TreeIdentifierVec> {
+        self.changed.keys().cloned().collect()
+    }
+
+    /// Forget changes which are not highlighted anymore
+    pub(crate) fn expire_changed(&mut self, pulse: &Pulse, now: Instant) {
+        self.changed
+            .retain(|_, changed| now.saturating_duration_since(*changed) < pulse.duration);
+    }
+}
+
+#[test]
+fn pulse_without_blink_is_on_until_duration() {
+    let pulse = Pulse {
+        duration: Duration::from_secs(2),
+        blink_interval: None,
+    };
+    let changed = Instant::now();
+    assert!(pulse.is_on(changed, changed));
+    assert!(pulse.is_on(changed, changed + Duration::from_millis(1999)));
+    assert!(!pulse.is_on(changed, changed + Duration::from_secs(2)));
+}
+
+#[test]
+fn pulse_blinks_with_interval() {
+    let pulse = Pulse {
+        duration: Duration::from_secs(2),
+        blink_interval: Some(Duration::from_millis(500)),
+    };
+    let changed = Instant::now();
+    assert!(pulse.is_on(changed, changed + Duration::from_millis(100)));
+    assert!(!pulse.is_on(changed, changed + Duration::from_millis(600)));
+    assert!(pulse.is_on(changed, changed + Duration::from_millis(1100)));
+}
@@ -0,0 +1,151 @@
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{text_to_string, TreeItem, TreeItemRender, TreeState};
+
+/// How [`TreeState::reconcile`] matches nodes of the old items with the new items
+#[derive(Debug, Clone, Copy)]
+pub enum ReconcileStrategy<A> {
+    /// Nodes match when their rendered text is equal
+    Text,
+    /// Nodes match when the key extracted from their element is equal
+    Key(fn(&A) -> String),
+}
+
+impl<A: TreeItemRender> ReconcileStrategy<A> {
+    fn key(&self, item: &TreeItem<A>) -> String {
+        match self {
+            Self::Text => text_to_string(&item.elem.as_text()),
+            Self::Key(key) => key(&item.elem),
+        }
+    }
+}
+
+/// Translate an identifier of the old items into the new items.
+/// Returns the translated part which can be shorter than the original when a node vanished.
+fn translate<A: TreeItemRender>(
+    identifier: TreeIdentifier,
+    old_items: &[TreeItem<A>],
+    new_items: &[TreeItem<A>],
+    strategy: &ReconcileStrategy<A>,
+) -> TreeIdentifierVec {
+    let mut result = Vec::with_capacity(identifier.len());
+    let mut old_items = old_items;
+    let mut new_items = new_items;
+    for index in identifier {
+        let old_item = match old_items.get(*index) {
+            Some(item) => item,
+            None => break,
+        };
+        let key = strategy.key(old_item);
+        let new_index = match new_items.iter().position(|o| strategy.key(o) == key) {
+            Some(index) => index,
+            None => break,
+        };
+        result.push(new_index);
+        old_items = &old_item.children;
+        new_items = &new_items[new_index].children;
+    }
+    result
+}
+
+impl TreeState {
+    /// Translate the selection, opened nodes and every other node stored within the state like
+    /// bookmarks, tags and checked nodes from `old_items` to `new_items`.
+    ///
+    /// Use this when the items are replaced wholesale (like a refresh) in order to keep the
+    /// user's place in the tree.
+    /// Opened nodes which no longer exist are closed and other nodes which no longer exist are
+    /// forgotten.
+    /// When the selected node no longer exists its closest still existing ancestor is selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{ReconcileStrategy, TreeItem, TreeState};
+    /// let old = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let new = vec![
+    ///     TreeItem::new_leaf("z"),
+    ///     TreeItem::new("a", vec![TreeItem::new_leaf("b")]),
+    /// ];
+    ///
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.select(vec![0, 0]);
+    ///
+    /// state.reconcile(&old, &new, ReconcileStrategy::Text);
+    /// assert_eq!(state.selected(), [1, 0]);
+    /// assert_eq!(state.get_all_opened(), [vec![1]]);
+    /// ```
+    pub fn reconcile<A: TreeItemRender>(
+        &mut self,
+        old_items: &[TreeItem<A>],
+        new_items: &[TreeItem<A>],
+        strategy: ReconcileStrategy<A>,
+    ) {
+        let selected = translate(&self.selected, old_items, new_items, &strategy);
+        self.remap_retain(&|identifier| {
+            let translated = translate(identifier, old_items, new_items, &strategy);
+            (translated.len() == identifier.len()).then(|| translated)
+        });
+        // Parents are checked depending on their children which may have changed
+        self.sync_checked_parents(new_items);
+        self.select(selected);
+    }
+}
+
+#[test]
+fn reconcile_selects_ancestor_of_removed_node() {
+    let old = crate::flatten::get_example_tree_items();
+    let mut new = crate::flatten::get_example_tree_items();
+    new[1].children.remove(1);
+    new.swap(0, 2);
+
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+
+    state.reconcile(&old, &new, ReconcileStrategy::Text);
+    assert_eq!(state.selected(), [1]);
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+}
+
+#[test]
+fn reconcile_by_key() {
+    let old = vec![TreeItem::new_leaf("a1"), TreeItem::new_leaf("b1")];
+    let new = vec![TreeItem::new_leaf("b2"), TreeItem::new_leaf("a2")];
+
+    let mut state = TreeState::default();
+    state.select(vec![1]);
+
+    state.reconcile(
+        &old,
+        &new,
+        ReconcileStrategy::Key(|elem| elem[..1].to_string()),
+    );
+    assert_eq!(state.selected(), [0]);
+}
+
+#[test]
+fn reconcile_translates_bookmarks_tags_and_checked() {
+    let old = crate::flatten::get_example_tree_items();
+    let mut new = crate::flatten::get_example_tree_items();
+    new[1].children.remove(0);
+    new.swap(0, 2);
+
+    let mut state = TreeState::default();
+    state.select(vec![1, 1]);
+    state.bookmark_selected();
+    state.select(vec![1, 0]);
+    state.bookmark_selected();
+    state.tag(vec![1, 2], "todo");
+    state.tag(vec![0], "todo");
+    state.toggle_checked(&old, &[1, 1, 1]).unwrap();
+
+    state.reconcile(&old, &new, ReconcileStrategy::Text);
+    assert_eq!(state.bookmarks(), [vec![1, 0]]);
+    let mut tagged = state.tagged("todo");
+    tagged.sort();
+    assert_eq!(tagged, [vec![1, 1], vec![2]]);
+    assert_eq!(state.checked_leaves(&new), [vec![1, 0, 1]]);
+    assert_eq!(state.selected(), [1]);
+}
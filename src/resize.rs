@@ -0,0 +1,72 @@
+use tui::layout::Rect;
+
+use crate::TreeState;
+
+impl TreeState {
+    /// Width and height of the area the tree was rendered into last.
+    /// `None` before the first render.
+    ///
+    /// When the size changes between renders the offset is revalidated: the rows fill the area
+    /// again and a selection [scrolled off](Self::scroll_viewport_down) is brought back into view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = (0..20).map(|_| TreeItem::new_leaf("row")).collect::<Vec<_>>();
+    /// let mut state = TreeState::default();
+    /// state.select(vec![19]);
+    ///
+    /// let small = Rect::new(0, 0, 10, 5);
+    /// Tree::new(items.clone()).render(small, &mut Buffer::empty(small), &mut state);
+    /// assert_eq!(state.get_offset(), 15);
+    ///
+    /// let large = Rect::new(0, 0, 10, 10);
+    /// Tree::new(items).render(large, &mut Buffer::empty(large), &mut state);
+    /// assert_eq!(state.last_area_size(), Some((10, 10)));
+    /// assert_eq!(state.get_offset(), 10);
+    /// ```
+    pub const fn last_area_size(&self) -> Option<(u16, u16)> {
+        self.last_area_size
+    }
+
+    /// Remember the size of the area rendered into.
+    /// Returns whether it changed since the last render, which also attaches the viewport to the
+    /// selection again.
+    pub(crate) fn track_area_size(&mut self, area: Rect) -> bool {
+        let size = Some((area.width, area.height));
+        let resized = self.last_area_size.is_some() && self.last_area_size != size;
+        self.last_area_size = size;
+        if resized {
+            self.viewport_detached = false;
+        }
+        resized
+    }
+}
+
+#[test]
+fn shrinking_brings_detached_selection_back() {
+    use tui::buffer::Buffer;
+    use tui::widgets::StatefulWidget;
+
+    let items = (0..20)
+        .map(|_| crate::TreeItem::new_leaf("row"))
+        .collect::<Vec<_>>();
+    let mut state = TreeState::default();
+    state.select(vec![9]);
+    let area = Rect::new(0, 0, 10, 10);
+    crate::Tree::new(items.clone()).render(area, &mut Buffer::empty(area), &mut state);
+    assert!(!state.track_area_size(area));
+
+    state.scroll_viewport_down(&items, 5);
+    crate::Tree::new(items.clone()).render(area, &mut Buffer::empty(area), &mut state);
+    assert_eq!(state.get_offset(), 5);
+
+    let area = Rect::new(0, 0, 10, 3);
+    crate::Tree::new(items).render(area, &mut Buffer::empty(area), &mut state);
+    assert_eq!(state.get_offset(), 7);
+    assert!(!state.viewport_detached);
+}
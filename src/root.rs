@@ -0,0 +1,49 @@
+use tui::style::Style;
+use tui::text::Spans;
+
+use crate::TreeItem;
+
+/// Root item with a header row above its subtree, like the name of a mounted workspace
+///
+/// Rendered by a [`Tree`](crate::Tree) created with
+/// [`Tree::from_roots`](crate::Tree::from_roots).
+/// The header belongs to the root index, so it stays with its root when the roots are sorted.
+///
+/// # Example
+///
+/// ```
+/// # use tui::style::{Modifier, Style};
+/// # use tui_tree_widget::{Tree, TreeItem, TreeRoot};
+/// let roots = vec![
+///     TreeRoot::new("workspace a", TreeItem::new_leaf("src"))
+///         .style(Style::default().add_modifier(Modifier::BOLD)),
+///     TreeRoot::new("workspace b", TreeItem::new_leaf("docs")),
+/// ];
+/// let tree = Tree::from_roots(roots).selectable_headers(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreeRoot<'a, A> {
+    pub(crate) title: Spans<'a>,
+    pub(crate) style: Style,
+    pub(crate) item: TreeItem<A>,
+}
+
+impl<'a, A> TreeRoot<'a, A> {
+    pub fn new<T>(title: T, item: TreeItem<A>) -> Self
+    where
+        T: Into<Spans<'a>>,
+    {
+        Self {
+            title: title.into(),
+            style: Style::default(),
+            item,
+        }
+    }
+
+    /// Style of the whole header row
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
@@ -0,0 +1,87 @@
+use crate::flatten::Flattened;
+use crate::identifier::TreeIdentifierVec;
+
+/// Scroll position of a [`Tree`](crate::Tree) rendered without a [`TreeState`](crate::TreeState)
+///
+/// For read-only embeds like dashboards which display a scrolled tree without selection or
+/// expansion interactivity.
+/// Pass it with [`Tree::scroll`](crate::Tree::scroll) and render the tree as a
+/// [`Widget`](tui::widgets::Widget).
+///
+/// # Example
+///
+/// ```
+/// # use tui::buffer::Buffer;
+/// # use tui::layout::Rect;
+/// # use tui::widgets::Widget;
+/// # use tui_tree_widget::{Tree, TreeItem, TreeScroll};
+/// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+/// let mut scroll = TreeScroll::default();
+/// scroll.scroll_down(1);
+///
+/// let area = Rect::new(0, 0, 3, 1);
+/// let mut buffer = Buffer::empty(area);
+/// Tree::new(items).scroll(scroll).render(area, &mut buffer);
+/// assert_eq!(buffer, Buffer::with_lines(vec!["  b"]));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct TreeScroll {
+    offset: usize,
+    /// Row shown first, takes precedence over the offset when it is visible
+    anchor: Option<TreeIdentifierVec>,
+}
+
+impl TreeScroll {
+    /// Index of the first row shown when no anchor is set or found
+    pub const fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_anchor(&self) -> Option<&[usize]> {
+        self.anchor.as_deref()
+    }
+
+    /// Show the row at the index first. Removes the anchor.
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+        self.anchor = None;
+    }
+
+    /// Show the node first.
+    /// The anchor keeps the node in place even when rows above it are added or removed.
+    /// The offset is used when the node is not visible.
+    pub fn set_anchor(&mut self, identifier: TreeIdentifierVec) {
+        self.anchor = Some(identifier);
+    }
+
+    /// Scroll down by the amount of rows. Removes the anchor.
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.set_offset(self.offset.saturating_add(rows));
+    }
+
+    /// Scroll up by the amount of rows. Removes the anchor.
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.set_offset(self.offset.saturating_sub(rows));
+    }
+
+    /// Index of the first row to show
+    pub(crate) fn resolve<A>(&self, visible: &[Flattened<A>]) -> usize {
+        self.anchor
+            .as_ref()
+            .and_then(|anchor| visible.iter().position(|o| o.identifier == *anchor))
+            .unwrap_or(self.offset)
+    }
+}
+
+#[test]
+fn anchor_takes_precedence_when_visible() {
+    let items = crate::flatten::get_example_tree_items();
+    let visible = crate::flatten::flatten(&[vec![1]], &items);
+    let mut scroll = TreeScroll::default();
+    scroll.scroll_down(2);
+    assert_eq!(scroll.resolve(&visible), 2);
+    scroll.set_anchor(vec![2]);
+    assert_eq!(scroll.resolve(&visible), 5);
+    scroll.set_anchor(vec![1, 1, 0]);
+    assert_eq!(scroll.resolve(&visible), 2);
+}
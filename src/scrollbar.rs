@@ -0,0 +1,151 @@
+use tui::buffer::Buffer;
+use tui::layout::{Corner, Rect};
+use tui::style::Style;
+
+use crate::{Tree, TreeState};
+
+/// Symbols and styles of the scrollbar shown by [`Tree::scrollbar`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollbarStyle<'a> {
+    pub track_symbol: &'a str,
+    pub thumb_symbol: &'a str,
+    pub track_style: Style,
+    pub thumb_style: Style,
+}
+
+impl<'a> Default for ScrollbarStyle<'a> {
+    fn default() -> Self {
+        Self {
+            track_symbol: "\u{2502}",
+            thumb_symbol: "\u{2588}",
+            track_style: Style::default(),
+            thumb_style: Style::default(),
+        }
+    }
+}
+
+/// Position of the rendered rows within all visible rows as of the last render
+///
+/// Returned by [`TreeState::scroll_metrics`] to draw a custom scrollbar or scroll indicator.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScrollMetrics {
+    /// Amount of visible rows, including rows scrolled off
+    pub total_rows: usize,
+    /// Index of the first rendered row
+    pub offset: usize,
+    /// Amount of rendered rows
+    pub viewport_rows: usize,
+}
+
+impl TreeState {
+    /// Position of the rendered rows within all visible rows as of the last render
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{ScrollMetrics, Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b"), TreeItem::new_leaf("c")];
+    /// let mut state = TreeState::default();
+    /// state.select(vec![2]);
+    ///
+    /// let area = Rect::new(0, 0, 5, 2);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items).render(area, &mut buffer, &mut state);
+    /// let expected = ScrollMetrics {
+    ///     total_rows: 3,
+    ///     offset: 1,
+    ///     viewport_rows: 2,
+    /// };
+    /// assert_eq!(state.scroll_metrics(), expected);
+    /// ```
+    pub const fn scroll_metrics(&self) -> ScrollMetrics {
+        self.scroll_metrics
+    }
+}
+
+impl<'a, A> Tree<'a, A> {
+    /// Show a scrollbar in the last column, or the first one when rendered
+    /// [right to left](Self::right_to_left).
+    ///
+    /// The column is reserved even when all rows fit, so the labels do not move when the
+    /// amount of rows changes.
+    /// The scrollbar is only drawn when some rows are scrolled off.
+    #[must_use]
+    pub const fn scrollbar(mut self, style: ScrollbarStyle<'a>) -> Self {
+        self.scrollbar = Some(style);
+        self
+    }
+
+    /// Split the column of the scrollbar from the area of the rows
+    pub(crate) fn split_scrollbar(&self, area: Rect) -> (Rect, Option<Rect>) {
+        if self.scrollbar.is_none() || area.width < 2 {
+            return (area, None);
+        }
+        let rows = Rect {
+            x: area.x + u16::from(self.right_to_left),
+            width: area.width - 1,
+            ..area
+        };
+        let x = if self.right_to_left {
+            area.left()
+        } else {
+            area.right() - 1
+        };
+        let bar = Rect {
+            x,
+            width: 1,
+            ..area
+        };
+        (rows, Some(bar))
+    }
+
+    pub(crate) fn render_scrollbar(&self, area: Rect, metrics: ScrollMetrics, buf: &mut Buffer) {
+        let style = match self.scrollbar {
+            Some(style) => style,
+            None => return,
+        };
+        if metrics.total_rows <= metrics.viewport_rows {
+            return;
+        }
+        let (thumb_start, thumb_length) = thumb(area.height, metrics);
+        for line in 0..area.height {
+            // Rows are rendered upwards from the bottom left corner
+            let y = match self.start_corner {
+                Corner::BottomLeft => area.bottom() - 1 - line,
+                _ => area.top() + line,
+            };
+            let (symbol, symbol_style) =
+                if (thumb_start..thumb_start + thumb_length).contains(&line) {
+                    (style.thumb_symbol, style.thumb_style)
+                } else {
+                    (style.track_symbol, style.track_style)
+                };
+            buf.set_stringn(area.x, y, symbol, 1, symbol_style);
+        }
+    }
+}
+
+/// Start and length of the thumb within a track of the height
+fn thumb(height: u16, metrics: ScrollMetrics) -> (u16, u16) {
+    let height = usize::from(height);
+    let length = (height * metrics.viewport_rows / metrics.total_rows).clamp(1, height);
+    let max_offset = metrics.total_rows - metrics.viewport_rows;
+    let start = (height - length) * metrics.offset.min(max_offset) / max_offset;
+    (start as u16, length as u16)
+}
+
+#[test]
+fn thumb_reaches_both_ends() {
+    let metrics = |offset| ScrollMetrics {
+        total_rows: 20,
+        offset,
+        viewport_rows: 5,
+    };
+    assert_eq!(thumb(10, metrics(0)), (0, 2));
+    assert_eq!(thumb(10, metrics(15)), (8, 2));
+    assert_eq!(thumb(10, metrics(8)), (4, 2));
+    assert_eq!(thumb(3, metrics(15)), (2, 1));
+}
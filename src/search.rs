@@ -0,0 +1,208 @@
+use crate::flatten::flatten_state;
+use crate::identifier::{required_ancestors, TreeIdentifierVec};
+use crate::items::IterDepthFirst;
+use crate::{text_to_string, TreeItem, TreeItemRender, TreeState};
+
+/// Which [`TreeItem`s](TreeItem) are considered by a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchScope {
+    /// Only the currently visible items. The expansion is not changed.
+    Visible,
+    /// All items. The ancestors of every match are opened.
+    AllExpanded,
+    /// All items. Only the ancestors of the newly selected match are opened.
+    EntireTree,
+}
+
+/// Case insensitive check whether the rendered text of the item contains the query
+pub(crate) fn is_match<A: TreeItemRender>(item: &TreeItem<A>, query: &str) -> bool {
+    text_to_string(&item.elem.as_text())
+        .to_lowercase()
+        .contains(query)
+}
+
+impl TreeState {
+    /// All items within the scope in the order they are searched
+    fn search_candidates<'a, A: TreeItemRender>(
+        &self,
+        items: &'a [TreeItem<A>],
+        scope: SearchScope,
+    ) -> Vec<(TreeIdentifierVec, &'a TreeItem<A>)> {
+        match scope {
+            SearchScope::Visible => flatten_state(self, items)
+                .into_iter()
+                .map(|o| (o.identifier, o.item))
+                .collect(),
+            SearchScope::AllExpanded | SearchScope::EntireTree => {
+                IterDepthFirst::new(items).collect()
+            }
+        }
+    }
+
+    /// Identifiers of all items within the scope matching the query.
+    /// Visible items are returned in rendering order, all others in the order of the items.
+    ///
+    /// Matching is case insensitive and done on the rendered text of the items.
+    /// This does not open any nodes, even with [`SearchScope::AllExpanded`].
+    pub fn search_matches<A: TreeItemRender>(
+        &self,
+        items: &[TreeItem<A>],
+        query: &str,
+        scope: SearchScope,
+    ) -> Vec<TreeIdentifierVec> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let query = query.to_lowercase();
+        self.search_candidates(items, scope)
+            .into_iter()
+            .filter(|(_, item)| is_match(item, &query))
+            .map(|(identifier, _)| identifier)
+            .collect()
+    }
+
+    /// Select the next item matching the query after the current selection.
+    /// Wraps around to the first match.
+    ///
+    /// Returns `true` when a match was selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{SearchScope, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("src", vec![TreeItem::new_leaf("lib.rs")])];
+    /// let mut state = TreeState::default();
+    ///
+    /// assert!(!state.search_next(&items, "lib", SearchScope::Visible));
+    /// assert!(state.search_next(&items, "lib", SearchScope::EntireTree));
+    /// assert_eq!(state.selected(), [0, 0]);
+    /// assert_eq!(state.get_all_opened(), [vec![0]]);
+    /// ```
+    pub fn search_next<A: TreeItemRender>(
+        &mut self,
+        items: &[TreeItem<A>],
+        query: &str,
+        scope: SearchScope,
+    ) -> bool {
+        if query.is_empty() {
+            return false;
+        }
+        let query = query.to_lowercase();
+        let candidates = self.search_candidates(items, scope);
+        let selected_position = candidates
+            .iter()
+            .position(|(identifier, _)| *identifier == self.selected);
+        let after_selected = selected_position.map_or(0, |position| position + 1);
+
+        let matches = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, item))| is_match(item, &query))
+            .map(|(position, (identifier, _))| (position, identifier))
+            .collect::<Vec<_>>();
+        let next = matches
+            .iter()
+            .find(|(position, _)| *position >= after_selected)
+            .or_else(|| matches.first());
+        let next = match next {
+            Some((_, identifier)) => (*identifier).clone(),
+            None => return false,
+        };
+
+        match scope {
+            SearchScope::Visible => {}
+            SearchScope::AllExpanded => {
+                for (_, identifier) in &matches {
+                    self.open_ancestors(identifier);
+                }
+            }
+            SearchScope::EntireTree => self.open_ancestors(&next),
+        }
+        self.select(next);
+        true
+    }
+
+    /// Select the node and open its closed ancestors so it is visible.
+    /// The ancestors are opened as [transient](Self::collapse_transient) nodes.
+    pub fn reveal(&mut self, identifier: TreeIdentifierVec) {
+        self.open_ancestors(&identifier);
+        self.select(identifier);
+    }
+
+    /// Open the closed ancestors as [transient](Self::collapse_transient) nodes.
+    /// Closed nodes [linked](Self::link) to them are opened as transient nodes too.
+    pub(crate) fn open_ancestors(&mut self, identifier: &[usize]) {
+        for ancestor in required_ancestors(identifier) {
+            if self.opened.contains(&ancestor) {
+                continue;
+            }
+            let mut group = self.linked_group(&ancestor);
+            group.push(ancestor);
+            for node in group {
+                if !self.opened.contains(&node) {
+                    self.open_single(node.clone());
+                    self.transient.insert(node);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn search_visible_does_not_open() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert!(!state.search_next(&items, "e", SearchScope::Visible));
+    assert!(state.get_all_opened().is_empty());
+}
+
+#[test]
+fn search_all_expanded_opens_every_match() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![1, 1, 0]);
+    assert!(state.search_next(&items, "E", SearchScope::AllExpanded));
+    // Only match wraps around to itself
+    assert_eq!(state.selected(), [1, 1, 0]);
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+}
+
+#[test]
+fn search_next_wraps_around() {
+    let items = vec![
+        TreeItem::new_leaf("foo"),
+        TreeItem::new_leaf("bar"),
+        TreeItem::new_leaf("food"),
+    ];
+    let mut state = TreeState::default();
+    state.select(vec![2]);
+    assert!(state.search_next(&items, "foo", SearchScope::Visible));
+    assert_eq!(state.selected(), [0]);
+    assert!(state.search_next(&items, "foo", SearchScope::Visible));
+    assert_eq!(state.selected(), [2]);
+}
+
+#[test]
+fn reveal_opens_ancestors() {
+    let mut state = TreeState::default();
+    state.reveal(vec![1, 1, 0]);
+    assert_eq!(state.selected(), [1, 1, 0]);
+    assert!(state.is_transient(&[1]));
+    assert!(state.is_transient(&[1, 1]));
+}
+
+#[test]
+fn reveal_collapses_linked_nodes_again() {
+    let mut state = TreeState::default();
+    state.open(vec![2]);
+    state.link(vec![1], vec![2]);
+    state.link(vec![1, 1], vec![2, 1]);
+    state.reveal(vec![1, 1, 0]);
+    assert!(state.is_transient(&[2, 1]));
+    assert!(!state.is_transient(&[2]));
+
+    state.collapse_transient();
+    assert_eq!(state.get_all_opened(), [vec![2]]);
+}
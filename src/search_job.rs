@@ -0,0 +1,116 @@
+use crate::identifier::TreeIdentifierVec;
+use crate::source::TreeItemSource;
+use crate::{text_to_string, TreeItemRender};
+
+/// Search through a whole [`TreeItemSource`] a few nodes at a time
+///
+/// Searching huge lazy trees at once would block the UI.
+/// Instead call [`step`](Self::step) once per frame with a budget of nodes and keep rendering
+/// while the [`matches`](Self::matches) found so far grow.
+///
+/// Matching is case insensitive and done on the rendered text of the nodes, in depth first
+/// order.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{SearchJob, TreeIdentifier, TreeItemSource};
+/// struct Numbers;
+///
+/// impl TreeItemSource for Numbers {
+///     type Elem = &'static str;
+///
+///     fn child_count(&self, parent: TreeIdentifier) -> usize {
+///         if parent.is_empty() { 1_000 } else { 0 }
+///     }
+///
+///     fn child(&self, _parent: TreeIdentifier, index: usize) -> Self::Elem {
+///         if index % 2 == 0 { "even" } else { "odd" }
+///     }
+/// }
+///
+/// let mut job = SearchJob::new("odd");
+/// // Within the event loop, before rendering
+/// job.step(&Numbers, 100);
+/// assert_eq!(job.matches().len(), 50);
+///
+/// while !job.step(&Numbers, 100) {}
+/// assert_eq!(job.matches().len(), 500);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchJob {
+    query: String,
+    /// Parents still to be searched with the index of their next child
+    stack: Vec<(TreeIdentifierVec, usize)>,
+    matches: Vec<TreeIdentifierVec>,
+    visited: usize,
+}
+
+impl SearchJob {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_lowercase(),
+            stack: vec![(Vec::new(), 0)],
+            matches: Vec::new(),
+            visited: 0,
+        }
+    }
+
+    /// Search at most `budget` more nodes.
+    ///
+    /// Returns `true` when the whole tree has been searched.
+    pub fn step<S: TreeItemSource>(&mut self, source: &S, budget: usize) -> bool {
+        let mut remaining = budget;
+        while remaining > 0 {
+            let (parent, index) = match self.stack.last_mut() {
+                Some(frame) => frame,
+                None => break,
+            };
+            if *index >= source.child_count(parent) {
+                self.stack.pop();
+                continue;
+            }
+            let elem = source.child(parent, *index);
+            let mut identifier = parent.clone();
+            identifier.push(*index);
+            *index += 1;
+            remaining -= 1;
+            self.visited += 1;
+
+            let text = text_to_string(&elem.as_text()).to_lowercase();
+            if !self.query.is_empty() && text.contains(&self.query) {
+                self.matches.push(identifier.clone());
+            }
+            self.stack.push((identifier, 0));
+        }
+        self.is_finished()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Identifiers of the nodes matching so far in depth first order
+    pub fn matches(&self) -> &[TreeIdentifierVec] {
+        &self.matches
+    }
+
+    /// Amount of nodes searched so far
+    pub const fn visited(&self) -> usize {
+        self.visited
+    }
+}
+
+#[test]
+fn step_keeps_the_budget() {
+    let source = crate::source::ExampleSource;
+    let mut job = SearchJob::new("E");
+    assert!(!job.step(&source, 5));
+    assert_eq!(job.visited(), 5);
+    assert_eq!(job.matches(), [vec![1, 1, 0]]);
+
+    assert!(job.step(&source, 100));
+    assert_eq!(job.visited(), 8);
+    assert!(job.step(&source, 1));
+    assert_eq!(job.visited(), 8);
+}
@@ -0,0 +1,105 @@
+use crate::error::{resolve, resolve_mut};
+use crate::flatten::flatten_state;
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+impl TreeState {
+    /// Move the selection by `delta` visible rows, down when positive and up when negative.
+    /// Stops at the first and last row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = (0..10).map(|_| TreeItem::new_leaf("row")).collect::<Vec<_>>();
+    /// let mut state = TreeState::default();
+    /// state.select_first();
+    ///
+    /// state.select_relative(&items, 4);
+    /// assert_eq!(state.selected(), [4]);
+    /// state.select_relative(&items, -7);
+    /// assert_eq!(state.selected(), [0]);
+    /// ```
+    pub fn select_relative<A: TreeItemRender>(&mut self, items: &[TreeItem<A>], delta: isize) {
+        let steps = delta.unsigned_abs();
+        if delta < 0 {
+            self.select_relative_rows(items, |index| index.saturating_sub(steps));
+        } else {
+            self.select_relative_rows(items, |index| index.saturating_add(steps));
+        }
+    }
+
+    /// Select the visible row at the index, counted from the first row of the tree.
+    /// Returns `false` without changing the selection when there are not as many visible rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![
+    ///     TreeItem::new("a", vec![TreeItem::new_leaf("b")]),
+    ///     TreeItem::new_leaf("c"),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    ///
+    /// assert!(state.select_visible_index(&items, 1));
+    /// assert_eq!(state.selected(), [0, 0]);
+    /// assert!(!state.select_visible_index(&items, 3));
+    /// ```
+    pub fn select_visible_index<A: TreeItemRender>(
+        &mut self,
+        items: &[TreeItem<A>],
+        index: usize,
+    ) -> bool {
+        let identifier = flatten_state(self, items)
+            .get(index)
+            .map(|row| row.identifier.clone());
+        identifier.map_or(false, |identifier| {
+            self.select(identifier);
+            true
+        })
+    }
+
+    /// Get the selected [`TreeItem`].
+    /// Returns `None` when nothing is selected or the selection does not exist in the items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// assert!(state.selected_item(&items).is_none());
+    ///
+    /// state.select(vec![0]);
+    /// assert_eq!(state.selected_item(&items).unwrap().children().len(), 1);
+    /// ```
+    pub fn selected_item<'a, A>(&self, items: &'a [TreeItem<A>]) -> Option<&'a TreeItem<A>> {
+        resolve(items, &self.selected).ok()
+    }
+
+    /// Same as [`selected_item`](Self::selected_item) but the [`TreeItem`] is borrowed mutably
+    pub fn selected_item_mut<'a, A>(
+        &self,
+        items: &'a mut [TreeItem<A>],
+    ) -> Option<&'a mut TreeItem<A>> {
+        resolve_mut(items, &self.selected).ok()
+    }
+}
+
+#[test]
+fn relative_selection_follows_visible_rows() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select_first();
+
+    state.select_relative(&items, 2);
+    assert_eq!(state.selected(), [1, 0]);
+    state.select_relative(&items, 2);
+    assert_eq!(state.selected(), [1, 2]);
+    state.select_relative(&items, 100);
+    assert_eq!(state.selected(), [2]);
+    state.select_relative(&items, -1);
+    assert_eq!(state.selected_item(&items).unwrap().elem, "g");
+}
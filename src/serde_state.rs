@@ -0,0 +1,59 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::identifier::TreeIdentifierVec;
+use crate::TreeState;
+
+/// Parts of a [`TreeState`] which are persisted
+#[derive(Serialize, Deserialize)]
+struct PersistedState {
+    opened: Vec<TreeIdentifierVec>,
+    selected: TreeIdentifierVec,
+    offset: usize,
+}
+
+/// Persist which nodes were opened between sessions.
+///
+/// Only the opened nodes, the selection and the offset are serialized.
+/// Everything else like the filter or the layout of the last render starts with its default when
+/// deserialized.
+impl Serialize for TreeState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut opened = self.get_all_opened();
+        opened.sort();
+        PersistedState {
+            opened,
+            selected: self.selected(),
+            offset: self.offset,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TreeState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let persisted = PersistedState::deserialize(deserializer)?;
+        Ok(Self {
+            opened: persisted.opened.into_iter().collect(),
+            selected: persisted.selected,
+            offset: persisted.offset,
+            ..Self::default()
+        })
+    }
+}
+
+#[test]
+fn state_round_trips_through_json() {
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+    state.set_filter("e");
+
+    let json = serde_json::to_string(&state).unwrap();
+    assert_eq!(
+        json,
+        r#"{"opened":[[1],[1,1]],"selected":[1,1,0],"offset":0}"#
+    );
+    let restored: TreeState = serde_json::from_str(&json).unwrap();
+    assert!(restored.equals_ignoring(&state, &[crate::TreeStateField::Filter]));
+}
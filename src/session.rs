@@ -0,0 +1,215 @@
+use crate::flatten::flatten_state;
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{text_to_string, TreeItem, TreeItemRender, TreeState};
+
+/// Path of a node described by the rendered text of the node and its ancestors
+pub type LabelPath = Vec<String>;
+
+/// Snapshot of the UI state of a [`Tree`](crate::Tree) which survives restarts
+///
+/// Nodes are referenced by the rendered text of the node and its ancestors instead of their
+/// position so the session can be restored even when the items changed in the meantime.
+/// Created by [`TreeState::export_session`] and restored by [`TreeState::import_session`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TreeSession {
+    pub opened_paths: Vec<LabelPath>,
+    pub selected_path: LabelPath,
+    /// First row visible in the viewport
+    pub anchor_path: LabelPath,
+}
+
+fn labels_of<A: TreeItemRender>(items: &[TreeItem<A>], identifier: TreeIdentifier) -> LabelPath {
+    let mut result = Vec::with_capacity(identifier.len());
+    let mut items = items;
+    for index in identifier {
+        let item = match items.get(*index) {
+            Some(item) => item,
+            None => break,
+        };
+        result.push(text_to_string(&item.elem.as_text()));
+        items = &item.children;
+    }
+    result
+}
+
+/// Resolve the labels to an identifier.
+/// Returns the resolved part which is shorter than the labels when a label was not found.
+fn resolve_labels<A: TreeItemRender, S: AsRef<str>>(
+    items: &[TreeItem<A>],
+    labels: &[S],
+) -> TreeIdentifierVec {
+    resolve_keys(items, labels, |elem| text_to_string(&elem.as_text()))
+}
+
+/// Resolve the keys extracted by `key_of` to an identifier.
+/// Returns the resolved part which is shorter than the keys when a key was not found.
+fn resolve_keys<A, S, F>(items: &[TreeItem<A>], keys: &[S], key_of: F) -> TreeIdentifierVec
+where
+    S: AsRef<str>,
+    F: Fn(&A) -> String,
+{
+    let mut result = Vec::with_capacity(keys.len());
+    let mut items = items;
+    for key in keys {
+        let index = match items.iter().position(|o| key_of(&o.elem) == key.as_ref()) {
+            Some(index) => index,
+            None => break,
+        };
+        result.push(index);
+        items = &items[index].children;
+    }
+    result
+}
+
+impl TreeState {
+    /// Create a [`TreeSession`] which can be persisted and restored later with
+    /// [`import_session`](Self::import_session).
+    pub fn export_session<A: TreeItemRender>(&self, items: &[TreeItem<A>]) -> TreeSession {
+        let mut opened_paths = self
+            .opened
+            .iter()
+            .map(|identifier| labels_of(items, &identifier))
+            .collect::<Vec<_>>();
+        opened_paths.sort();
+
+        let anchor_path = flatten_state(self, items)
+            .get(self.offset)
+            .map(|o| labels_of(items, &o.identifier))
+            .unwrap_or_default();
+
+        TreeSession {
+            opened_paths,
+            selected_path: labels_of(items, &self.selected),
+            anchor_path,
+        }
+    }
+
+    /// Restore a [`TreeSession`] created by [`export_session`](Self::export_session).
+    ///
+    /// Opened nodes which can not be found are ignored.
+    /// When the selected node can not be found its closest existing ancestor is selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    /// state.select(vec![0, 0]);
+    /// let session = state.export_session(&items);
+    ///
+    /// let items = vec![
+    ///     TreeItem::new_leaf("new"),
+    ///     TreeItem::new("a", vec![TreeItem::new_leaf("b")]),
+    /// ];
+    /// let mut restored = TreeState::default();
+    /// restored.import_session(&items, &session);
+    /// assert_eq!(restored.selected(), [1, 0]);
+    /// assert_eq!(restored.get_all_opened(), [vec![1]]);
+    /// ```
+    pub fn import_session<A: TreeItemRender>(
+        &mut self,
+        items: &[TreeItem<A>],
+        session: &TreeSession,
+    ) {
+        self.opened = session
+            .opened_paths
+            .iter()
+            .filter_map(|labels| {
+                let identifier = resolve_labels(items, labels);
+                (!identifier.is_empty() && identifier.len() == labels.len()).then(|| identifier)
+            })
+            .collect();
+
+        self.transient.clear();
+        self.select(resolve_labels(items, &session.selected_path));
+
+        let anchor = resolve_labels(items, &session.anchor_path);
+        self.offset = flatten_state(self, items)
+            .iter()
+            .position(|o| o.identifier == anchor)
+            .unwrap_or(0);
+    }
+
+    /// Resolve the path of labels (the rendered text of the nodes) to an identifier and open all
+    /// its ancestors so the node is visible.
+    ///
+    /// Returns `None` without opening anything when a label can not be found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let items = vec![TreeItem::new(
+    ///     "src",
+    ///     vec![TreeItem::new("widgets", vec![TreeItem::new_leaf("tree.rs")])],
+    /// )];
+    /// let mut state = TreeState::default();
+    ///
+    /// let identifier = state.open_path_by_labels(&items, &["src", "widgets", "tree.rs"]);
+    /// assert_eq!(identifier, Some(vec![0, 0, 0]));
+    /// assert_eq!(state.get_all_opened().len(), 2);
+    /// ```
+    pub fn open_path_by_labels<A: TreeItemRender, S: AsRef<str>>(
+        &mut self,
+        items: &[TreeItem<A>],
+        labels: &[S],
+    ) -> Option<TreeIdentifierVec> {
+        self.open_path_by_keys(items, labels, |elem| text_to_string(&elem.as_text()))
+    }
+
+    /// Same as [`open_path_by_labels`](Self::open_path_by_labels) but the nodes are matched by
+    /// the key extracted from their element instead of their rendered text.
+    pub fn open_path_by_keys<A, S, F>(
+        &mut self,
+        items: &[TreeItem<A>],
+        keys: &[S],
+        key_of: F,
+    ) -> Option<TreeIdentifierVec>
+    where
+        S: AsRef<str>,
+        F: Fn(&A) -> String,
+    {
+        let identifier = resolve_keys(items, keys, key_of);
+        if identifier.is_empty() || identifier.len() != keys.len() {
+            return None;
+        }
+        self.open_ancestors(&identifier);
+        Some(identifier)
+    }
+}
+
+#[test]
+fn session_roundtrip_keeps_anchor() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 1]);
+    state.offset = 3;
+
+    let session = state.export_session(&items);
+    assert_eq!(session.anchor_path, ["b", "d"]);
+    assert_eq!(session.opened_paths, [vec!["b"], vec!["b", "d"]]);
+
+    let mut restored = TreeState::default();
+    restored.import_session(&items, &session);
+    assert_eq!(restored.selected(), [1, 1, 1]);
+    assert_eq!(restored.get_offset(), 3);
+}
+
+#[test]
+fn open_path_by_labels_fails_without_opening() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    assert_eq!(state.open_path_by_labels(&items, &["b", "x", "e"]), None);
+    assert_eq!(state.open_path_by_labels::<_, &str>(&items, &[]), None);
+    assert!(state.get_all_opened().is_empty());
+
+    let identifier = state.open_path_by_keys(&items, &["B", "D", "E"], |elem| elem.to_uppercase());
+    assert_eq!(identifier, Some(vec![1, 1, 0]));
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 1]]);
+}
@@ -0,0 +1,162 @@
+use std::sync::{Arc, LockResult, Mutex, MutexGuard};
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{TreeItem, TreeItemRender, TreeState};
+
+/// [`TreeState`] which can be shared between threads
+///
+/// Clones refer to the same [`TreeState`].
+/// Every method acquires the lock internally, so a background thread can for example reveal
+/// nodes while the UI thread renders.
+///
+/// # Panics
+///
+/// The methods panic when another thread panicked while holding the lock, see
+/// [`lock`](Self::lock) to recover from that.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::SharedTreeState;
+/// let state = SharedTreeState::default();
+///
+/// let background = state.clone();
+/// std::thread::spawn(move || {
+///     background.open(vec![0]);
+///     background.select(vec![0, 1]);
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(state.selected(), [0, 1]);
+/// // Render with `f.render_stateful_widget(tree, area, &mut state.lock().unwrap());`
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SharedTreeState(Arc<Mutex<TreeState>>);
+
+impl SharedTreeState {
+    pub fn new(state: TreeState) -> Self {
+        Self(Arc::new(Mutex::new(state)))
+    }
+
+    /// Acquire the lock of the [`TreeState`], for example to render it.
+    ///
+    /// # Errors
+    ///
+    /// A thread panicked while holding the lock, possibly in the middle of changing the
+    /// [`TreeState`] like while [reconciling](TreeState::reconcile) or rendering.
+    /// The state may then be only partially updated.
+    /// It can still be taken from the error with
+    /// [`into_inner`](std::sync::PoisonError::into_inner) and for example be reset with
+    /// [`invalidate`](TreeState::invalidate) or replaced.
+    pub fn lock(&self) -> LockResult<MutexGuard<'_, TreeState>> {
+        self.0.lock()
+    }
+
+    /// Acquire the lock and propagate the panic of a thread which poisoned it
+    fn state(&self) -> MutexGuard<'_, TreeState> {
+        self.0.lock().unwrap()
+    }
+
+    pub fn get_offset(&self) -> usize {
+        self.state().get_offset()
+    }
+
+    pub fn get_all_opened(&self) -> Vec<TreeIdentifierVec> {
+        self.state().get_all_opened()
+    }
+
+    pub fn selected(&self) -> Vec<usize> {
+        self.state().selected()
+    }
+
+    pub fn select<I>(&self, identifier: I)
+    where
+        I: Into<Vec<usize>>,
+    {
+        self.state().select(identifier);
+    }
+
+    pub fn open(&self, identifier: TreeIdentifierVec) -> bool {
+        self.state().open(identifier)
+    }
+
+    pub fn close(&self, identifier: TreeIdentifier) -> bool {
+        self.state().close(identifier)
+    }
+
+    pub fn toggle(&self, identifier: TreeIdentifierVec) {
+        self.state().toggle(identifier);
+    }
+
+    pub fn toggle_selected(&self) {
+        self.state().toggle_selected();
+    }
+
+    pub fn close_all(&self) {
+        self.state().close_all();
+    }
+
+    pub fn select_first(&self) {
+        self.state().select_first();
+    }
+
+    pub fn select_last<A: TreeItemRender>(&self, items: &[TreeItem<A>]) {
+        self.state().select_last(items);
+    }
+
+    pub fn key_up<A: TreeItemRender>(&self, items: &[TreeItem<A>]) {
+        self.state().key_up(items);
+    }
+
+    pub fn key_down<A: TreeItemRender>(&self, items: &[TreeItem<A>]) {
+        self.state().key_down(items);
+    }
+
+    pub fn key_left(&self) {
+        self.state().key_left();
+    }
+
+    pub fn key_right(&self) {
+        self.state().key_right();
+    }
+}
+
+impl From<TreeState> for SharedTreeState {
+    fn from(state: TreeState) -> Self {
+        Self::new(state)
+    }
+}
+
+#[test]
+fn clones_share_the_state() {
+    let items = crate::flatten::get_example_tree_items();
+    let state = SharedTreeState::default();
+    let other = state.clone();
+    other.select_first();
+    state.key_down(&items);
+    other.key_right();
+    assert_eq!(state.selected(), [1]);
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+}
+
+#[test]
+fn poisoned_lock_is_reported() {
+    let state = SharedTreeState::default();
+    let other = state.clone();
+    let result = std::thread::spawn(move || {
+        let mut guard = other.state();
+        guard.open(vec![0]);
+        panic!("interrupted while changing the state");
+    })
+    .join();
+    assert!(result.is_err());
+
+    let mut guard = state
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    assert_eq!(guard.get_all_opened(), [vec![0]]);
+    guard.close_all();
+    drop(guard);
+    assert!(state.lock().is_err());
+}
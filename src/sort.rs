@@ -0,0 +1,63 @@
+use std::cmp::Ordering;
+
+use crate::identifier::TreeIdentifierVec;
+use crate::{text_to_string, TreeItemRender, TreeState};
+
+/// How the children of a parent are ordered when they are rendered.
+/// See [`TreeState::set_child_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SortKey {
+    /// Ascending by the rendered text
+    Label,
+    /// Descending by the rendered text
+    LabelDescending,
+    /// By [`TreeItemRender::compare`] with the given key like `"mtime"`
+    Named(&'static str),
+}
+
+impl SortKey {
+    pub(crate) fn compare<A: TreeItemRender>(self, a: &A, b: &A) -> Ordering {
+        match self {
+            Self::Label => text_to_string(&a.as_text()).cmp(&text_to_string(&b.as_text())),
+            Self::LabelDescending => {
+                text_to_string(&b.as_text()).cmp(&text_to_string(&a.as_text()))
+            }
+            Self::Named(key) => a.compare(b, key),
+        }
+    }
+}
+
+impl TreeState {
+    /// Sort the children of the parent when they are rendered without changing the items.
+    /// The empty identifier sorts the root items.
+    ///
+    /// Different subtrees can be sorted differently, like one folder by name and another by
+    /// modification time.
+    /// Identifiers keep referencing the position within the items, not the rendered position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{SortKey, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("a")];
+    /// let mut state = TreeState::default();
+    /// state.set_child_order(vec![], SortKey::Label);
+    ///
+    /// state.select_first();
+    /// state.key_down(&items);
+    /// assert_eq!(state.selected(), [0]);
+    /// ```
+    pub fn set_child_order(&mut self, parent: TreeIdentifierVec, key: SortKey) {
+        self.child_orders.insert(parent, key);
+    }
+
+    /// Render the children of the parent in the order of the items again.
+    /// Returns the [`SortKey`] used before.
+    pub fn remove_child_order(&mut self, parent: &[usize]) -> Option<SortKey> {
+        self.child_orders.remove(parent)
+    }
+
+    pub fn child_order(&self, parent: &[usize]) -> Option<SortKey> {
+        self.child_orders.get(parent).copied()
+    }
+}
@@ -0,0 +1,529 @@
+use std::collections::HashMap;
+
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::widgets::{Block, StatefulWidget, Widget};
+use unicode_width::UnicodeWidthStr;
+
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::opened::OpenedSet;
+use crate::{
+    depth_prefix, pick_node_symbol, TreeItemRender, TreeState, INDENT_WIDTH, NODE_CLOSED_SYMBOL,
+    NODE_LEAF_SYMBOL, NODE_OPEN_SYMBOL,
+};
+
+/// Tree data which produces its children on demand instead of materialized
+/// [`TreeItem`s](crate::TreeItem)
+///
+/// Parents only report how many children they have and a child is only created when it is
+/// actually rendered.
+/// This allows displaying parents with millions of children in constant memory.
+/// Rendered by [`SourceTree`].
+pub trait TreeItemSource {
+    type Elem: TreeItemRender;
+
+    /// Amount of children of the parent. An empty identifier references the root items.
+    fn child_count(&self, parent: TreeIdentifier) -> usize;
+
+    /// Create the element of the child at the index of the parent
+    fn child(&self, parent: TreeIdentifier, index: usize) -> Self::Elem;
+
+    /// Stable key of the child at the index of the parent.
+    /// Children without a key are referenced by their position.
+    /// See [`TreeState::export_keyed`].
+    fn child_key(&self, _parent: TreeIdentifier, _index: usize) -> Option<u64> {
+        None
+    }
+
+    /// Current index of the child of the parent with the key
+    fn index_of_key(&self, _parent: TreeIdentifier, _key: u64) -> Option<usize> {
+        None
+    }
+}
+
+/// Amount of visible rows below every shown opened parent, the root items below the empty
+/// identifier.
+/// Computed once per render or key press instead of for every row.
+struct VisibleCounts(HashMap<TreeIdentifierVec, usize>);
+
+impl VisibleCounts {
+    /// Walks the opened parents with an explicit stack so deep trees can not overflow the stack.
+    fn new<S: TreeItemSource>(source: &S, opened: &OpenedSet) -> Self {
+        let mut counts = HashMap::new();
+        let mut preorder = Vec::new();
+        let mut stack = vec![Vec::new()];
+        while let Some(parent) = stack.pop() {
+            let count = source.child_count(&parent);
+            for index in opened.children(&parent).take_while(|index| *index < count) {
+                let mut child = parent.clone();
+                child.push(index);
+                stack.push(child);
+            }
+            counts.insert(parent.clone(), count);
+            preorder.push(parent);
+        }
+        // Descendants follow their parent, so they are complete before they are added to it
+        for identifier in preorder.iter().rev() {
+            if let Some((_, parent)) = identifier.split_last() {
+                let descendants = counts[identifier];
+                if let Some(count) = counts.get_mut(parent) {
+                    *count += descendants;
+                }
+            }
+        }
+        Self(counts)
+    }
+
+    /// Amount of visible rows
+    fn total(&self) -> usize {
+        self.0.get(&[][..]).copied().unwrap_or_default()
+    }
+
+    /// Opened children of the parent with the amount of their visible descendants, ordered by index
+    fn opened_children(&self, opened: &OpenedSet, parent: TreeIdentifier) -> Vec<(usize, usize)> {
+        let mut child = parent.to_vec();
+        child.push(0);
+        opened
+            .children(parent)
+            .filter_map(|index| {
+                child[parent.len()] = index;
+                self.0.get(&child).map(|descendants| (index, *descendants))
+            })
+            .collect()
+    }
+
+    /// Identifier of the visible row at the index
+    fn locate<S: TreeItemSource>(
+        &self,
+        source: &S,
+        opened: &OpenedSet,
+        mut index: usize,
+    ) -> Option<TreeIdentifierVec> {
+        let mut parent = Vec::new();
+        'parents: loop {
+            let mut next_child = 0;
+            for (child, descendants) in self.opened_children(opened, &parent) {
+                let closed = child - next_child;
+                if index < closed {
+                    parent.push(next_child + index);
+                    return Some(parent);
+                }
+                index -= closed;
+                parent.push(child);
+                if index == 0 {
+                    return Some(parent);
+                }
+                index -= 1;
+                if index < descendants {
+                    continue 'parents;
+                }
+                parent.pop();
+                index -= descendants;
+                next_child = child + 1;
+            }
+
+            let child = next_child + index;
+            return (child < source.child_count(&parent)).then(|| {
+                parent.push(child);
+                parent
+            });
+        }
+    }
+
+    /// Index of the visible row of the identifier.
+    /// Returns `None` when the identifier does not exist or one of its ancestors is closed.
+    fn row_of<S: TreeItemSource>(
+        &self,
+        source: &S,
+        opened: &OpenedSet,
+        identifier: TreeIdentifier,
+    ) -> Option<usize> {
+        let mut row = 0;
+        for depth in 0..identifier.len() {
+            let parent = &identifier[..depth];
+            if depth > 0 {
+                if !opened.contains(parent) {
+                    return None;
+                }
+                // The parent row itself
+                row += 1;
+            }
+            let index = identifier[depth];
+            if index >= source.child_count(parent) {
+                return None;
+            }
+            row += index;
+            row += self
+                .opened_children(opened, parent)
+                .iter()
+                .take_while(|(child, _)| *child < index)
+                .map(|(_, descendants)| descendants)
+                .sum::<usize>();
+        }
+        Some(row)
+    }
+}
+
+/// Identifier of the visible row following the row of the identifier
+fn next_row<S: TreeItemSource>(
+    source: &S,
+    opened: &OpenedSet,
+    mut identifier: TreeIdentifierVec,
+) -> Option<TreeIdentifierVec> {
+    if opened.contains(&identifier) && source.child_count(&identifier) > 0 {
+        identifier.push(0);
+        return Some(identifier);
+    }
+    while let Some(index) = identifier.pop() {
+        if index + 1 < source.child_count(&identifier) {
+            identifier.push(index + 1);
+            return Some(identifier);
+        }
+    }
+    None
+}
+
+impl TreeState {
+    /// Handles the up arrow key for a [`TreeItemSource`].
+    /// See [`key_up`](Self::key_up).
+    pub fn key_up_source<S: TreeItemSource>(&mut self, source: &S) {
+        let counts = VisibleCounts::new(source, &self.opened);
+        let row = counts
+            .row_of(source, &self.opened, &self.selected)
+            .map_or(0, |row| row.saturating_sub(1));
+        if let Some(identifier) = counts.locate(source, &self.opened, row) {
+            self.select(identifier);
+        }
+    }
+
+    /// Handles the down arrow key for a [`TreeItemSource`].
+    /// See [`key_down`](Self::key_down).
+    pub fn key_down_source<S: TreeItemSource>(&mut self, source: &S) {
+        let counts = VisibleCounts::new(source, &self.opened);
+        let total = counts.total();
+        let row = counts
+            .row_of(source, &self.opened, &self.selected)
+            .map_or(0, |row| row.saturating_add(1).min(total.saturating_sub(1)));
+        if let Some(identifier) = counts.locate(source, &self.opened, row) {
+            self.select(identifier);
+        }
+    }
+}
+
+/// A tree rendering the items of a [`TreeItemSource`]
+///
+/// Only the rows within the area are created. Every row is exactly one line high.
+///
+/// # Example
+///
+/// ```
+/// # use tui::buffer::Buffer;
+/// # use tui::layout::Rect;
+/// # use tui::widgets::StatefulWidget;
+/// # use tui_tree_widget::{SourceTree, TreeIdentifier, TreeItemSource, TreeState};
+/// struct Numbers;
+///
+/// impl TreeItemSource for Numbers {
+///     type Elem = &'static str;
+///
+///     fn child_count(&self, parent: TreeIdentifier) -> usize {
+///         if parent.is_empty() { 1_000_000 } else { 0 }
+///     }
+///
+///     fn child(&self, _parent: TreeIdentifier, index: usize) -> Self::Elem {
+///         if index % 2 == 0 { "even" } else { "odd" }
+///     }
+/// }
+///
+/// let mut state = TreeState::default();
+/// state.select(vec![999_999]);
+///
+/// let area = Rect::new(0, 0, 10, 3);
+/// let mut buffer = Buffer::empty(area);
+/// SourceTree::new(&Numbers).render(area, &mut buffer, &mut state);
+/// assert_eq!(state.get_offset(), 999_997);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SourceTree<'a, S> {
+    block: Option<Block<'a>>,
+    source: &'a S,
+    /// Style used as a base style for the widget
+    style: Style,
+    /// Style used to render selected item
+    highlight_style: Style,
+    /// Symbol in front of the selected item (Shift all items to the right)
+    highlight_symbol: Option<&'a str>,
+    node_closed_symbol: &'a str,
+    node_open_symbol: &'a str,
+    node_leaf_symbol: &'a str,
+    indent_width: usize,
+}
+
+impl<'a, S> SourceTree<'a, S> {
+    pub fn new(source: &'a S) -> Self {
+        Self {
+            block: None,
+            source,
+            style: Style::default(),
+            highlight_style: Style::default(),
+            highlight_symbol: None,
+            node_closed_symbol: NODE_CLOSED_SYMBOL,
+            node_open_symbol: NODE_OPEN_SYMBOL,
+            node_leaf_symbol: NODE_LEAF_SYMBOL,
+            indent_width: INDENT_WIDTH,
+        }
+    }
+
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[must_use]
+    pub const fn highlight_symbol(mut self, highlight_symbol: &'a str) -> Self {
+        self.highlight_symbol = Some(highlight_symbol);
+        self
+    }
+
+    #[must_use]
+    pub const fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Symbol in front of nodes with hidden children, see [`Tree::node_closed_symbol`](crate::Tree::node_closed_symbol)
+    #[must_use]
+    pub const fn node_closed_symbol(mut self, symbol: &'a str) -> Self {
+        self.node_closed_symbol = symbol;
+        self
+    }
+
+    /// Symbol in front of nodes with shown children, see [`Tree::node_open_symbol`](crate::Tree::node_open_symbol)
+    #[must_use]
+    pub const fn node_open_symbol(mut self, symbol: &'a str) -> Self {
+        self.node_open_symbol = symbol;
+        self
+    }
+
+    /// Symbol in front of items without children, see [`Tree::node_leaf_symbol`](crate::Tree::node_leaf_symbol)
+    #[must_use]
+    pub const fn node_leaf_symbol(mut self, symbol: &'a str) -> Self {
+        self.node_leaf_symbol = symbol;
+        self
+    }
+
+    /// Columns of indentation per depth, see [`Tree::indent_width`](crate::Tree::indent_width)
+    #[must_use]
+    pub const fn indent_width(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+}
+
+impl<'a, S: TreeItemSource> StatefulWidget for SourceTree<'a, S> {
+    type State = TreeState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        buf.set_style(area, self.style);
+
+        // Get the inner area inside a possible block, otherwise use the full area
+        let area = self.block.map_or(area, |b| {
+            let inner_area = b.inner(area);
+            b.render(area, buf);
+            inner_area
+        });
+
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        let counts = VisibleCounts::new(self.source, &state.opened);
+        let available_height = area.height as usize;
+        let selected_index = counts
+            .row_of(self.source, &state.opened, &state.selected)
+            .unwrap_or(0);
+
+        let mut start = state.offset.min(selected_index);
+        if selected_index >= start + available_height {
+            start = selected_index + 1 - available_height;
+        }
+        state.offset = start;
+
+        let highlight_symbol = self.highlight_symbol.unwrap_or("");
+        let blank_symbol = " ".repeat(highlight_symbol.width());
+        let has_selection = !state.selected.is_empty();
+
+        // Only the first row is located, the following rows are walked to from the previous one
+        let mut next = counts.locate(self.source, &state.opened, start);
+        #[allow(clippy::cast_possible_truncation)]
+        for line in 0..available_height {
+            let identifier = match next {
+                Some(identifier) => identifier,
+                None => break,
+            };
+            let (parent, index) = identifier.split_at(identifier.len() - 1);
+            let elem = self.source.child(parent, index[0]);
+
+            let x = area.left();
+            let y = area.top() + line as u16;
+            let is_selected = state.selected == identifier;
+
+            let symbol = if !has_selection {
+                ""
+            } else if is_selected {
+                highlight_symbol
+            } else {
+                &blank_symbol
+            };
+            let node_symbol = pick_node_symbol(
+                self.node_closed_symbol,
+                self.node_open_symbol,
+                self.node_leaf_symbol,
+                self.source.child_count(&identifier) > 0,
+                state.opened.contains(&identifier),
+            );
+            let prefix = depth_prefix(parent.len(), self.indent_width, node_symbol);
+            let (x, _) = buf.set_stringn(x, y, symbol, area.width as usize, self.style);
+            let max_width = area.width.saturating_sub(x - area.left());
+            let (x, _) = buf.set_stringn(x, y, prefix, max_width as usize, self.style);
+            let max_width = area.width.saturating_sub(x - area.left());
+            if let Some(line) = elem.as_text().lines.first() {
+                buf.set_spans(x, y, line, max_width);
+            }
+
+            if is_selected {
+                buf.set_style(
+                    Rect::new(area.left(), y, area.width, 1),
+                    self.highlight_style,
+                );
+            }
+
+            next = next_row(self.source, &state.opened, identifier);
+        }
+    }
+}
+
+impl<'a, S: TreeItemSource> Widget for SourceTree<'a, S> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = TreeState::default();
+        StatefulWidget::render(self, area, buf, &mut state);
+    }
+}
+
+#[cfg(test)]
+pub(crate) struct ExampleSource;
+
+/// Same structure as the example tree items
+#[cfg(test)]
+impl TreeItemSource for ExampleSource {
+    type Elem = &'static str;
+
+    fn child_count(&self, parent: TreeIdentifier) -> usize {
+        match parent {
+            [] | [1] => 3,
+            [1, 1] => 2,
+            _ => 0,
+        }
+    }
+
+    fn child(&self, parent: TreeIdentifier, index: usize) -> Self::Elem {
+        match (parent, index) {
+            ([], 0) => "a",
+            ([], 1) => "b",
+            ([], _) => "h",
+            ([1], 0) => "c",
+            ([1], 1) => "d",
+            ([1], _) => "g",
+            (_, 0) => "e",
+            (_, _) => "f",
+        }
+    }
+}
+
+#[test]
+fn locate_and_row_of_match_flatten() {
+    let items = crate::flatten::get_example_tree_items();
+    let opened = [vec![1], vec![1, 1]];
+    let visible = crate::flatten::flatten(&opened, &items);
+    let opened = opened.iter().cloned().collect::<OpenedSet>();
+    let counts = VisibleCounts::new(&ExampleSource, &opened);
+
+    assert_eq!(counts.total(), visible.len());
+    let mut next = Some(vec![0]);
+    for (row, item) in visible.iter().enumerate() {
+        assert_eq!(
+            counts.locate(&ExampleSource, &opened, row),
+            Some(item.identifier.clone())
+        );
+        assert_eq!(
+            counts.row_of(&ExampleSource, &opened, &item.identifier),
+            Some(row)
+        );
+        assert_eq!(next.as_ref(), Some(&item.identifier));
+        next = next_row(&ExampleSource, &opened, item.identifier.clone());
+    }
+    assert_eq!(counts.locate(&ExampleSource, &opened, visible.len()), None);
+    assert_eq!(next, None);
+}
+
+#[test]
+fn key_down_source_moves_into_opened() {
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.select(vec![1]);
+    state.key_down_source(&ExampleSource);
+    assert_eq!(state.selected(), [1, 0]);
+    state.key_up_source(&ExampleSource);
+    state.key_up_source(&ExampleSource);
+    assert_eq!(state.selected(), [0]);
+}
+
+#[cfg(test)]
+struct DeepSource(usize);
+
+/// A single chain of nodes with a leaf at the depth
+#[cfg(test)]
+impl TreeItemSource for DeepSource {
+    type Elem = &'static str;
+
+    fn child_count(&self, parent: TreeIdentifier) -> usize {
+        usize::from(parent.len() <= self.0)
+    }
+
+    fn child(&self, _parent: TreeIdentifier, _index: usize) -> Self::Elem {
+        "node"
+    }
+}
+
+#[test]
+fn deep_source_renders_on_small_stack() {
+    let depth = 1_000;
+    let thread = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            let mut state = TreeState::default();
+            for length in 1..=depth {
+                state.open(vec![0; length]);
+            }
+            state.select(vec![0; depth + 1]);
+            let area = Rect::new(0, 0, 4, 3);
+            let mut buffer = Buffer::empty(area);
+            let source = DeepSource(depth);
+            let tree = SourceTree::new(&source).indent_width(0);
+            StatefulWidget::render(tree, area, &mut buffer, &mut state);
+            assert_eq!(state.get_offset(), depth - 2);
+            state.key_up_source(&source);
+            assert_eq!(state.selected(), vec![0; depth]);
+        })
+        .unwrap();
+    thread.join().unwrap();
+}
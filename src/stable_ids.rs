@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use crate::child_key::{ChildKey, KeyedIdentifier};
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{TreeItem, TreeState};
+
+/// Opened and selected nodes together with their keys when they were last synchronized
+#[derive(Debug, Default, Clone)]
+pub(crate) struct StableIds {
+    opened: HashMap<TreeIdentifierVec, KeyedIdentifier>,
+    selected: (TreeIdentifierVec, KeyedIdentifier),
+}
+
+impl<A> TreeItem<A> {
+    /// Identify the item by the key instead of its position.
+    ///
+    /// Keys only need to be unique among siblings.
+    /// See [`TreeState::set_stable_ids`] for keeping opened and selected nodes while the items
+    /// are sorted or mutated.
+    #[must_use]
+    pub const fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Key of the item set with [`with_id`](Self::with_id)
+    pub const fn id(&self) -> Option<u64> {
+        self.id
+    }
+}
+
+fn keyed<A>(items: &[TreeItem<A>], identifier: TreeIdentifier) -> KeyedIdentifier {
+    let mut result = Vec::with_capacity(identifier.len());
+    let mut items = items;
+    for index in identifier {
+        let item = match items.get(*index) {
+            Some(item) => item,
+            None => break,
+        };
+        result.push(item.id.map_or(ChildKey::Index(*index), ChildKey::Key));
+        items = &item.children;
+    }
+    result
+}
+
+/// Resolve the keys to an identifier.
+/// Returns the resolved part which is shorter than the keys when a key was not found.
+fn resolve_keyed<A>(items: &[TreeItem<A>], keyed: &[ChildKey]) -> TreeIdentifierVec {
+    let mut result = Vec::with_capacity(keyed.len());
+    let mut items = items;
+    for key in keyed {
+        let index = match key {
+            ChildKey::Index(index) => Some(*index).filter(|i| *i < items.len()),
+            ChildKey::Key(key) => items.iter().position(|item| item.id == Some(*key)),
+        };
+        match index {
+            Some(index) => {
+                result.push(index);
+                items = &items[index].children;
+            }
+            None => break,
+        }
+    }
+    result
+}
+
+impl TreeState {
+    /// Track the opened and selected nodes by the [ids](TreeItem::with_id) of the items instead
+    /// of their positions.
+    ///
+    /// The state survives sorting, inserting and removing items this way.
+    /// Items without an id are tracked by their position.
+    /// The positions are updated from the ids on every render or by calling
+    /// [`sync_stable_ids`](Self::sync_stable_ids) directly after the items changed.
+    /// Nodes opened or selected since the last synchronization are assumed to reference the
+    /// current items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{TreeItem, TreeState};
+    /// let mut items = vec![
+    ///     TreeItem::new("b", vec![TreeItem::new_leaf("c")]).with_id(2),
+    ///     TreeItem::new_leaf("a").with_id(1),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// state.set_stable_ids(true);
+    /// state.open(vec![0]);
+    /// state.select(vec![0, 0]);
+    /// state.sync_stable_ids(&items);
+    ///
+    /// items.reverse();
+    /// state.sync_stable_ids(&items);
+    /// assert_eq!(state.get_all_opened(), [vec![1]]);
+    /// assert_eq!(state.selected(), [1, 0]);
+    /// ```
+    pub fn set_stable_ids(&mut self, enabled: bool) {
+        if !enabled {
+            self.stable_ids = None;
+        } else if self.stable_ids.is_none() {
+            self.stable_ids = Some(StableIds::default());
+        }
+    }
+
+    /// Update the positions of the opened and selected nodes from their ids after the items
+    /// changed and remember the ids for the next time.
+    ///
+    /// Opened nodes whose id vanished are closed.
+    /// When the id of the selected node vanished its closest remaining ancestor is selected.
+    /// Does nothing unless enabled with [`set_stable_ids`](Self::set_stable_ids).
+    pub fn sync_stable_ids<A>(&mut self, items: &[TreeItem<A>]) {
+        let recorded = match self.stable_ids.take() {
+            Some(recorded) => recorded,
+            None => return,
+        };
+
+        let translate = |identifier: TreeIdentifierVec| match recorded.opened.get(&identifier) {
+            Some(keyed) => {
+                let resolved = resolve_keyed(items, keyed);
+                (resolved.len() == keyed.len()).then(|| resolved)
+            }
+            None => Some(identifier),
+        };
+        self.opened = self.opened.iter().filter_map(translate).collect();
+        self.transient = std::mem::take(&mut self.transient)
+            .into_iter()
+            .filter_map(translate)
+            .collect();
+        if self.selected == recorded.selected.0 {
+            let selected = resolve_keyed(items, &recorded.selected.1);
+            if selected != self.selected {
+                self.select(selected);
+            }
+        }
+
+        self.stable_ids = Some(StableIds {
+            opened: self
+                .opened
+                .iter()
+                .map(|identifier| {
+                    let keyed = keyed(items, &identifier);
+                    (identifier, keyed)
+                })
+                .collect(),
+            selected: (self.selected.clone(), keyed(items, &self.selected)),
+        });
+    }
+}
+
+#[test]
+fn vanished_ids_are_closed() {
+    let mut items = vec![
+        TreeItem::new("a", vec![TreeItem::new_leaf("b").with_id(7)]).with_id(1),
+        TreeItem::new("c", vec![TreeItem::new_leaf("d")]).with_id(2),
+    ];
+    let mut state = TreeState::default();
+    state.set_stable_ids(true);
+    state.open(vec![0]);
+    state.open(vec![1]);
+    state.select(vec![0, 0]);
+    state.sync_stable_ids(&items);
+
+    items.remove(0);
+    items.insert(0, TreeItem::new_leaf("e").with_id(3));
+    state.sync_stable_ids(&items);
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+    assert!(state.selected().is_empty());
+
+    state.set_stable_ids(false);
+    items.reverse();
+    state.sync_stable_ids(&items);
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+}
@@ -0,0 +1,69 @@
+use crate::TreeItem;
+
+/// Structural statistics of a tree, see [`tree_stats`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TreeStats {
+    /// Amount of all nodes, opened or not
+    pub node_count: usize,
+    /// Depth of the deepest node.
+    /// Root nodes have a depth of 0 like [`Flattened::depth`](crate::Flattened::depth).
+    pub max_depth: usize,
+    /// Amount of nodes without children
+    pub leaf_count: usize,
+    /// Most children any node has. The root nodes are not counted as children.
+    pub max_children: usize,
+}
+
+/// Compute the [`TreeStats`] of the items in a single pass.
+///
+/// Allows apps to adapt their UI to the shape of the tree, like warning about deep nesting.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::{tree_stats, TreeItem, TreeStats};
+/// let items = vec![
+///     TreeItem::new("a", vec![TreeItem::new_leaf("b"), TreeItem::new_leaf("c")]),
+///     TreeItem::new_leaf("d"),
+/// ];
+///
+/// let stats = tree_stats(&items);
+/// assert_eq!(
+///     stats,
+///     TreeStats {
+///         node_count: 4,
+///         max_depth: 1,
+///         leaf_count: 3,
+///         max_children: 2,
+///     }
+/// );
+/// ```
+pub fn tree_stats<A>(items: &[TreeItem<A>]) -> TreeStats {
+    let mut stats = TreeStats::default();
+    let mut stack = items.iter().map(|item| (0, item)).collect::<Vec<_>>();
+    while let Some((depth, item)) = stack.pop() {
+        stats.node_count += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        stats.max_children = stats.max_children.max(item.children.len());
+        if item.children.is_empty() {
+            stats.leaf_count += 1;
+        }
+        stack.extend(item.children.iter().map(|child| (depth + 1, child)));
+    }
+    stats
+}
+
+#[test]
+fn stats_of_example() {
+    let items = crate::flatten::get_example_tree_items();
+    assert_eq!(
+        tree_stats(&items),
+        TreeStats {
+            node_count: 8,
+            max_depth: 2,
+            leaf_count: 6,
+            max_children: 3,
+        }
+    );
+    assert_eq!(tree_stats::<&str>(&[]), TreeStats::default());
+}
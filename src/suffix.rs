@@ -0,0 +1,79 @@
+/// What to give up first when a row is too narrow for its label and suffix
+///
+/// See [`TreeItemRender::suffix`](crate::TreeItemRender::suffix) and
+/// [`Tree::shrink_priority`](crate::Tree::shrink_priority).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShrinkOrder {
+    /// Hide the suffix first, then truncate the label
+    HideSuffixFirst,
+    /// Truncate the label first, then hide the suffix when it does not fit on its own
+    TruncateLabelFirst,
+}
+
+impl Default for ShrinkOrder {
+    fn default() -> Self {
+        Self::HideSuffixFirst
+    }
+}
+
+impl ShrinkOrder {
+    /// Maximal width of the label and whether the suffix is shown.
+    /// The suffix is separated from the label by at least one column.
+    pub(crate) fn layout(
+        self,
+        available: usize,
+        label_width: usize,
+        suffix_width: Option<usize>,
+    ) -> (usize, bool) {
+        let suffix_width = match suffix_width {
+            Some(width) => width + 1,
+            None => return (available, false),
+        };
+        if label_width + suffix_width <= available {
+            return (available - suffix_width, true);
+        }
+        match self {
+            Self::HideSuffixFirst => (available, false),
+            Self::TruncateLabelFirst if suffix_width <= available => {
+                (available - suffix_width, true)
+            }
+            Self::TruncateLabelFirst => (available, false),
+        }
+    }
+}
+
+#[test]
+fn layout_fits_everything() {
+    assert_eq!(
+        ShrinkOrder::HideSuffixFirst.layout(10, 4, Some(3)),
+        (6, true)
+    );
+    assert_eq!(
+        ShrinkOrder::HideSuffixFirst.layout(10, 4, None),
+        (10, false)
+    );
+}
+
+#[test]
+fn layout_hide_suffix_first() {
+    assert_eq!(
+        ShrinkOrder::HideSuffixFirst.layout(6, 4, Some(3)),
+        (6, false)
+    );
+    assert_eq!(
+        ShrinkOrder::HideSuffixFirst.layout(2, 4, Some(3)),
+        (2, false)
+    );
+}
+
+#[test]
+fn layout_truncate_label_first() {
+    assert_eq!(
+        ShrinkOrder::TruncateLabelFirst.layout(6, 4, Some(3)),
+        (2, true)
+    );
+    assert_eq!(
+        ShrinkOrder::TruncateLabelFirst.layout(3, 4, Some(3)),
+        (3, false)
+    );
+}
@@ -0,0 +1,110 @@
+use crate::identifier::TreeIdentifierVec;
+use crate::TreeState;
+
+impl TreeState {
+    /// Tag a node with a name like "staged" or "conflicted".
+    /// A node can have multiple tags and a tag can be on any amount of nodes.
+    /// Returns `true` when the node did not have the tag before.
+    ///
+    /// The [`Tree`](crate::Tree) renders a marker for each tag with a style in
+    /// [`Tree::tag_styles`](crate::Tree::tag_styles).
+    /// Tags follow their nodes when the items are [reconciled](Self::reconcile).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::TreeState;
+    /// let mut state = TreeState::default();
+    /// state.tag(vec![2], "staged");
+    /// state.tag(vec![0, 1], "staged");
+    /// state.tag(vec![2], "conflicted");
+    ///
+    /// assert_eq!(state.tagged("staged"), [vec![0, 1], vec![2]]);
+    /// assert_eq!(state.tags_of(&[2]), ["conflicted", "staged"]);
+    /// ```
+    pub fn tag(&mut self, identifier: TreeIdentifierVec, tag: &str) -> bool {
+        self.tags
+            .entry(tag.to_string())
+            .or_default()
+            .insert(identifier)
+    }
+
+    /// Remove the tag from the node.
+    /// Returns `true` when the node had the tag.
+    pub fn untag(&mut self, identifier: &[usize], tag: &str) -> bool {
+        let tagged = match self.tags.get_mut(tag) {
+            Some(tagged) => tagged,
+            None => return false,
+        };
+        let removed = tagged.remove(identifier);
+        if tagged.is_empty() {
+            self.tags.remove(tag);
+        }
+        removed
+    }
+
+    /// Remove the tag from all nodes
+    pub fn clear_tag(&mut self, tag: &str) {
+        self.tags.remove(tag);
+    }
+
+    pub fn has_tag(&self, identifier: &[usize], tag: &str) -> bool {
+        self.tags
+            .get(tag)
+            .map_or(false, |tagged| tagged.contains(identifier))
+    }
+
+    /// All nodes with the tag in the order of their identifiers
+    pub fn tagged(&self, tag: &str) -> Vec<TreeIdentifierVec> {
+        self.tags
+            .get(tag)
+            .map(|tagged| tagged.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// All tags of the node sorted by name
+    pub fn tags_of(&self, identifier: &[usize]) -> Vec<&str> {
+        let mut tags = self
+            .tags
+            .iter()
+            .filter(|(_, tagged)| tagged.contains(identifier))
+            .map(|(tag, _)| tag.as_str())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags
+    }
+}
+
+#[test]
+fn untag_forgets_empty_tags() {
+    let mut state = TreeState::default();
+    assert!(state.tag(vec![1], "staged"));
+    assert!(!state.tag(vec![1], "staged"));
+    assert!(state.has_tag(&[1], "staged"));
+    assert!(state.untag(&[1], "staged"));
+    assert!(!state.untag(&[1], "staged"));
+    assert!(state.tagged("staged").is_empty());
+    assert_eq!(state, TreeState::default());
+}
+
+#[test]
+fn tags_follow_reconciled_nodes() {
+    let old = crate::flatten::get_example_tree_items();
+    let mut new = crate::flatten::get_example_tree_items();
+    new.remove(0);
+
+    let mut state = TreeState::default();
+    state.tag(vec![0], "removed");
+    state.tag(vec![1, 1], "staged");
+    state.tag(vec![2], "staged");
+    state.reconcile(&old, &new, crate::ReconcileStrategy::Text);
+    assert_eq!(state.tagged("staged"), [vec![0, 1], vec![1]]);
+    assert!(state.tags_of(&[0]).is_empty());
+    assert!(state.tagged("removed").is_empty());
+
+    // Tags without any nodes are forgotten like with untag
+    let mut expected = TreeState::default();
+    expected.tag(vec![0, 1], "staged");
+    expected.tag(vec![1], "staged");
+    assert_eq!(state, expected);
+}
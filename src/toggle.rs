@@ -0,0 +1,80 @@
+use crate::identifier::TreeIdentifierVec;
+use crate::{error, TreeItem, TreeState};
+
+/// What happened when toggling a node with [`TreeState::toggle_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Toggled {
+    Opened,
+    Closed,
+    /// The node is a leaf and was not opened.
+    /// Apps can treat this like pressing enter on the node, for example to open the file.
+    Activate,
+    /// The identifier does not reference an existing node
+    Missing,
+}
+
+impl TreeState {
+    /// Toggles a tree node like [`toggle`](Self::toggle) but leaves are never opened.
+    ///
+    /// Opening a leaf would only add it to the opened nodes which are for example persisted with
+    /// [`export_session`](Self::export_session).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{Toggled, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("a", vec![TreeItem::new_leaf("b")])];
+    /// let mut state = TreeState::default();
+    /// assert_eq!(state.toggle_with(&items, vec![0]), Toggled::Opened);
+    /// assert_eq!(state.toggle_with(&items, vec![0, 0]), Toggled::Activate);
+    /// assert_eq!(state.get_all_opened(), [vec![0]]);
+    /// ```
+    pub fn toggle_with<A>(
+        &mut self,
+        items: &[TreeItem<A>],
+        identifier: TreeIdentifierVec,
+    ) -> Toggled {
+        match error::resolve(items, &identifier) {
+            Err(_) => Toggled::Missing,
+            Ok(item) if !item.has_children() => Toggled::Activate,
+            Ok(_) => {
+                if self.close(&identifier) {
+                    Toggled::Closed
+                } else {
+                    self.open(identifier);
+                    Toggled::Opened
+                }
+            }
+        }
+    }
+
+    /// Toggles the currently selected tree node.
+    /// See also [`toggle_with`](Self::toggle_with)
+    pub fn toggle_selected_with<A>(&mut self, items: &[TreeItem<A>]) -> Toggled {
+        self.toggle_with(items, self.selected())
+    }
+}
+
+#[test]
+fn toggle_with_ignores_leaves_and_missing_nodes() {
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.select(vec![0]);
+    assert_eq!(state.toggle_selected_with(&items), Toggled::Activate);
+    assert_eq!(state.toggle_with(&items, vec![9]), Toggled::Missing);
+    assert_eq!(state.toggle_with(&items, vec![]), Toggled::Missing);
+    assert!(state.get_all_opened().is_empty());
+
+    state.select(vec![1]);
+    assert_eq!(state.toggle_selected_with(&items), Toggled::Opened);
+    assert_eq!(state.toggle_selected_with(&items), Toggled::Closed);
+    assert!(state.get_all_opened().is_empty());
+}
+
+#[test]
+fn toggle_with_opens_unloaded_lazy_nodes() {
+    let items = vec![TreeItem::new_lazy("a")];
+    let mut state = TreeState::default();
+    assert_eq!(state.toggle_with(&items, vec![0]), Toggled::Opened);
+    assert_eq!(state.toggle_with(&items, vec![0]), Toggled::Closed);
+}
@@ -0,0 +1,123 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::text::Text;
+use tui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+use crate::error::resolve;
+use crate::{Tree, TreeState};
+
+impl<'a, A> Tree<'a, A> {
+    /// Show the text returned for the item below the [hovered](TreeState::set_hover) position
+    /// in a bordered box next to its row.
+    ///
+    /// The box is placed below the row or above it when there is no room below and is clipped
+    /// to the area of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::text::Text;
+    /// # use tui::widgets::StatefulWidget;
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("b")];
+    /// let mut state = TreeState::default();
+    /// state.set_hover(Some((2, 0)));
+    ///
+    /// let area = Rect::new(0, 0, 6, 4);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items)
+    ///     .tooltip_for_hover(|elem| Some(Text::raw(elem.to_uppercase())))
+    ///     .render(area, &mut buffer, &mut state);
+    /// assert_eq!(
+    ///     buffer,
+    ///     Buffer::with_lines(vec!["  a   ", "  ┌─┐ ", "  │A│ ", "  └─┘ "])
+    /// );
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn tooltip_for_hover(mut self, tooltip: fn(&A) -> Option<Text<'static>>) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// Render the tooltip of the hovered row of the last render within the area
+    pub(crate) fn render_tooltip(&self, area: Rect, buf: &mut Buffer, state: &TreeState) {
+        let (tooltip, (x, y)) = match (self.tooltip, state.hover) {
+            (Some(tooltip), Some(position)) => (tooltip, position),
+            _ => return,
+        };
+        let row = state.rendered.iter().find(|row| {
+            y >= row.y && y < row.y + row.height && x >= row.x && x < row.x + row.width
+        });
+        let row = match row {
+            Some(row) => row,
+            None => return,
+        };
+        let text = match resolve(&self.items, &row.identifier)
+            .ok()
+            .and_then(|item| tooltip(&item.elem))
+        {
+            Some(text) => text,
+            None => return,
+        };
+
+        let width = (text.width() as u16).saturating_add(2);
+        let height = (text.height() as u16).saturating_add(2);
+        let tooltip_area = tooltip_area((x, row.y), row.height, (width, height), area);
+        Clear.render(tooltip_area, buf);
+        Paragraph::new(text)
+            .style(self.style)
+            .block(Block::default().borders(Borders::ALL))
+            .render(tooltip_area, buf);
+    }
+}
+
+impl TreeState {
+    /// Position of the mouse pointer for the [tooltip](Tree::tooltip_for_hover).
+    /// `None` hides the tooltip.
+    pub fn set_hover(&mut self, position: Option<(u16, u16)>) {
+        self.hover = position;
+    }
+
+    pub const fn hover(&self) -> Option<(u16, u16)> {
+        self.hover
+    }
+}
+
+/// Area of a box of the size below the row starting at `anchor` or above the row when it does
+/// not fit below.
+/// The box is moved left and clipped to stay within the bounds.
+fn tooltip_area(anchor: (u16, u16), row_height: u16, size: (u16, u16), bounds: Rect) -> Rect {
+    let (x, row_y) = anchor;
+    let width = size.0.min(bounds.width);
+    let height = size.1.min(bounds.height);
+    let x = x.min(bounds.right() - width);
+    let below = row_y + row_height;
+    let y = if below + height <= bounds.bottom() {
+        below
+    } else if row_y >= bounds.top() + height {
+        row_y - height
+    } else {
+        bounds.bottom() - height
+    };
+    Rect::new(x, y, width, height)
+}
+
+#[test]
+fn tooltip_area_stays_within_bounds() {
+    let bounds = Rect::new(0, 0, 10, 6);
+    assert_eq!(
+        tooltip_area((2, 0), 1, (4, 3), bounds),
+        Rect::new(2, 1, 4, 3)
+    );
+    assert_eq!(
+        tooltip_area((8, 4), 1, (4, 3), bounds),
+        Rect::new(6, 1, 4, 3)
+    );
+    assert_eq!(
+        tooltip_area((0, 2), 2, (20, 4), bounds),
+        Rect::new(0, 2, 10, 4)
+    );
+}
@@ -0,0 +1,58 @@
+use crate::TreeState;
+
+impl TreeState {
+    /// Close all nodes which were opened automatically to reveal another node, like the
+    /// ancestors of a search match or a bookmark.
+    ///
+    /// Nodes opened explicitly with [`open`](Self::open) stay open, even when they were opened
+    /// automatically before.
+    /// Dismissing a search can restore the expansion the user arranged by hand this way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{SearchScope, TreeItem, TreeState};
+    /// let items = vec![
+    ///     TreeItem::new("a", vec![TreeItem::new_leaf("b")]),
+    ///     TreeItem::new("c", vec![TreeItem::new_leaf("d")]),
+    /// ];
+    /// let mut state = TreeState::default();
+    /// state.open(vec![0]);
+    ///
+    /// state.search_next(&items, "d", SearchScope::EntireTree);
+    /// assert!(state.is_transient(&[1]));
+    ///
+    /// state.collapse_transient();
+    /// assert_eq!(state.get_all_opened(), [vec![0]]);
+    /// ```
+    pub fn collapse_transient(&mut self) {
+        // Linked nodes opened along are transient themselves, others stay open
+        for identifier in std::mem::take(&mut self.transient) {
+            self.close_single(&identifier);
+        }
+    }
+
+    /// Whether the node was opened automatically, see
+    /// [`collapse_transient`](Self::collapse_transient)
+    pub fn is_transient(&self, identifier: &[usize]) -> bool {
+        self.transient.contains(identifier)
+    }
+}
+
+#[test]
+fn explicit_open_is_kept() {
+    let mut state = TreeState::default();
+    state.open_ancestors(&[1, 1, 0]);
+    assert!(state.is_transient(&[1]));
+    assert!(state.is_transient(&[1, 1]));
+
+    state.open(vec![1]);
+    state.collapse_transient();
+    assert_eq!(state.get_all_opened(), [vec![1]]);
+    assert!(!state.is_transient(&[1]));
+
+    state.open_ancestors(&[1, 1, 0]);
+    state.close(&[1, 1]);
+    assert!(!state.is_transient(&[1, 1]));
+    assert!(state.equals_ignoring(&TreeState::default(), &[crate::TreeStateField::Opened]));
+}
@@ -0,0 +1,272 @@
+//! Structural changes of the items which keep the [`TreeState`] in sync
+//!
+//! Identifiers are positions, so moving an item changes the identifiers of it, its siblings in
+//! between and all their descendants.
+//! The operations here change the items and remap every identifier stored within the state in
+//! one go, so opened nodes, the selection, bookmarks and tags stay attached to the same items.
+
+use crate::error::{resolve_mut, TreeError};
+use crate::identifier::{TreeIdentifier, TreeIdentifierVec};
+use crate::{ItemHandle, TreeItem, TreeState};
+
+/// Move the child at `from` of the parent to `to`, shifting the children in between by one.
+///
+/// The identifiers within the state are remapped accordingly.
+/// An empty parent moves a root item.
+///
+/// # Errors
+///
+/// Returns an error when the parent does not exist or either position is out of range.
+/// Neither the items nor the state are changed then.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::tree_ops::rotate_children;
+/// # use tui_tree_widget::{TreeItem, TreeState};
+/// let mut items = vec![
+///     TreeItem::new("a", vec![TreeItem::new_leaf("b")]),
+///     TreeItem::new_leaf("c"),
+///     TreeItem::new_leaf("d"),
+/// ];
+/// let mut state = TreeState::default();
+/// state.open(vec![0]);
+/// state.select(vec![0, 0]);
+///
+/// rotate_children(&mut items, &[], 0, 2, &mut state).unwrap();
+/// assert_eq!(items[2].children().len(), 1);
+/// assert_eq!(state.get_all_opened(), [vec![2]]);
+/// assert_eq!(state.selected(), [2, 0]);
+/// ```
+pub fn rotate_children<A>(
+    items: &mut [TreeItem<A>],
+    parent: TreeIdentifier,
+    from: usize,
+    to: usize,
+    state: &mut TreeState,
+) -> Result<(), TreeError> {
+    let children = if parent.is_empty() {
+        items
+    } else {
+        resolve_mut(items, parent)?.children.as_mut_slice()
+    };
+    let out_of_range = [from, to]
+        .into_iter()
+        .find(|index| *index >= children.len());
+    if let Some(index) = out_of_range {
+        let mut identifier = parent.to_vec();
+        identifier.push(index);
+        return Err(TreeError::OutOfBounds {
+            identifier,
+            depth: parent.len(),
+        });
+    }
+    if from < to {
+        children[from..=to].rotate_left(1);
+    } else {
+        children[to..=from].rotate_right(1);
+    }
+
+    let depth = parent.len();
+    state.remap(&|identifier| {
+        if identifier.len() <= depth || !identifier.starts_with(parent) {
+            return identifier.to_vec();
+        }
+        let index = identifier[depth];
+        let new_index = if index == from {
+            to
+        } else if from < to && from < index && index <= to {
+            index - 1
+        } else if to < from && to <= index && index < from {
+            index + 1
+        } else {
+            index
+        };
+        let mut result = identifier.to_vec();
+        result[depth] = new_index;
+        result
+    });
+    Ok(())
+}
+
+/// Insert the item as the child at `index` of the parent, shifting the following children by one.
+///
+/// The identifiers within the state are remapped accordingly.
+/// An empty parent inserts a root item.
+/// Returns a handle of the inserted item, which follows the item through later changes.
+///
+/// # Errors
+///
+/// Returns an error when the parent does not exist or the index is larger than the amount of
+/// its children.
+/// Neither the items nor the state are changed then.
+///
+/// # Example
+///
+/// ```
+/// # use tui_tree_widget::tree_ops::insert_child;
+/// # use tui_tree_widget::{TreeItem, TreeState};
+/// let mut items = vec![TreeItem::new_leaf("a"), TreeItem::new_leaf("c")];
+/// let mut state = TreeState::default();
+/// state.select(vec![1]);
+///
+/// let handle = insert_child(&mut items, &[], 1, TreeItem::new_leaf("b"), &mut state).unwrap();
+/// assert_eq!(handle.identifier(), [1]);
+/// assert_eq!(state.selected(), [2]);
+///
+/// state
+///     .resolve_handle_mut(&mut items, &handle)
+///     .unwrap()
+///     .add_child(TreeItem::new_leaf("d"));
+/// assert_eq!(items[1].children().len(), 1);
+/// ```
+pub fn insert_child<A>(
+    items: &mut Vec<TreeItem<A>>,
+    parent: TreeIdentifier,
+    index: usize,
+    item: TreeItem<A>,
+    state: &mut TreeState,
+) -> Result<ItemHandle, TreeError> {
+    let children = if parent.is_empty() {
+        items
+    } else {
+        &mut *resolve_mut(items, parent)?.children
+    };
+    if index > children.len() {
+        let mut identifier = parent.to_vec();
+        identifier.push(index);
+        return Err(TreeError::OutOfBounds {
+            identifier,
+            depth: parent.len(),
+        });
+    }
+    children.insert(index, item);
+
+    let depth = parent.len();
+    state.remap(&|identifier| {
+        let mut result = identifier.to_vec();
+        if identifier.len() > depth && identifier.starts_with(parent) && index <= identifier[depth]
+        {
+            result[depth] += 1;
+        }
+        result
+    });
+    let mut identifier = parent.to_vec();
+    identifier.push(index);
+    Ok(state.handle(identifier))
+}
+
+impl TreeState {
+    /// Replace every identifier stored within the state with its mapped identifier
+    pub(crate) fn remap(&mut self, map: &dyn Fn(TreeIdentifier) -> TreeIdentifierVec) {
+        self.remap_retain(&|identifier| Some(map(identifier)));
+    }
+
+    /// Replace every identifier stored within the state with its mapped identifier.
+    /// Entries whose identifier is mapped to `None` are dropped, a selection mapped to `None`
+    /// is kept as it is.
+    /// [`ItemHandle`s](crate::ItemHandle) follow their items, handles mapped to `None` become
+    /// stale.
+    pub(crate) fn remap_retain(
+        &mut self,
+        map: &dyn Fn(TreeIdentifier) -> Option<TreeIdentifierVec>,
+    ) {
+        self.opened = self.opened.iter().filter_map(|o| map(&o)).collect();
+        if let Some(selected) = map(&self.selected) {
+            self.selected = selected;
+        }
+        self.changed = std::mem::take(&mut self.changed)
+            .into_iter()
+            .filter_map(|(identifier, at)| Some((map(&identifier)?, at)))
+            .collect();
+        self.child_orders = std::mem::take(&mut self.child_orders)
+            .into_iter()
+            .filter_map(|(identifier, key)| Some((map(&identifier)?, key)))
+            .collect();
+        self.opening = std::mem::take(&mut self.opening)
+            .into_iter()
+            .filter_map(|(identifier, frame)| Some((map(&identifier)?, frame)))
+            .collect();
+        for tagged in self.tags.values_mut() {
+            *tagged = tagged
+                .iter()
+                .filter_map(|identifier| map(identifier))
+                .collect();
+        }
+        self.tags.retain(|_, tagged| !tagged.is_empty());
+        self.transient = self.transient.iter().filter_map(|t| map(t)).collect();
+        self.bookmarks = self.bookmarks.iter().filter_map(|b| map(b)).collect();
+        self.selected_multi = self.selected_multi.iter().filter_map(|s| map(s)).collect();
+        self.checked = self.checked.iter().filter_map(|c| map(c)).collect();
+        self.expanded_groups = self.expanded_groups.iter().filter_map(|g| map(g)).collect();
+        self.loading = self.loading.iter().filter_map(|l| map(l)).collect();
+        self.handles = std::mem::take(&mut self.handles)
+            .into_iter()
+            .filter_map(|(id, identifier)| Some((id, map(&identifier)?)))
+            .collect();
+        self.links = std::mem::take(&mut self.links)
+            .into_iter()
+            .filter_map(|(identifier, links)| {
+                let links = links.iter().filter_map(|l| map(l)).collect();
+                Some((map(&identifier)?, links))
+            })
+            .collect();
+        #[cfg(feature = "diff")]
+        {
+            self.previous_texts = std::mem::take(&mut self.previous_texts)
+                .into_iter()
+                .filter_map(|(identifier, previous)| Some((map(&identifier)?, previous)))
+                .collect();
+        }
+        // Derived from positions which are outdated now
+        self.style_cache = crate::lazy_style::StyleCache::default();
+        if self.stable_ids.is_some() {
+            self.stable_ids = Some(crate::stable_ids::StableIds::default());
+        }
+        self.invalidate_rows();
+    }
+}
+
+#[test]
+fn rotate_remaps_siblings_in_between() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 2]);
+    state.bookmark_selected();
+    state.select(vec![1, 1, 0]);
+
+    rotate_children(&mut items, &[1], 2, 0, &mut state).unwrap();
+    assert_eq!(items[1].children[0].elem, "g");
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 2]]);
+    assert_eq!(state.selected(), [1, 2, 0]);
+    assert_eq!(state.bookmarks(), [vec![1, 0]]);
+
+    assert!(rotate_children(&mut items, &[1], 0, 3, &mut state).is_err());
+    assert!(rotate_children(&mut items, &[0], 0, 0, &mut state).is_err());
+}
+
+#[test]
+fn insert_shifts_following_siblings() {
+    let mut items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    state.select(vec![1, 1, 0]);
+
+    let handle = insert_child(&mut items, &[1], 1, TreeItem::new_leaf("x"), &mut state).unwrap();
+    assert_eq!(handle.identifier(), [1, 1]);
+    assert_eq!(items[1].children[1].elem, "x");
+    let mut opened = state.get_all_opened();
+    opened.sort();
+    assert_eq!(opened, [vec![1], vec![1, 2]]);
+    assert_eq!(state.selected(), [1, 2, 0]);
+
+    assert!(insert_child(&mut items, &[1], 4, TreeItem::new_leaf("y"), &mut state).is_ok());
+    assert!(insert_child(&mut items, &[1], 6, TreeItem::new_leaf("z"), &mut state).is_err());
+    assert!(state.is_current(&handle));
+    assert_eq!(state.handle_identifier(&handle), Some(&[1, 1][..]));
+}
@@ -0,0 +1,104 @@
+use tui::layout::Rect;
+use unicode_width::UnicodeWidthStr;
+
+use crate::flatten::{flatten_state, with_peek_rows};
+use crate::{indentation, Tree, TreeItemRender, TreeState};
+
+impl<'a, A: TreeItemRender> Tree<'a, A> {
+    /// Width needed to render every visible row without truncation, including the block.
+    ///
+    /// Allows auto sizing a sidebar pane to the widest row.
+    /// The width of a label is taken from [`TreeItemRender::preferred_width`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui_tree_widget::{Tree, TreeItem, TreeState};
+    /// let items = vec![TreeItem::new("src", vec![TreeItem::new_leaf("main.rs")])];
+    /// let mut state = TreeState::default();
+    /// let tree = Tree::new(items);
+    /// assert_eq!(tree.required_width(&state), 5);
+    ///
+    /// state.open(vec![0]);
+    /// assert_eq!(tree.required_width(&state), 11);
+    /// ```
+    pub fn required_width(&self, state: &TreeState) -> usize {
+        let visible = flatten_state(state, &self.items);
+        let (visible, _) = with_peek_rows(visible, state, self.peek_children);
+
+        let fixed_width = self.fixed_width(!state.selected.is_empty());
+
+        let rows = visible.iter().map(|item| {
+            let elem = &item.item.elem;
+            let label_width = elem
+                .preferred_width()
+                .unwrap_or_else(|| elem.as_text().width());
+            let suffix_width = elem.suffix().map_or(0, |suffix| suffix.width() + 1);
+            // Indentation followed by the symbol and a space
+            let prefix_width = self.prefix_width(item.depth());
+            fixed_width + prefix_width + label_width + suffix_width
+        });
+        let headers = self.root_headers.iter().map(|(title, _)| title.width());
+        let separator = self
+            .root_separator
+            .as_ref()
+            .map(|separator| separator.width());
+        let content_width = rows
+            .chain(headers)
+            .chain(separator)
+            .max()
+            .unwrap_or_default();
+
+        // Borders and padding of the block
+        let block_width = self.block.as_ref().map_or(0, |block| {
+            let area = Rect::new(0, 0, 100, 100);
+            usize::from(area.width - block.inner(area).width)
+        });
+        content_width + block_width
+    }
+
+    /// Width of the columns in front of the indentation, of the checkbox and of the value bar
+    pub(crate) fn fixed_width(&self, has_selection: bool) -> usize {
+        let highlight_symbol_width = if has_selection {
+            self.highlight_symbol.map_or(0, UnicodeWidthStr::width)
+        } else {
+            0
+        };
+        highlight_symbol_width
+            + self.tag_styles.len()
+            + self.bookmark_symbol.map_or(0, |(symbol, _)| symbol.width())
+            + self.marked_symbol.map_or(0, UnicodeWidthStr::width)
+            + self.value_bars.map_or(0, |(width, _)| usize::from(width))
+            + self.check_symbols.map_or(0, |symbols| symbols.width())
+    }
+
+    /// Columns left for the label of a row at the depth within the width of the area
+    pub(crate) fn label_width(&self, area_width: u16, has_selection: bool, depth: usize) -> usize {
+        let prefix_width = self.prefix_width(depth);
+        usize::from(area_width).saturating_sub(self.fixed_width(has_selection) + prefix_width)
+    }
+
+    /// Indentation followed by the node symbol and a space
+    pub(crate) fn prefix_width(&self, depth: usize) -> usize {
+        indentation(depth, self.indent_width, self.max_indent_cols).width()
+            + self.node_symbol_width()
+            + 1
+    }
+}
+
+#[test]
+fn required_width_counts_block_and_highlight_symbol() {
+    use tui::widgets::{Block, Borders};
+
+    let items = crate::flatten::get_example_tree_items();
+    let mut state = TreeState::default();
+    state.open(vec![1]);
+    state.open(vec![1, 1]);
+    let tree = Tree::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .highlight_symbol(">> ");
+    assert_eq!(tree.required_width(&state), 9);
+
+    state.select(vec![0]);
+    assert_eq!(tree.required_width(&state), 12);
+}
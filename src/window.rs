@@ -0,0 +1,82 @@
+/// Cumulative heights of the rows of a flattened tree
+///
+/// Allows computing which rows fit into the viewport in `O(log n)`.
+pub(crate) struct Heights {
+    /// `prefix[i]` is the sum of the heights of all rows before row `i`
+    prefix: Vec<usize>,
+}
+
+impl Heights {
+    pub fn new<I>(heights: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let heights = heights.into_iter();
+        let mut prefix = Vec::with_capacity(heights.size_hint().0 + 1);
+        let mut sum = 0;
+        prefix.push(sum);
+        for height in heights {
+            sum += height;
+            prefix.push(sum);
+        }
+        Self { prefix }
+    }
+
+    /// Amount of rows
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    /// Height of the row at the index
+    pub fn get(&self, index: usize) -> usize {
+        self.prefix[index + 1] - self.prefix[index]
+    }
+
+    /// End (exclusive) of the rows fitting into `available` lines when starting at `start`
+    pub fn end_from(&self, start: usize, available: usize) -> usize {
+        let limit = self.prefix[start] + available;
+        self.prefix.partition_point(|sum| *sum <= limit) - 1
+    }
+
+    /// Start of the rows fitting into `available` lines when ending at `end` (exclusive)
+    pub fn start_until(&self, end: usize, available: usize) -> usize {
+        let limit = self.prefix[end].saturating_sub(available);
+        self.prefix.partition_point(|sum| *sum < limit)
+    }
+
+    /// Rows `(start, end)` to render starting at `offset` while keeping `selected` visible
+    ///
+    /// A selected row taller than `available` is the only row and has to be clipped.
+    pub fn window(&self, offset: usize, selected: usize, available: usize) -> (usize, usize) {
+        let start = offset.min(selected);
+        let end = self.end_from(start, available);
+        if selected < end {
+            (start, end)
+        } else {
+            let end = (selected + 1).min(self.len());
+            (self.start_until(end, available).min(selected), end)
+        }
+    }
+}
+
+#[test]
+fn window_keeps_offset_when_selection_is_visible() {
+    let heights = Heights::new(vec![1, 2, 1, 3, 1]);
+    assert_eq!(heights.window(0, 0, 4), (0, 3));
+    assert_eq!(heights.window(1, 2, 4), (1, 3));
+}
+
+#[test]
+fn window_scrolls_to_selection() {
+    let heights = Heights::new(vec![1, 2, 1, 3, 1]);
+    assert_eq!(heights.window(0, 3, 4), (2, 4));
+    assert_eq!(heights.window(0, 4, 4), (3, 5));
+    assert_eq!(heights.window(4, 0, 4), (0, 3));
+}
+
+#[test]
+fn window_with_item_taller_than_available() {
+    let heights = Heights::new(vec![1, 5, 1]);
+    assert_eq!(heights.window(0, 1, 3), (1, 2));
+    assert_eq!(heights.window(2, 1, 3), (1, 2));
+}
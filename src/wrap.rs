@@ -0,0 +1,102 @@
+use tui::text::{Span, Spans, Text};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::Tree;
+
+impl<'a, A> Tree<'a, A> {
+    /// Wrap lines of labels which are wider than the space left of them onto continuation lines.
+    ///
+    /// Every continuation line starts with the prefix like `"\u{21aa} "` right below the start of
+    /// the label so it is not confused with a child item.
+    /// Lines are wrapped at any character, the suffix is not taken into account.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// # use tui_tree_widget::{Tree, TreeItem};
+    /// let items = vec![TreeItem::new_leaf("abcdef")];
+    ///
+    /// let area = Rect::new(0, 0, 6, 3);
+    /// let mut buffer = Buffer::empty(area);
+    /// Tree::new(items).wrap_labels("> ").render(area, &mut buffer);
+    /// assert_eq!(buffer, Buffer::with_lines(vec!["  abcd", "  > ef", "      "]));
+    /// ```
+    #[must_use]
+    pub const fn wrap_labels(mut self, continuation: &'a str) -> Self {
+        self.wrap_continuation = Some(continuation);
+        self
+    }
+}
+
+/// Split every line of the text into lines of at most `width` columns.
+/// Continuation lines start with the prefix unless it leaves no room for the text.
+/// Without any room the text is kept as it is.
+pub(crate) fn wrap_text<'t>(text: Text<'t>, width: usize, continuation: &'t str) -> Text<'t> {
+    if width == 0 {
+        return text;
+    }
+    let continuation = if continuation.width() < width {
+        continuation
+    } else {
+        ""
+    };
+    let mut lines = Vec::with_capacity(text.lines.len());
+    for line in text.lines {
+        if line.width() <= width {
+            lines.push(line);
+            continue;
+        }
+        let mut current = Vec::new();
+        let mut current_width = 0;
+        // A line gets at least one character even when it is wider than the width
+        let mut has_content = false;
+        for span in line.0 {
+            let mut content = String::new();
+            for c in span.content.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if current_width + char_width > width && has_content {
+                    if !content.is_empty() {
+                        current.push(Span::styled(std::mem::take(&mut content), span.style));
+                    }
+                    lines.push(Spans::from(std::mem::take(&mut current)));
+                    if !continuation.is_empty() {
+                        current.push(Span::raw(continuation));
+                    }
+                    current_width = continuation.width();
+                }
+                content.push(c);
+                current_width += char_width;
+                has_content = true;
+            }
+            if !content.is_empty() {
+                current.push(Span::styled(content, span.style));
+            }
+        }
+        lines.push(Spans::from(current));
+    }
+    Text::from(lines)
+}
+
+#[test]
+fn wrap_keeps_styles_of_spans() {
+    use tui::style::{Color, Style};
+
+    let red = Style::default().fg(Color::Red);
+    let text = Text::from(vec![
+        Spans::from(vec![Span::raw("ab"), Span::styled("cde", red)]),
+        Spans::from("f"),
+    ]);
+    let wrapped = wrap_text(text, 3, "-");
+    let expected = Text::from(vec![
+        Spans::from(vec![Span::raw("ab"), Span::styled("c", red)]),
+        Spans::from(vec![Span::raw("-"), Span::styled("de", red)]),
+        Spans::from("f"),
+    ]);
+    assert_eq!(wrapped, expected);
+
+    // Without room for the prefix the lines are wrapped without it
+    assert_eq!(wrap_text(Text::raw("abc"), 1, "-"), Text::raw("a\nb\nc"));
+}